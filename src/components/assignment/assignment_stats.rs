@@ -7,7 +7,7 @@ pub fn AssignmentStats(assignments_signal: ReadOnlySignal<Option<AssignmentPlan>
     // Memoize expensive stats calculations - only recalculates when assignments change
     let stats = use_memo(move || {
         let Some(assignments) = assignments_signal() else {
-            return (0, 0, 0, 0, 0, 100);
+            return (0, 0, 0, 0, 0, 0, 100);
         };
 
         let assigned_selres_count = assignments
@@ -19,10 +19,10 @@ pub fn AssignmentStats(assignments_signal: ReadOnlySignal<Option<AssignmentPlan>
         let assigned_aw_count = assignments
             .assignments
             .iter()
-            .filter(|assignment| assignment.person.raterank.starts_with("AW"))
+            .filter(|assignment| assignment.person.raterank.is_aw())
             .count();
 
-        let total_positions = assignments.assignments.len() + assignments.unfilled_positions.len();
+        let total_positions = assignments.assignments.len() + assignments.total_unfilled();
         let fill_percentage = if total_positions > 0 {
             (assignments.assignments.len() as f32 / total_positions as f32 * 100.0).round() as u32
         } else {
@@ -32,15 +32,23 @@ pub fn AssignmentStats(assignments_signal: ReadOnlySignal<Option<AssignmentPlan>
         (
             assignments.assignments.len(),
             assignments.unassigned_people.len(),
-            assignments.unfilled_positions.len(),
+            assignments.total_unfilled(),
+            assignments.unfilled_mandatory_positions.len(),
             assigned_selres_count,
             assigned_aw_count,
             fill_percentage,
         )
     });
 
-    let (total_assigned, total_unassigned, total_unfilled, total_selres_used, total_aw_used, fill_percentage) =
-        stats();
+    let (
+        total_assigned,
+        total_unassigned,
+        total_unfilled,
+        total_critical,
+        total_selres_used,
+        total_aw_used,
+        fill_percentage,
+    ) = stats();
 
     rsx! {
         div {
@@ -60,6 +68,9 @@ pub fn AssignmentStats(assignments_signal: ReadOnlySignal<Option<AssignmentPlan>
                     class: "stat-card-unfilled",
                     h3 { class: "stat-number-red", "{total_unfilled}" }
                     p { class: "stat-label-red", "Unfilled Positions" }
+                    if total_critical > 0 {
+                        p { class: "text-xs font-bold text-red-700", "🚨 {total_critical} mandatory" }
+                    }
                 }
                 div {
                     class: "stat-card-selres",
@@ -0,0 +1,165 @@
+use std::collections::BTreeSet;
+
+use dioxus::prelude::*;
+
+use crate::engine::projection::ProjectionPoint;
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 220.0;
+const CHART_PADDING: f64 = 32.0;
+
+#[component]
+pub fn ManningProjection(points_signal: ReadOnlySignal<Vec<ProjectionPoint>>) -> Element {
+    let points = points_signal();
+    if points.is_empty() {
+        return rsx! {
+            div {
+                class: "section-card",
+                p { class: "text-sm text-gray-600", "No projection available for the current inputs." }
+            }
+        };
+    }
+
+    // x by month, fill rate on a fixed 0-100 scale, SELRES bars scaled to the busiest month
+    let step = (CHART_WIDTH - 2.0 * CHART_PADDING) / (points.len().max(2) - 1) as f64;
+    let plot_height = CHART_HEIGHT - 2.0 * CHART_PADDING;
+    let x = |i: usize| CHART_PADDING + i as f64 * step;
+    let y = |rate: f64| CHART_PADDING + plot_height * (1.0 - rate / 100.0);
+    let max_selres = points
+        .iter()
+        .map(|p| p.selres_used)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let fill_line = points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| format!("{:.1},{:.1}", x(i), y(p.fill_rate())))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // every (team, qualification) that is short in any month
+    let gaps: BTreeSet<(String, String)> = points
+        .iter()
+        .flat_map(|p| p.unfilled.keys().cloned())
+        .collect();
+
+    rsx! {
+        div {
+            class: "section-card",
+            h2 { class: "section-title", "📈 12-Month Manning Projection" }
+            p {
+                class: "text-sm text-gray-600 mb-3",
+                "Fill rate (line) and SELRES used (bars) as people reach their PRD, re-solved each month."
+            }
+
+            svg {
+                class: "w-full h-auto",
+                view_box: "0 0 {CHART_WIDTH} {CHART_HEIGHT}",
+                for rate in [0.0, 50.0, 100.0] {
+                    line {
+                        key: "grid-{rate}",
+                        x1: "{CHART_PADDING}",
+                        x2: "{CHART_WIDTH - CHART_PADDING}",
+                        y1: "{y(rate)}",
+                        y2: "{y(rate)}",
+                        stroke: "#e5e7eb",
+                    }
+                    text {
+                        key: "label-{rate}",
+                        x: "4",
+                        y: "{y(rate) + 4.0}",
+                        font_size: "10",
+                        fill: "#6b7280",
+                        "{rate}%"
+                    }
+                }
+                for (i, point) in points.iter().enumerate() {
+                    rect {
+                        key: "selres-{i}",
+                        x: "{x(i) - step * 0.2}",
+                        y: "{CHART_HEIGHT - CHART_PADDING - plot_height * point.selres_used as f64 / max_selres as f64 * 0.5}",
+                        width: "{step * 0.4}",
+                        height: "{plot_height * point.selres_used as f64 / max_selres as f64 * 0.5}",
+                        fill: "#fde68a",
+                    }
+                    text {
+                        key: "month-{i}",
+                        x: "{x(i)}",
+                        y: "{CHART_HEIGHT - 8.0}",
+                        font_size: "10",
+                        fill: "#6b7280",
+                        text_anchor: "middle",
+                        "{point.date.format(\"%b %y\")}"
+                    }
+                }
+                polyline {
+                    points: "{fill_line}",
+                    fill: "none",
+                    stroke: "#4f46e5",
+                    stroke_width: "2",
+                }
+                for (i, point) in points.iter().enumerate() {
+                    circle {
+                        key: "fill-{i}",
+                        cx: "{x(i)}",
+                        cy: "{y(point.fill_rate())}",
+                        r: "3",
+                        fill: "#4f46e5",
+                        title { "{point.date}: {point.positions_filled}/{point.total_positions} filled, {point.selres_used} SELRES" }
+                    }
+                }
+            }
+
+            if !gaps.is_empty() {
+                div {
+                    class: "table-wrapper mt-4",
+                    table {
+                        class: "results-table",
+                        thead {
+                            class: "table-header",
+                            tr {
+                                th { class: "table-header-cell", "Team" }
+                                th { class: "table-header-cell", "Qualification" }
+                                for point in points.iter() {
+                                    th { key: "{point.date}", class: "table-header-cell", "{point.date.format(\"%b\")}" }
+                                }
+                            }
+                        }
+                        tbody {
+                            for (team, qualification) in gaps.iter() {
+                                tr {
+                                    key: "{team}-{qualification}",
+                                    class: "table-row",
+                                    td { class: "table-cell", "{team}" }
+                                    td { class: "table-cell", "{qualification}" }
+                                    for point in points.iter() {
+                                        match point.unfilled.get(&(team.clone(), qualification.clone())) {
+                                            Some(count) => rsx! {
+                                                td { key: "{point.date}", class: "table-cell text-red-600 font-semibold", "{count}" }
+                                            },
+                                            None => rsx! {
+                                                td { key: "{point.date}", class: "table-cell text-gray-300", "·" }
+                                            },
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            ul {
+                class: "mt-3 text-sm text-gray-700 space-y-0.5",
+                for point in points.iter().filter(|p| !p.losses.is_empty()) {
+                    li {
+                        key: "{point.date}",
+                        span { class: "font-medium", "{point.date.format(\"%b %Y\")}: " }
+                        "{point.losses.len()} lost to PRD - {point.losses.join(\", \")}"
+                    }
+                }
+            }
+        }
+    }
+}
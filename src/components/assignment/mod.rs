@@ -15,3 +15,27 @@ pub use unassigned_table::UnassignedTable;
 
 mod assignment_stats;
 pub use assignment_stats::AssignmentStats;
+
+mod solver_diagnostics;
+pub use solver_diagnostics::SolverDiagnostics;
+
+mod manning_projection;
+pub use manning_projection::ManningProjection;
+
+mod pairing_rules;
+pub use pairing_rules::PairingRules;
+
+mod pending_qualifications;
+pub use pending_qualifications::PendingQualifications;
+
+mod shadow_prices;
+pub use shadow_prices::ShadowPriceTable;
+
+mod training_plan;
+pub use training_plan::TrainingPlan;
+
+mod scenario_sandbox;
+pub use scenario_sandbox::ScenarioSandbox;
+
+mod plan_switcher;
+pub use plan_switcher::{PlanSummary, PlanSwitcher};
@@ -0,0 +1,112 @@
+use dioxus::prelude::*;
+
+use crate::engine::pairing::{PairingConstraint, PairingKind};
+use crate::views::results::AssignmentUIContext;
+
+#[component]
+pub fn PairingRules(
+    pairings: Vec<PairingConstraint>,
+    unsatisfied_signal: ReadOnlySignal<Vec<PairingConstraint>>,
+    on_add: Callback<PairingConstraint>,
+    on_remove: Callback<PairingConstraint>,
+) -> Element {
+    let ui_context = use_context::<AssignmentUIContext>();
+    // Form state for the rule being added
+    let mut first = use_signal(String::new);
+    let mut second = use_signal(String::new);
+    let mut kind = use_signal(|| PairingKind::Together);
+
+    let people = (ui_context.people)();
+    let unsatisfied = unsatisfied_signal();
+
+    let add_pairing = move |_| {
+        if first().is_empty() || second().is_empty() || first() == second() {
+            return;
+        }
+        on_add.call(PairingConstraint {
+            first: first(),
+            second: second(),
+            kind: kind(),
+        });
+        first.set(String::new());
+        second.set(String::new());
+    };
+
+    rsx! {
+        div {
+            class: "section-card",
+            h2 { class: "section-title", "🤝 Pairing Rules" }
+            p {
+                class: "text-sm text-gray-600 mb-2",
+                "Keep two people on the same team, or make sure they never share one."
+            }
+
+            div {
+                class: "flex flex-wrap items-center gap-2 text-sm",
+                select {
+                    class: "border border-gray-300 rounded px-2 py-1",
+                    value: "{first}",
+                    onchange: move |evt| first.set(evt.value()),
+                    option { value: "", "Person..." }
+                    for person in people.iter() {
+                        option { key: "{person.name}", value: "{person.name}", "{person.name}" }
+                    }
+                }
+                select {
+                    class: "border border-gray-300 rounded px-2 py-1",
+                    value: if kind() == PairingKind::Together { "together" } else { "apart" },
+                    onchange: move |evt| {
+                        kind.set(if evt.value() == "apart" { PairingKind::Apart } else { PairingKind::Together });
+                    },
+                    option { value: "together", "together with" }
+                    option { value: "apart", "apart from" }
+                }
+                select {
+                    class: "border border-gray-300 rounded px-2 py-1",
+                    value: "{second}",
+                    onchange: move |evt| second.set(evt.value()),
+                    option { value: "", "Person..." }
+                    for person in people.iter().filter(|p| p.name != first()) {
+                        option { key: "{person.name}", value: "{person.name}", "{person.name}" }
+                    }
+                }
+                button {
+                    class: "px-2 py-1 bg-indigo-600 text-white rounded text-xs hover:bg-indigo-700 disabled:opacity-50",
+                    disabled: first().is_empty() || second().is_empty() || first() == second(),
+                    onclick: add_pairing,
+                    "Add"
+                }
+            }
+
+            if !pairings.is_empty() {
+                ul {
+                    class: "mt-3 text-sm text-gray-700 space-y-1",
+                    for pairing in pairings.iter() {
+                        li {
+                            key: "{pairing}",
+                            class: "flex items-center gap-2",
+                            if unsatisfied.contains(pairing) {
+                                span {
+                                    class: "text-amber-600",
+                                    title: "No plan keeps this rule without leaving more rules broken",
+                                    "⚠"
+                                }
+                            } else {
+                                span { class: "text-green-600", "✓" }
+                            }
+                            "{pairing}"
+                            button {
+                                class: "text-gray-400 hover:text-red-600",
+                                onclick: {
+                                    let pairing = pairing.clone();
+                                    move |_| on_remove.call(pairing.clone())
+                                },
+                                "✕"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
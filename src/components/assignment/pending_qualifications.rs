@@ -0,0 +1,133 @@
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+
+use crate::engine::person::PendingQualification;
+use crate::views::results::AssignmentUIContext;
+
+#[component]
+pub fn PendingQualifications(
+    pending: Vec<PendingQualification>,
+    /// Every qualification the Requirements file asks for
+    qualifications: Vec<String>,
+    on_add: Callback<PendingQualification>,
+    on_remove: Callback<PendingQualification>,
+    /// Contents of a Name, Qual, Completion Date CSV
+    on_import: Callback<String>,
+) -> Element {
+    let ui_context = use_context::<AssignmentUIContext>();
+    // Form state for the qualification being added
+    let mut person_name = use_signal(String::new);
+    let mut qualification = use_signal(String::new);
+    let mut completion_date = use_signal(|| None::<NaiveDate>);
+
+    let people = (ui_context.people)();
+    let is_complete = move || {
+        !person_name().is_empty() && !qualification().is_empty() && completion_date().is_some()
+    };
+
+    let add_pending = move |_| {
+        let Some(date) = completion_date() else {
+            return;
+        };
+        if person_name().is_empty() || qualification().is_empty() {
+            return;
+        }
+        on_add.call(PendingQualification {
+            person_name: person_name(),
+            qualification: qualification(),
+            completion_date: date,
+        });
+        person_name.set(String::new());
+        qualification.set(String::new());
+    };
+
+    rsx! {
+        div {
+            class: "section-card",
+            h2 { class: "section-title", "🎓 Quals in Training" }
+            p {
+                class: "text-sm text-gray-600 mb-2",
+                "Count a qualification from its expected completion date, for analysis dates on or after it."
+            }
+
+            div {
+                class: "flex flex-wrap items-center gap-2 text-sm",
+                select {
+                    class: "border border-gray-300 rounded px-2 py-1",
+                    value: "{person_name}",
+                    onchange: move |evt| person_name.set(evt.value()),
+                    option { value: "", "Person..." }
+                    for person in people.iter() {
+                        option { key: "{person.name}", value: "{person.name}", "{person.name}" }
+                    }
+                }
+                select {
+                    class: "border border-gray-300 rounded px-2 py-1",
+                    value: "{qualification}",
+                    onchange: move |evt| qualification.set(evt.value()),
+                    option { value: "", "Qualification..." }
+                    for qual in qualifications.iter() {
+                        option { key: "{qual}", value: "{qual}", "{qual}" }
+                    }
+                }
+                span { "done by" }
+                input {
+                    r#type: "date",
+                    class: "border border-gray-300 rounded px-2 py-1",
+                    onchange: move |evt| {
+                        completion_date.set(NaiveDate::parse_from_str(&evt.value(), "%Y-%m-%d").ok());
+                    },
+                }
+                button {
+                    class: "px-2 py-1 bg-indigo-600 text-white rounded text-xs hover:bg-indigo-700 disabled:opacity-50",
+                    disabled: !is_complete(),
+                    onclick: add_pending,
+                    "Add"
+                }
+
+                // file picker for a Name, Qual, Completion Date CSV
+                label {
+                    class: "px-2 py-1 bg-gray-200 text-gray-700 rounded text-xs hover:bg-gray-300 cursor-pointer",
+                    "📄 Import CSV"
+                    input {
+                        r#type: "file",
+                        accept: ".csv",
+                        style: "display: none;",
+                        onchange: move |evt| {
+                            spawn(async move {
+                                if let Some(file_engine) = evt.files() {
+                                    if let Some(fname) = file_engine.files().first() {
+                                        if let Some(csv_content) = file_engine.read_file_to_string(fname).await {
+                                            on_import.call(csv_content);
+                                        }
+                                    }
+                                }
+                            });
+                        }
+                    }
+                }
+            }
+
+            if !pending.is_empty() {
+                ul {
+                    class: "mt-3 text-sm text-gray-700 space-y-1",
+                    for item in pending.iter() {
+                        li {
+                            key: "{item}",
+                            class: "flex items-center gap-2",
+                            "{item}"
+                            button {
+                                class: "text-gray-400 hover:text-red-600",
+                                onclick: {
+                                    let item = item.clone();
+                                    move |_| on_remove.call(item.clone())
+                                },
+                                "✕"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
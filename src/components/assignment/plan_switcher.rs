@@ -0,0 +1,105 @@
+use dioxus::prelude::*;
+
+use crate::engine::plan_diff::PlanDiff;
+
+/// Headline numbers for one ranked plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanSummary {
+    pub positions_filled: usize,
+    pub selres_used: usize,
+    pub cost: i64,
+}
+
+fn plan_label(index: usize) -> String {
+    format!("Plan {}", (b'A' + index as u8) as char)
+}
+
+#[component]
+pub fn PlanSwitcher(
+    plan_summaries_signal: ReadOnlySignal<Vec<PlanSummary>>,
+    selected_plan_signal: Signal<usize>,
+    plan_diff_signal: ReadOnlySignal<PlanDiff>,
+    on_find_alternatives: EventHandler<()>,
+) -> Element {
+    let plan_summaries = plan_summaries_signal();
+    let selected_plan = selected_plan_signal();
+    let plan_diff = plan_diff_signal();
+
+    rsx! {
+        div {
+            class: "section-card",
+            div {
+                class: "flex items-center gap-2 mb-2",
+                h2 { class: "section-title", "Alternative Plans" }
+                if plan_summaries.is_empty() {
+                    button {
+                        class: "ml-auto px-2 py-1 bg-indigo-600 text-white rounded text-xs hover:bg-indigo-700",
+                        onclick: move |_| on_find_alternatives.call(()),
+                        "🔀 Find Alternative Plans"
+                    }
+                }
+            }
+
+            if plan_summaries.is_empty() {
+                p {
+                    class: "text-sm text-gray-600",
+                    "Rank the next-best plans for the current roster, locks and analysis date."
+                }
+            } else {
+                div {
+                    class: "flex flex-wrap gap-2",
+                    for (index, summary) in plan_summaries.iter().enumerate() {
+                        button {
+                            key: "{index}",
+                            class: if index == selected_plan {
+                                "px-3 py-1.5 bg-indigo-600 text-white rounded text-left text-xs"
+                            } else {
+                                "px-3 py-1.5 bg-gray-100 text-gray-700 rounded text-left text-xs hover:bg-gray-200"
+                            },
+                            onclick: move |_| selected_plan_signal.set(index),
+                            div {
+                                class: "font-bold text-sm",
+                                "{plan_label(index)}"
+                                if index == 0 {
+                                    span { class: "ml-1 font-normal", "(best)" }
+                                }
+                            }
+                            div { "{summary.positions_filled} filled · {summary.selres_used} SELRES" }
+                            if index > 0 {
+                                div { "Cost +{summary.cost - plan_summaries[0].cost}" }
+                            }
+                        }
+                    }
+                }
+
+                if selected_plan > 0 {
+                    div {
+                        class: "mt-3",
+                        h3 {
+                            class: "text-sm font-semibold text-gray-700 mb-1",
+                            "What differs from Plan A"
+                        }
+                        if plan_diff.is_empty() {
+                            p {
+                                class: "text-sm text-gray-600",
+                                "Same people on the same teams - only which instances are mandatory differs."
+                            }
+                        }
+                        ul {
+                            class: "text-sm text-gray-700 space-y-0.5",
+                            for person_move in plan_diff.moves.iter() {
+                                li {
+                                    key: "{person_move.person_name}",
+                                    span { class: "font-medium", "{person_move.person_name}: " }
+                                    {person_move.from.as_ref().map_or("Unassigned".to_string(), |slot| slot.to_string())}
+                                    " → "
+                                    {person_move.to.as_ref().map_or("Unassigned".to_string(), |slot| slot.to_string())}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,9 +3,10 @@ use itertools::Itertools;
 #[cfg(target_arch = "wasm32")]
 use {wasm_bindgen, web_sys};
 
-use crate::components::InteractionMode;
+use crate::components::CostBreakdownTable;
 use crate::engine::{
-    assignment::AssignmentPlan,
+    assignment::{AssignmentExclusion, AssignmentPlan},
+    cost_policy::{CostBreakdown, CostPolicy},
     person::{DutyStatus, Person},
     team::Position,
 };
@@ -18,12 +19,15 @@ pub fn RolePopup(
     team_name: String,
     current_person: Option<Person>,
     assignments_signal: ReadOnlySignal<Option<AssignmentPlan>>,
+    analysis_date_signal: ReadOnlySignal<chrono::NaiveDate>,
+    cost_policy_signal: ReadOnlySignal<CostPolicy>,
 
     // Positioning
     popup_position: (f64, f64),
 
     // Actions
     on_swap: Callback<String>,
+    on_exclude: Callback<AssignmentExclusion>,
     on_close: Callback<()>,
 ) -> Element {
     let ui_context = use_context::<AssignmentUIContext>();
@@ -31,9 +35,13 @@ pub fn RolePopup(
     // Get people who can fill this role with their current assignments
     let eligible_people = use_memo({
         let position_clone = position.clone();
+        let team_name_clone = team_name.clone();
         let current_person_clone = current_person.clone();
         move || {
             let people = (ui_context.people)();
+            let exclusions = (ui_context.exclusions)();
+            let analysis_date = analysis_date_signal();
+            let cost_policy = cost_policy_signal();
             let Some(assignments_plan) = assignments_signal() else {
                 return Vec::new();
             };
@@ -41,10 +49,16 @@ pub fn RolePopup(
             people
                 .iter()
                 .filter(|person| {
-                    // Must have the required qualification
-                    person.qualifications.contains(&position_clone.qualification) &&
+                    // Must hold the required qualification, unexpired
+                    person.is_qualified(&position_clone.qualification, analysis_date) &&
+                    // and meet the paygrade and rate limits
+                    position_clone.admits(person) &&
                     // Don't include the person currently in this role
-                    current_person_clone.as_ref().map_or(true, |cp| cp.name != person.name)
+                    current_person_clone.as_ref().is_none_or(|cp| cp.name != person.name) &&
+                    // Nor anyone excluded from it
+                    !exclusions.iter().any(|e| {
+                        e.excludes(&person.name, &team_name_clone, &position_clone.qualification)
+                    })
                 })
                 .map(|person| {
                     // Find current assignment for this person
@@ -53,9 +67,16 @@ pub fn RolePopup(
                         .iter()
                         .find(|assignment| assignment.person.name == person.name);
 
-                    (person.clone(), current_assignment.cloned())
+                    // what the solver would charge to put them here instead
+                    let cost = cost_policy.assignment_cost(
+                        person,
+                        &position_clone.qualification,
+                        analysis_date,
+                    );
+
+                    (person.clone(), current_assignment.cloned(), cost)
                 })
-                .sorted_by_key(|(person, assignment)| {
+                .sorted_by_key(|(person, assignment, _)| {
                     // Sort by: unassigned first, then TAR first, then by PRD date
                     (
                         assignment.is_some(), // unassigned people (None) sort first
@@ -69,6 +90,18 @@ pub fn RolePopup(
         }
     });
 
+    // Breakdown of what the current occupant costs in this role
+    let current_cost = use_memo({
+        let role_id = position.role_id(&team_name);
+        move || -> Option<CostBreakdown> {
+            assignments_signal()?
+                .assignments
+                .iter()
+                .find(|a| a.role_id() == role_id)
+                .map(|a| a.cost)
+        }
+    });
+
     // Calculate popup position with edge detection
     let (x, y) = popup_position;
 
@@ -95,9 +128,52 @@ pub fn RolePopup(
             }
             if let Some(ref person) = current_person {
                 div {
-                    class: "text-xs text-blue-600 mb-2",
+                    class: "text-xs text-blue-600 mb-1",
                     "Current: {person.name} ({person.raterank})"
                 }
+                if let Some(cost) = current_cost() {
+                    div {
+                        class: "mb-2",
+                        CostBreakdownTable { cost }
+                    }
+                }
+                // Exclusions free the role and keep the person off it on every re-solve
+                div {
+                    class: "flex items-center gap-1 text-xs text-gray-500 mb-2",
+                    "Keep off:"
+                    button {
+                        class: "px-2 py-0.5 border border-red-200 text-red-700 rounded hover:bg-red-50",
+                        onclick: {
+                            let person_name = person.name.clone();
+                            let team_name = team_name.clone();
+                            move |_| {
+                                on_exclude.call(AssignmentExclusion {
+                                    person_name: person_name.clone(),
+                                    team_name: Some(team_name.clone()),
+                                    qualification: None,
+                                });
+                                on_close.call(());
+                            }
+                        },
+                        "{team_name}"
+                    }
+                    button {
+                        class: "px-2 py-0.5 border border-red-200 text-red-700 rounded hover:bg-red-50",
+                        onclick: {
+                            let person_name = person.name.clone();
+                            let qualification = position.qualification.clone();
+                            move |_| {
+                                on_exclude.call(AssignmentExclusion {
+                                    person_name: person_name.clone(),
+                                    team_name: None,
+                                    qualification: Some(qualification.clone()),
+                                });
+                                on_close.call(());
+                            }
+                        },
+                        "{position.qualification}"
+                    }
+                }
             } else {
                 div {
                     class: "text-xs text-gray-500 mb-2 italic",
@@ -118,7 +194,7 @@ pub fn RolePopup(
                 }
                 div {
                     class: "space-y-0.5 mb-3 max-h-32 overflow-y-auto",
-                    for (person, assignment) in eligible_people_list.iter() {
+                    for (person, assignment, cost) in eligible_people_list.iter() {
                         div {
                             key: "{person.name}",
                             class: "flex items-center justify-between p-1.5 rounded hover:bg-gray-50 border border-gray-100",
@@ -133,6 +209,11 @@ pub fn RolePopup(
                                         " • {prd.format(\"%m/%y\")}"
                                     }
                                 }
+                                div {
+                                    class: "text-xs text-gray-500 font-mono",
+                                    title: "{cost_summary(cost)}",
+                                    "cost {cost.total()}"
+                                }
                                 if let Some(ref assignment) = assignment {
                                     div {
                                         class: "text-xs text-blue-600",
@@ -146,6 +227,24 @@ pub fn RolePopup(
                                 }
                             }
 
+                            // Exclude button
+                            button {
+                                class: "ml-2 px-1.5 py-0.5 text-red-600 text-xs rounded hover:bg-red-50",
+                                title: "Never use as {position.qualification}",
+                                onclick: {
+                                    let person_name = person.name.clone();
+                                    let qualification = position.qualification.clone();
+                                    move |_| {
+                                        on_exclude.call(AssignmentExclusion {
+                                            person_name: person_name.clone(),
+                                            team_name: None,
+                                            qualification: Some(qualification.clone()),
+                                        });
+                                    }
+                                },
+                                "🚫"
+                            }
+
                             // Swap button
                             button {
                                 class: "ml-2 px-2 py-0.5 bg-blue-600 text-white text-xs rounded hover:bg-blue-700 transition-colors",
@@ -188,3 +287,12 @@ pub fn RolePopup(
         }
     }
 }
+
+/// Non-zero parts of `cost`, for a tooltip
+fn cost_summary(cost: &CostBreakdown) -> String {
+    cost.parts()
+        .iter()
+        .filter(|(_, amount)| *amount != 0)
+        .map(|(label, amount)| format!("{}: {:+}", label, amount))
+        .join(", ")
+}
@@ -0,0 +1,343 @@
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use itertools::Itertools;
+
+use crate::engine::{
+    person::{DutyStatus, Person},
+    scenario::{ScenarioComparison, ScenarioEdit},
+    team::Team,
+};
+use crate::views::results::AssignmentUIContext;
+
+/// Which kind of edit the form is building
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EditKind {
+    RemovePerson,
+    AddPerson,
+    SetPositionCount,
+    SetPrd,
+}
+
+impl EditKind {
+    const ALL: [EditKind; 4] = [
+        EditKind::RemovePerson,
+        EditKind::AddPerson,
+        EditKind::SetPositionCount,
+        EditKind::SetPrd,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            EditKind::RemovePerson => "Remove a person",
+            EditKind::AddPerson => "Add a notional person",
+            EditKind::SetPositionCount => "Change a position count",
+            EditKind::SetPrd => "Move a PRD",
+        }
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+#[component]
+pub fn ScenarioSandbox(
+    edits: Vec<ScenarioEdit>,
+    teams: Vec<Team>,
+    /// `Ok(None)` until there is an edit to try; `Err` when an edit does not apply
+    outcome_signal: ReadOnlySignal<Result<Option<ScenarioComparison>, String>>,
+    on_add: Callback<ScenarioEdit>,
+    on_remove: Callback<usize>,
+    on_clear: Callback<()>,
+) -> Element {
+    let ui_context = use_context::<AssignmentUIContext>();
+    let mut kind = use_signal(|| EditKind::RemovePerson);
+    // Form state shared by the edit kinds
+    let mut person_name = use_signal(String::new);
+    let mut raterank = use_signal(String::new);
+    let mut duty_status = use_signal(|| DutyStatus::Tar);
+    let mut qualifications = use_signal(String::new);
+    let mut team_name = use_signal(String::new);
+    let mut qualification = use_signal(String::new);
+    let mut count = use_signal(|| 0u32);
+    let mut date = use_signal(|| None::<NaiveDate>);
+
+    let people = (ui_context.people)();
+    let outcome = outcome_signal();
+    let team_qualifications: Vec<String> = teams
+        .iter()
+        .filter(|t| t.name == team_name())
+        .flat_map(|t| t.required_positions.iter())
+        .map(|p| p.qualification.clone())
+        .sorted()
+        .dedup()
+        .collect();
+
+    // `None` while the form is missing something the edit needs
+    let edit = move || -> Option<ScenarioEdit> {
+        let name = person_name().trim().to_string();
+        match kind() {
+            EditKind::RemovePerson => {
+                (!name.is_empty()).then_some(ScenarioEdit::RemovePerson { person_name: name })
+            }
+            EditKind::AddPerson => {
+                if name.is_empty() || raterank().trim().is_empty() {
+                    return None;
+                }
+                let quals: BTreeSet<String> = qualifications()
+                    .split(',')
+                    .map(|q| q.trim().to_string())
+                    .filter(|q| !q.is_empty())
+                    .collect();
+                Some(ScenarioEdit::AddPerson {
+                    person: Person {
+                        name,
+                        raterank: raterank().trim().into(),
+                        duty_status: duty_status(),
+                        qualifications: quals,
+                        prd: date(),
+                        qualification_expirations: Default::default(),
+                        qualification_completions: Default::default(),
+                    },
+                })
+            }
+            EditKind::SetPositionCount => (!team_name().is_empty() && !qualification().is_empty())
+                .then_some(ScenarioEdit::SetPositionCount {
+                    team_name: team_name(),
+                    qualification: qualification(),
+                    count: count(),
+                }),
+            EditKind::SetPrd => (!name.is_empty()).then_some(ScenarioEdit::SetPrd {
+                person_name: name,
+                prd: date(),
+            }),
+        }
+    };
+
+    let add_edit = move |_| {
+        if let Some(edit) = edit() {
+            on_add.call(edit);
+            person_name.set(String::new());
+            raterank.set(String::new());
+            qualifications.set(String::new());
+        }
+    };
+
+    let person_select = rsx! {
+        select {
+            class: "border border-gray-300 rounded px-2 py-1",
+            value: "{person_name}",
+            onchange: move |evt| person_name.set(evt.value()),
+            option { value: "", "Person..." }
+            for person in people.iter() {
+                option { key: "{person.name}", value: "{person.name}", "{person.name}" }
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            class: "section-card",
+            h2 { class: "section-title", "🧪 What-If Scenario" }
+            p {
+                class: "text-sm text-gray-600 mb-2",
+                "Try changes against copies of the roster and requirements. The loaded data and the plan on the Assignments tab stay as they are."
+            }
+
+            div {
+                class: "flex flex-wrap items-center gap-2 text-sm",
+                select {
+                    class: "border border-gray-300 rounded px-2 py-1",
+                    onchange: move |evt| {
+                        let index: usize = evt.value().parse().unwrap_or_default();
+                        kind.set(EditKind::ALL[index]);
+                        person_name.set(String::new());
+                        date.set(None);
+                    },
+                    for (index, option_kind) in EditKind::ALL.iter().enumerate() {
+                        option {
+                            key: "{index}",
+                            value: "{index}",
+                            selected: *option_kind == kind(),
+                            "{option_kind.label()}"
+                        }
+                    }
+                }
+
+                match kind() {
+                    EditKind::RemovePerson => person_select,
+                    EditKind::AddPerson => rsx! {
+                        input {
+                            class: "border border-gray-300 rounded px-2 py-1",
+                            placeholder: "Name",
+                            value: "{person_name}",
+                            oninput: move |evt| person_name.set(evt.value()),
+                        }
+                        input {
+                            class: "border border-gray-300 rounded px-2 py-1 w-20",
+                            placeholder: "AM2",
+                            value: "{raterank}",
+                            oninput: move |evt| raterank.set(evt.value()),
+                        }
+                        select {
+                            class: "border border-gray-300 rounded px-2 py-1",
+                            onchange: move |evt| duty_status.set(DutyStatus::from(evt.value().as_str())),
+                            option { value: "TAR", "TAR" }
+                            option { value: "SELRES", "SELRES" }
+                        }
+                        input {
+                            class: "border border-gray-300 rounded px-2 py-1",
+                            placeholder: "Quals, comma separated",
+                            value: "{qualifications}",
+                            oninput: move |evt| qualifications.set(evt.value()),
+                        }
+                        span { "PRD" }
+                        input {
+                            r#type: "date",
+                            class: "border border-gray-300 rounded px-2 py-1",
+                            onchange: move |evt| date.set(parse_date(&evt.value())),
+                        }
+                    },
+                    EditKind::SetPositionCount => rsx! {
+                        select {
+                            class: "border border-gray-300 rounded px-2 py-1",
+                            value: "{team_name}",
+                            onchange: move |evt| {
+                                team_name.set(evt.value());
+                                qualification.set(String::new());
+                            },
+                            option { value: "", "Team..." }
+                            for team in teams.iter() {
+                                option { key: "{team.name}", value: "{team.name}", "{team.name}" }
+                            }
+                        }
+                        select {
+                            class: "border border-gray-300 rounded px-2 py-1",
+                            value: "{qualification}",
+                            onchange: move |evt| qualification.set(evt.value()),
+                            option { value: "", "Qualification..." }
+                            for qual in team_qualifications.iter() {
+                                option { key: "{qual}", value: "{qual}", "{qual}" }
+                            }
+                        }
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            class: "border border-gray-300 rounded px-2 py-1 w-16",
+                            value: "{count}",
+                            oninput: move |evt| count.set(evt.value().parse().unwrap_or_default()),
+                        }
+                    },
+                    EditKind::SetPrd => rsx! {
+                        {person_select}
+                        span { "to" }
+                        input {
+                            r#type: "date",
+                            class: "border border-gray-300 rounded px-2 py-1",
+                            onchange: move |evt| date.set(parse_date(&evt.value())),
+                        }
+                    },
+                }
+
+                button {
+                    class: "px-2 py-1 bg-indigo-600 text-white rounded text-xs hover:bg-indigo-700 disabled:opacity-50",
+                    disabled: edit().is_none(),
+                    onclick: add_edit,
+                    "Add"
+                }
+            }
+
+            if !edits.is_empty() {
+                div {
+                    class: "mt-3 flex items-start gap-2",
+                    ol {
+                        class: "text-sm text-gray-700 space-y-1 list-decimal list-inside",
+                        for (index, edit) in edits.iter().enumerate() {
+                            li {
+                                key: "{index}",
+                                "{edit}"
+                                button {
+                                    class: "ml-2 text-gray-400 hover:text-red-600",
+                                    onclick: move |_| on_remove.call(index),
+                                    "✕"
+                                }
+                            }
+                        }
+                    }
+                    button {
+                        class: "ml-auto px-2 py-1 bg-gray-200 text-gray-700 rounded text-xs hover:bg-gray-300",
+                        onclick: move |_| on_clear.call(()),
+                        "Clear Scenario"
+                    }
+                }
+            }
+
+            match outcome {
+                Err(message) => rsx! {
+                    p { class: "mt-3 text-sm text-red-600", "{message}" }
+                },
+                Ok(None) => rsx! {},
+                Ok(Some(comparison)) => rsx! {
+                    div {
+                        class: "mt-4 grid gap-4 md:grid-cols-3 text-sm",
+                        div {
+                            h3 { class: "font-semibold text-gray-700 mb-1", "Fill rate" }
+                            p {
+                                class: "text-lg font-bold",
+                                "{comparison.fill_rate_before:.0}% → {comparison.fill_rate_after:.0}%"
+                            }
+                        }
+                        div {
+                            h3 { class: "font-semibold text-gray-700 mb-1", "Positions gained" }
+                            if comparison.gained_positions.is_empty() {
+                                p { class: "text-gray-500", "None" }
+                            }
+                            ul {
+                                class: "text-green-700",
+                                for role_id in comparison.gained_positions.iter() {
+                                    li { key: "{role_id}", "{role_id}" }
+                                }
+                            }
+                        }
+                        div {
+                            h3 { class: "font-semibold text-gray-700 mb-1", "Positions lost" }
+                            if comparison.lost_positions.is_empty() {
+                                p { class: "text-gray-500", "None" }
+                            }
+                            ul {
+                                class: "text-red-700",
+                                for role_id in comparison.lost_positions.iter() {
+                                    li { key: "{role_id}", "{role_id}" }
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        class: "mt-3",
+                        h3 {
+                            class: "text-sm font-semibold text-gray-700 mb-1",
+                            "Who moves ({comparison.moves.moves.len()})"
+                        }
+                        if comparison.moves.is_empty() {
+                            p { class: "text-sm text-gray-600", "Nobody moves." }
+                        }
+                        ul {
+                            class: "text-sm text-gray-700 space-y-0.5",
+                            for person_move in comparison.moves.moves.iter() {
+                                li {
+                                    key: "{person_move.person_name}",
+                                    span { class: "font-medium", "{person_move.person_name}: " }
+                                    {person_move.from.as_ref().map_or("Unassigned".to_string(), |slot| slot.to_string())}
+                                    " → "
+                                    {person_move.to.as_ref().map_or("Unassigned".to_string(), |slot| slot.to_string())}
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use dioxus::prelude::*;
+
+use crate::engine::assignment::ShadowPrice;
+
+/// Closed until asked for, since ranking re-solves once per qualification
+#[component]
+pub fn ShadowPriceTable(
+    prices_signal: ReadOnlySignal<Vec<ShadowPrice>>,
+    open_signal: Signal<bool>,
+) -> Element {
+    let open = open_signal();
+    let prices = prices_signal();
+    // quals where one more holder changes nothing are summed up below the table
+    let (valuable, no_gain): (Vec<ShadowPrice>, Vec<ShadowPrice>) = prices
+        .into_iter()
+        .partition(|p| p.positions_gained > 0 || p.cost_change < 0);
+
+    rsx! {
+        div {
+            class: "section-card",
+            div {
+                class: "flex items-center gap-2 mb-2",
+                h2 { class: "section-title", "🎯 Most Valuable Qual to Train" }
+                button {
+                    class: "ml-auto px-2 py-1 bg-indigo-600 text-white rounded text-xs hover:bg-indigo-700",
+                    onclick: move |_| open_signal.set(!open),
+                    if open { "Hide" } else { "🎯 Rank Qualifications" }
+                }
+            }
+            p {
+                class: "text-sm text-gray-600 mb-2",
+                "What one more qualified TAR E-5 would change, re-solving with them added. Pairing rules are not re-checked."
+            }
+
+            if open {
+                if valuable.is_empty() {
+                    p {
+                        class: "text-sm text-gray-600",
+                        "No single extra qualification would fill another position or lower the cost."
+                    }
+                } else {
+                    table {
+                        class: "w-full text-sm",
+                        thead {
+                            tr {
+                                class: "text-left text-gray-500 border-b border-gray-200",
+                                th { class: "py-1", "#" }
+                                th { class: "py-1", "Qualification" }
+                                th { class: "py-1 text-right", "Positions" }
+                                th { class: "py-1 text-right", "Mandatory" }
+                                th { class: "py-1 text-right", "Fill rate" }
+                                th { class: "py-1 text-right", "Cost change" }
+                            }
+                        }
+                        tbody {
+                            for (rank, price) in valuable.iter().enumerate() {
+                                tr {
+                                    key: "{price.qualification}",
+                                    class: "border-b border-gray-100",
+                                    td { class: "py-1 text-gray-500", "{rank + 1}" }
+                                    td { class: "py-1 font-medium", "{price.qualification}" }
+                                    td { class: "py-1 text-right", "+{price.positions_gained}" }
+                                    td {
+                                        class: if price.mandatory_gained > 0 { "py-1 text-right font-semibold text-red-700" } else { "py-1 text-right" },
+                                        "+{price.mandatory_gained}"
+                                    }
+                                    td { class: "py-1 text-right", "+{price.fill_rate_gain:.1}%" }
+                                    td { class: "py-1 text-right font-mono", "{price.cost_change:+}" }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !no_gain.is_empty() {
+                    p {
+                        class: "mt-2 text-xs text-gray-500",
+                        "No gain from one more: "
+                        {no_gain.iter().map(|p| p.qualification.as_str()).collect::<Vec<_>>().join(", ")}
+                    }
+                }
+            }
+        }
+    }
+}
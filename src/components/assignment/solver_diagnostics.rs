@@ -0,0 +1,43 @@
+use dioxus::prelude::*;
+
+use crate::engine::assignment::SolveStats;
+
+#[component]
+pub fn SolverDiagnostics(stats_signal: ReadOnlySignal<Option<SolveStats>>) -> Element {
+    let Some(stats) = stats_signal() else {
+        return rsx! {};
+    };
+
+    let rows = [
+        ("Nodes", stats.nodes.to_string()),
+        ("Edges", stats.edges.to_string()),
+        (
+            "Augmenting iterations",
+            stats.augmenting_iterations.to_string(),
+        ),
+        ("Total flow", stats.total_flow.to_string()),
+        ("Total cost", stats.total_cost.to_string()),
+        ("Wall time", format!("{:.2} ms", stats.wall_time_ms())),
+        ("Locks applied", stats.locks_applied.to_string()),
+    ];
+
+    rsx! {
+        details {
+            class: "m-1 px-3 py-2 bg-gray-50 border border-gray-200 rounded text-sm text-gray-700",
+            summary {
+                class: "cursor-pointer font-medium",
+                "🔧 Solver diagnostics"
+            }
+            dl {
+                class: "mt-2 grid grid-cols-2 md:grid-cols-4 gap-x-4 gap-y-1",
+                for (label, value) in rows {
+                    div {
+                        key: "{label}",
+                        dt { class: "text-xs text-gray-500", "{label}" }
+                        dd { class: "font-mono", "{value}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -39,18 +39,40 @@ pub fn TeamCard(
         }
     });
 
-    // Memoize unfilled positions for this team - only recalculates when assignments change
+    // Memoize unfilled positions for this team - mandatory gaps first, flagged as critical
     let unfilled_positions = use_memo({
         let team_name = team.name.clone();
         move || {
             let Some(assignments) = assignments_signal() else {
                 return Vec::new();
             };
-            assignments
+            let mandatory = assignments
+                .unfilled_mandatory_positions
+                .iter()
+                .map(|gap| (gap, true));
+            let optional = assignments
                 .unfilled_positions
                 .iter()
-                .filter(|(tn, _)| tn == &team_name)
-                .map(|(_, role_id)| role_id.clone())
+                .map(|gap| (gap, false));
+            mandatory
+                .chain(optional)
+                .filter(|(gap, _)| gap.team_name == team_name)
+                .map(|(gap, is_mandatory)| (gap.clone(), is_mandatory))
+                .collect::<Vec<_>>()
+        }
+    });
+
+    // Composition rules this team's members break
+    let composition_violations = use_memo({
+        let team_name = team.name.clone();
+        move || {
+            let Some(assignments) = assignments_signal() else {
+                return Vec::new();
+            };
+            assignments
+                .composition_violations
+                .into_iter()
+                .filter(|violation| violation.team_name == team_name)
                 .collect::<Vec<_>>()
         }
     });
@@ -64,6 +86,34 @@ pub fn TeamCard(
                 class: "team-header",
                 span { class: "team-icon", "👥" }
                 "{team.name} ({team_assignments_vec.len()} assigned)"
+                if let Some(priority) = team.priority {
+                    span {
+                        class: "ml-auto px-2 py-0.5 text-xs font-bold text-indigo-700 bg-indigo-100 rounded-full",
+                        title: "Team priority - lower numbers are filled first",
+                        "Priority {priority}"
+                    }
+                }
+            }
+
+            if !team.composition.is_empty() {
+                div {
+                    class: "flex flex-wrap gap-1 mb-2",
+                    for rule in team.composition.rules() {
+                        span {
+                            key: "{rule}",
+                            class: "px-2 py-0.5 text-xs text-gray-700 bg-gray-100 rounded-full",
+                            title: "Team composition rule",
+                            "{rule}"
+                        }
+                    }
+                }
+            }
+            for violation in composition_violations() {
+                div {
+                    key: "{violation}",
+                    class: "mb-2 px-3 py-1.5 text-sm text-amber-800 bg-amber-50 border border-amber-200 rounded",
+                    "⚠️ {violation.actual} counted, needs {violation.rule}"
+                }
             }
 
             div {
@@ -96,22 +146,27 @@ pub fn TeamCard(
                         }
 
                     // rows for missing quals
-                    for missing_role_id in unfilled_positions_vec {
+                    for (gap, is_mandatory) in unfilled_positions_vec {
                         tr {
-                            class: "table-row bg-red-50",
+                            class: if is_mandatory { "table-row bg-red-100 border-l-4 border-l-red-600" } else { "table-row bg-red-50" },
+                            title: "{gap.reason}",
                             td {
                                 ""
                             }
                             td {
                                 class: "table-cell-name text-red-600",
-                                span { class: "text-xl mr-2", "⚠️" }
+                                if is_mandatory {
+                                    span { class: "text-xl mr-2", title: "Mandatory position", "🚨" }
+                                } else {
+                                    span { class: "text-xl mr-2", "⚠️" }
+                                }
                             }
-                            td { class: "table-cell-muted text-red-400", "" }
+                            td { class: "table-cell-muted text-xs text-red-500 italic", "{gap.reason.summary()}" }
                             td {
                                 class: "table-cell",
                                 span {
                                     class: "role-badge bg-red-100 text-red-800",
-                                    "{missing_role_id}"
+                                    "{gap.role_id}"
                                 }
                             }
                             td { class: "table-cell-muted text-red-400", "" }
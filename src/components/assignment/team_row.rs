@@ -56,29 +56,10 @@ pub fn TeamRow(
         }
     });
 
-    // Memoize swap eligibility - only recalculates when mode/selections/people change
-    let is_eligible = use_memo({
-        let person_name = person.name.clone();
-        let team_name_opt = team_name_opt.clone();
-        let position_opt = position_opt.clone();
-        let ui_context = ui_context.clone();
-        move || {
-            let interaction_mode = (ui_context.interaction_mode)();
-            let selected_assignments = (ui_context.selected_assignments)();
-            let people = (ui_context.people)();
-            false // Swap eligibility removed with old checkbox swap system
-        }
-    });
-
-    // Memoize checkbox disabled state - only recalculates when mode/selections change
+    // Memoize checkbox disabled state - only recalculates when the mode changes
     let is_disabled = use_memo({
-        let is_selected = is_selected();
         let ui_context = ui_context.clone();
-        move || {
-            let interaction_mode = (ui_context.interaction_mode)();
-            let selected_count = (ui_context.selected_assignments)().len();
-            is_checkbox_disabled(interaction_mode, selected_count, is_selected)
-        }
+        move || is_checkbox_disabled((ui_context.interaction_mode)())
     });
 
     // Create separate clones for closures to avoid ownership conflicts
@@ -92,8 +73,6 @@ pub fn TeamRow(
                 "{}",
                 if is_selected() {
                     "table-row bg-yellow-50 border-l-4 border-l-yellow-400"
-                } else if is_eligible() {
-                    "table-row bg-emerald-100 border-l-4 border-l-emerald-400"
                 } else if is_manual_override {
                     "table-row border-black border bg-gray-200"
                 } else {
@@ -144,6 +123,14 @@ pub fn TeamRow(
                             None
                         },
                     }
+                    // counted only because training finishes by the analysis date
+                    if let Some(done) = person.completion(&position.qualification) {
+                        span {
+                            class: "ml-1 px-1 rounded bg-sky-100 text-sky-800 text-xs",
+                            title: "Training expected complete {done}",
+                            "pending qual"
+                        }
+                    }
                 } else {
                     // For unassigned people, show their qualifications (no popup needed)
                     RoleBadge {
@@ -233,11 +220,7 @@ fn is_unassigned_selected(
         .any(|(name, team, pos)| name == person_name && team.is_none() && pos.is_none())
 }
 
-fn is_checkbox_disabled(
-    interaction_mode: InteractionMode,
-    current_count: usize,
-    is_currently_selected: bool,
-) -> bool {
+fn is_checkbox_disabled(interaction_mode: InteractionMode) -> bool {
     match interaction_mode {
         InteractionMode::Lock => false,
         InteractionMode::ViewOnly => true,
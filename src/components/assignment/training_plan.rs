@@ -0,0 +1,68 @@
+use dioxus::prelude::*;
+
+use crate::engine::training::TrainingRecommendation;
+
+#[component]
+pub fn TrainingPlan(
+    recommendations_signal: ReadOnlySignal<Vec<TrainingRecommendation>>,
+    on_export: Callback<()>,
+) -> Element {
+    let recommendations = recommendations_signal();
+    if recommendations.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "section-card",
+            div {
+                class: "flex items-center justify-between",
+                h2 { class: "section-title", "📚 Training Plan" }
+                button {
+                    class: "px-2 py-1 bg-gray-200 text-gray-700 rounded text-xs hover:bg-gray-300",
+                    onclick: move |_| on_export.call(()),
+                    "📄 Export CSV"
+                }
+            }
+            p {
+                class: "text-sm text-gray-600 mb-2",
+                "Who on the bench to qualify so the most gaps close, mandatory positions first. TAR sailors with distant PRDs and people holding related quals are preferred."
+            }
+            table {
+                class: "w-full text-sm",
+                thead {
+                    tr {
+                        class: "text-left text-gray-500 border-b border-gray-200",
+                        th { class: "py-1", "Person" }
+                        th { class: "py-1", "Qual to earn" }
+                        th { class: "py-1", "Fills" }
+                        th { class: "py-1", "Also unlocks" }
+                    }
+                }
+                tbody {
+                    for recommendation in recommendations.iter() {
+                        tr {
+                            key: "{recommendation.person_name}",
+                            class: "border-b border-gray-100",
+                            td { class: "py-1 font-medium", "{recommendation.person_name}" }
+                            td { class: "py-1", "{recommendation.qualification}" }
+                            td { class: "py-1", "{recommendation.fills}" }
+                            td {
+                                class: "py-1 text-xs text-gray-500",
+                                {
+                                    recommendation
+                                        .unlocks
+                                        .iter()
+                                        .filter(|role_id| **role_id != recommendation.fills)
+                                        .cloned()
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@ use dioxus::prelude::*;
 use itertools::Itertools;
 
 use crate::components::{SearchBar, TeamRow};
-use crate::engine::assignment::AssignmentPlan;
+use crate::engine::assignment::{AssignmentExclusion, AssignmentPlan};
 use crate::views::results::{
     AssignmentUIContext, PersonHoverHandler, PersonLeaveHandler, SelectionChangeHandler,
 };
@@ -12,14 +12,20 @@ use crate::views::results::{
 pub fn UnassignedTable(
     assignments_signal: ReadOnlySignal<Option<AssignmentPlan>>,
     analysis_date_signal: Signal<NaiveDate>,
+    team_names: Vec<String>,
+    on_exclude: Callback<AssignmentExclusion>,
+    on_remove_exclusion: Callback<AssignmentExclusion>,
     on_selection_change: SelectionChangeHandler,
     on_person_hover: PersonHoverHandler,
     on_person_leave: PersonLeaveHandler,
 ) -> Element {
     // Get context for shared UI state
-    let _ui_context = use_context::<AssignmentUIContext>();
+    let ui_context = use_context::<AssignmentUIContext>();
     // Local search query state
     let mut search_query = use_signal(String::new);
+    // Exclusion form state: person name and "team:<name>" / "qual:<name>"
+    let mut exclusion_person = use_signal(String::new);
+    let mut exclusion_target = use_signal(String::new);
 
     // Read current value from signal
     let analysis_date = analysis_date_signal();
@@ -55,6 +61,32 @@ pub fn UnassignedTable(
             .collect::<Vec<_>>()
     });
 
+    let exclusions = (ui_context.exclusions)();
+    let exclusion_quals = unassigned_people()
+        .iter()
+        .find(|p| p.name == exclusion_person())
+        .map(|p| p.qualifications.iter().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let add_exclusion = move |_| {
+        let person_name = exclusion_person();
+        let target = exclusion_target();
+        let (team_name, qualification) = match target.split_once(':') {
+            Some(("team", team)) => (Some(team.to_string()), None),
+            Some(("qual", qual)) => (None, Some(qual.to_string())),
+            _ => return,
+        };
+        if person_name.is_empty() {
+            return;
+        }
+        on_exclude.call(AssignmentExclusion {
+            person_name,
+            team_name,
+            qualification,
+        });
+        exclusion_target.set(String::new());
+    };
+
     rsx! {
         div {
             class: "section-card",
@@ -63,6 +95,72 @@ pub fn UnassignedTable(
                 "👤 Available Personnel"
             }
 
+            // Exclusions keep someone off a team or qualification while the solver
+            // still places them anywhere else
+            div {
+                class: "mb-4 p-3 bg-red-50 border border-red-100 rounded",
+                div {
+                    class: "flex flex-wrap items-center gap-2 text-sm",
+                    span { class: "font-semibold text-red-800", "🚫 Exclude" }
+                    select {
+                        class: "border border-gray-300 rounded px-2 py-1",
+                        value: "{exclusion_person}",
+                        onchange: move |evt| {
+                            exclusion_person.set(evt.value());
+                            exclusion_target.set(String::new());
+                        },
+                        option { value: "", "Person..." }
+                        for person in unassigned_people().iter() {
+                            option { key: "{person.name}", value: "{person.name}", "{person.name}" }
+                        }
+                    }
+                    select {
+                        class: "border border-gray-300 rounded px-2 py-1",
+                        value: "{exclusion_target}",
+                        onchange: move |evt| exclusion_target.set(evt.value()),
+                        option { value: "", "Team or qualification..." }
+                        optgroup {
+                            label: "Team",
+                            for team_name in team_names.iter() {
+                                option { key: "team:{team_name}", value: "team:{team_name}", "{team_name}" }
+                            }
+                        }
+                        optgroup {
+                            label: "Qualification",
+                            for qualification in exclusion_quals.iter() {
+                                option { key: "qual:{qualification}", value: "qual:{qualification}", "{qualification}" }
+                            }
+                        }
+                    }
+                    button {
+                        class: "px-2 py-1 bg-red-600 text-white rounded text-xs hover:bg-red-700 disabled:opacity-50",
+                        disabled: exclusion_person().is_empty() || exclusion_target().is_empty(),
+                        onclick: add_exclusion,
+                        "Add"
+                    }
+                }
+                if !exclusions.is_empty() {
+                    div {
+                        class: "flex flex-wrap gap-1 mt-2",
+                        for exclusion in exclusions.iter() {
+                            span {
+                                key: "{exclusion}",
+                                class: "inline-flex items-center gap-1 px-2 py-0.5 bg-white border border-red-200 text-red-700 rounded-full text-xs",
+                                "{exclusion}"
+                                button {
+                                    class: "text-red-400 hover:text-red-700",
+                                    onclick: {
+                                        let exclusion = exclusion.clone();
+                                        move |_| on_remove_exclusion.call(exclusion.clone())
+                                    },
+                                    "✕"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             SearchBar {
                 placeholder: "Search name or qual...",
                 value: search_query(),
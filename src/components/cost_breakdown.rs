@@ -0,0 +1,36 @@
+use dioxus::prelude::*;
+
+use crate::engine::cost_policy::CostBreakdown;
+
+/// The non-zero parts of an assignment cost and their total; lower is preferred
+#[component]
+pub fn CostBreakdownTable(cost: CostBreakdown) -> Element {
+    let parts: Vec<(&str, i64)> = cost
+        .parts()
+        .into_iter()
+        .filter(|(_, amount)| *amount != 0)
+        .collect();
+
+    rsx! {
+        table {
+            class: "text-xs w-full",
+            tbody {
+                for (label, amount) in parts {
+                    tr {
+                        key: "{label}",
+                        td { class: "text-gray-500 pr-2", "{label}" }
+                        td {
+                            class: if amount < 0 { "text-right font-mono text-green-700" } else { "text-right font-mono text-red-700" },
+                            "{amount:+}"
+                        }
+                    }
+                }
+                tr {
+                    class: "border-t border-gray-200",
+                    td { class: "font-semibold pr-2", "Cost" }
+                    td { class: "text-right font-mono font-semibold", "{cost.total()}" }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use dioxus::prelude::*;
 
+use crate::engine::backend::BackendKind;
+
 #[derive(Clone, Copy, PartialEq)]
 pub enum InteractionMode {
     ViewOnly,
@@ -12,6 +14,13 @@ pub enum InteractionAction {
     ExecuteLock,
     ClearLocks,
     SaveState,
+    LoadCostPolicy(String),
+    /// Make the plan on screen the baseline later solves stay close to
+    SetBaseline,
+    ClearBaseline,
+    SetBackend(BackendKind),
+    /// Solve with every backend and flag where their costs differ
+    SetCompareBackends(bool),
 }
 
 #[component]
@@ -19,12 +28,18 @@ pub fn InteractionBar(
     interaction_mode_signal: Signal<InteractionMode>,
     selected_count_signal: ReadOnlySignal<usize>,
     persistent_locks_count_signal: ReadOnlySignal<usize>,
+    baseline_moves_signal: ReadOnlySignal<Option<usize>>,
+    backend_signal: ReadOnlySignal<BackendKind>,
+    compare_backends_signal: ReadOnlySignal<bool>,
     on_action: EventHandler<InteractionAction>,
 ) -> Element {
     // Read current values from signals - component will auto-rerender when these change
     let interaction_mode = interaction_mode_signal();
     let selected_count = selected_count_signal();
     let persistent_locks_count = persistent_locks_count_signal();
+    let baseline_moves = baseline_moves_signal();
+    let backend = backend_signal();
+    let compare_backends = compare_backends_signal();
     rsx! {
         div {
             class: "sticky top-17 z-50 bg-white shadow-md border border-gray-200 rounded-lg flex gap-1 p-2 m-1 w-auto",
@@ -77,6 +92,86 @@ pub fn InteractionBar(
                 },
                 "💾 Save State"
             }
+
+            // baseline plan - later solves only move people when it pays off
+            match baseline_moves {
+                Some(moves) => rsx! {
+                    span {
+                        class: "px-2 py-1 bg-teal-100 text-teal-800 rounded text-xs font-medium",
+                        title: "People whose team or qualification differs from the baseline plan",
+                        "📌 {moves} moves vs. baseline"
+                    }
+                    button {
+                        class: "px-2 py-1 bg-gray-200 text-gray-700 rounded text-xs hover:bg-gray-300",
+                        onclick: move |_| {
+                            on_action.call(InteractionAction::ClearBaseline);
+                        },
+                        "Clear Baseline"
+                    }
+                },
+                None => rsx! {
+                    button {
+                        class: "px-2 py-1 bg-teal-600 text-white rounded text-xs hover:bg-teal-700",
+                        title: "Keep people where they are when the date, roster or rules change",
+                        onclick: move |_| {
+                            on_action.call(InteractionAction::SetBaseline);
+                        },
+                        "📌 Set as Baseline"
+                    }
+                },
+            }
+
+            // which algorithm solves the network; comparing runs them all
+            select {
+                class: "px-2 py-1 border border-gray-300 rounded text-xs",
+                title: "Solver backend",
+                onchange: move |evt| {
+                    let index: usize = evt.value().parse().unwrap_or_default();
+                    on_action.call(InteractionAction::SetBackend(BackendKind::ALL[index]));
+                },
+                for (index, kind) in BackendKind::ALL.iter().enumerate() {
+                    option {
+                        key: "{index}",
+                        value: "{index}",
+                        selected: *kind == backend,
+                        "{kind.label()}"
+                    }
+                }
+            }
+            label {
+                class: "flex items-center gap-1 px-2 py-1 text-xs text-gray-700",
+                title: "Also solve with every other backend and flag any difference in total cost",
+                input {
+                    r#type: "checkbox",
+                    checked: compare_backends,
+                    onchange: move |evt| {
+                        on_action.call(InteractionAction::SetCompareBackends(evt.checked()));
+                    },
+                }
+                "Compare"
+            }
+
+            // file picker for a cost policy JSON - changes the solver's trade-offs
+            label {
+                class: "px-2 py-1 bg-indigo-600 text-white rounded text-xs hover:bg-indigo-700 cursor-pointer",
+                "⚖️ Load Cost Policy"
+                input {
+                    r#type: "file",
+                    accept: ".json",
+                    style: "display: none;",
+                    onchange: move |evt| {
+                        spawn(async move {
+                            if let Some(file_engine) = evt.files() {
+                                if let Some(fname) = file_engine.files().first() {
+                                    if let Some(json_content) = file_engine.read_file_to_string(fname).await {
+                                        on_action.call(InteractionAction::LoadCostPolicy(json_content));
+                                    }
+                                }
+                            }
+                        });
+                    }
+                }
+            }
         }
     }
 }
@@ -26,6 +26,13 @@ pub use footer::Footer;
 mod player_card;
 pub use player_card::PlayerCard;
 
+mod cost_breakdown;
+pub use cost_breakdown::CostBreakdownTable;
+
 // Domain-specific component modules
 pub mod assignment;
-pub use assignment::{AssignmentStats, RoleBadge, RolePopup, TeamCard, TeamRow, UnassignedTable};
+pub use assignment::{
+    AssignmentStats, ManningProjection, PairingRules, PendingQualifications, PlanSummary,
+    PlanSwitcher, RoleBadge, RolePopup, ScenarioSandbox, ShadowPriceTable, SolverDiagnostics,
+    TeamCard, TeamRow, TrainingPlan, UnassignedTable,
+};
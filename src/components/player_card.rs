@@ -1,12 +1,29 @@
-use crate::engine::person::Person;
+use crate::components::CostBreakdownTable;
+use crate::engine::{cost_policy::CostBreakdown, person::Person};
+use chrono::NaiveDate;
 use dioxus::prelude::*;
 
 #[component]
 pub fn PlayerCard(
     person: Person,
     current_assignment: Option<String>,
+    /// What the current assignment costs, and why
+    cost: Option<CostBreakdown>,
     position: (f64, f64),
+    analysis_date: NaiveDate,
 ) -> Element {
+    // qualifications without an expiry date stay on one line
+    let (dated, undated): (Vec<_>, Vec<_>) = person
+        .qualifications
+        .iter()
+        .map(|q| (q.clone(), person.expiration(q)))
+        .partition(|(_, expiry)| expiry.is_some());
+    let dated: Vec<(String, NaiveDate)> = dated
+        .into_iter()
+        .filter_map(|(q, expiry)| Some((q, expiry?)))
+        .collect();
+    let undated: Vec<String> = undated.into_iter().map(|(q, _)| q).collect();
+
     rsx! {
         div {
             class: "fixed z-50 bg-white border border-gray-300 rounded-lg shadow-lg p-4 max-w-sm pointer-events-none",
@@ -19,6 +36,9 @@ pub fn PlayerCard(
             div {
                 class: "text-sm text-gray-600 mb-2",
                 "Rate/Rank: {person.raterank}"
+                if let Some(paygrade) = person.raterank.paygrade {
+                    " (E-{paygrade})"
+                }
             }
             if let Some(assignment) = current_assignment {
                 div {
@@ -28,8 +48,35 @@ pub fn PlayerCard(
             }
             div {
                 class: "text-xs text-gray-500",
-                "Qualifications: {person.qualifications.iter().cloned().collect::<Vec<_>>().join(\", \")}"
+                "Qualifications: {undated.join(\", \")}"
+            }
+            if !dated.is_empty() {
+                ul {
+                    class: "text-xs mt-1 space-y-0.5",
+                    for (qualification, expiry) in dated {
+                        li {
+                            key: "{qualification}",
+                            class: expiry_css_class(expiry, analysis_date),
+                            "{qualification} - expires {expiry}"
+                        }
+                    }
+                }
+            }
+            if let Some(cost) = cost {
+                div {
+                    class: "mt-2 pt-2 border-t border-gray-200",
+                    CostBreakdownTable { cost }
+                }
             }
         }
     }
 }
+
+/// Red once expired, amber within 90 days of the analysis date
+fn expiry_css_class(expiry: NaiveDate, analysis_date: NaiveDate) -> &'static str {
+    match (expiry - analysis_date).num_days() {
+        ..0 => "text-red-600 font-semibold",
+        0..90 => "text-amber-600",
+        _ => "text-gray-500",
+    }
+}
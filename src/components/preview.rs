@@ -116,6 +116,12 @@ pub fn RequirementsPreview(data: Rc<Vec<u8>>) -> Element {
                                         class: "font-semibold text-gray-900 flex items-center gap-2",
                                         span { "👥" }
                                         "{team.name}"
+                                        if let Some(priority) = team.priority {
+                                            span {
+                                                class: "ml-auto px-2 py-0.5 text-xs font-bold text-indigo-700 bg-indigo-100 rounded-full",
+                                                "Priority {priority}"
+                                            }
+                                        }
                                     }
                                 }
                                 div {
@@ -1,8 +1,15 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::engine::flow_graph::FlowGraph;
+use crate::engine::backend::{BackendKind, BackendMismatch};
+use crate::engine::cost_policy::{CostBreakdown, CostPolicy};
+use crate::engine::flow_graph::{FlowError, FlowGraph};
+use crate::engine::pairing::PairingConstraint;
 use crate::engine::person::{DutyStatus, Person};
-use crate::engine::team::{Position, Team};
+use crate::engine::plan_diff::{PlanBaseline, PlanSlot};
+use crate::engine::team::{
+    CompositionRule, CompositionRules, CompositionViolation, Position, Team,
+};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
@@ -15,29 +22,239 @@ pub struct RoleId {
     pub instance: u32,
 }
 
-impl From<RoleId> for Position {
-    fn from(val: RoleId) -> Self {
-        Position {
-            qualification: val.qualification,
-            instance: val.instance,
+impl Display for RoleId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}-{:03}",
+            self.team, self.qualification, self.instance
+        )
+    }
+}
+
+/// How `AssignmentSolver` lays out role nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NetworkMode {
+    /// One node per position instance; only the tests build it, to check the
+    /// aggregated network against
+    #[cfg(test)]
+    PerInstance,
+    /// Interchangeable instances (same team, qualification, mandatory flag and
+    /// paygrade and rate limits) share one node with capacity N, so edges grow with
+    /// people x qualifications instead of people x instances
+    #[default]
+    Aggregated,
+}
+
+/// One role node: a set of interchangeable position instances on the same team
+#[derive(Debug, Clone)]
+struct RoleGroup {
+    team: String,
+    qualification: String,
+    mandatory: bool,
+    /// Open (unlocked) instances sorted by instance; flows are handed out in this order
+    positions: Vec<Position>,
+    /// Every instance the group was built with, locked or not
+    all_positions: Vec<Position>,
+    /// Members the node takes when the team's composition rules split its people
+    class: MemberClass,
+    /// Index of the first group sharing these instances; a team with composition rules
+    /// gets one group per member class for each set of instances
+    base: usize,
+}
+
+impl RoleGroup {
+    fn group_positions(
+        teams: &[Team],
+        locked_role_ids: &HashSet<RoleId>,
+        network_mode: NetworkMode,
+    ) -> Vec<RoleGroup> {
+        let mut groups: Vec<RoleGroup> = vec![];
+        // positions only share a node when the same people are eligible for them
+        type GroupKey<'a> = (&'a str, &'a str, bool, Option<u8>, Option<u8>, &'a [String]);
+        let mut group_index: HashMap<GroupKey, usize> = HashMap::new();
+
+        for team in teams {
+            for position in &team.required_positions {
+                if locked_role_ids.contains(&position.as_role_id(&team.name)) {
+                    continue;
+                }
+
+                let key = (
+                    team.name.as_str(),
+                    position.qualification.as_str(),
+                    position.mandatory,
+                    position.min_paygrade,
+                    position.max_paygrade,
+                    position.allowed_rates.as_slice(),
+                );
+                let existing = match network_mode {
+                    NetworkMode::Aggregated => group_index.get(&key).copied(),
+                    #[cfg(test)]
+                    NetworkMode::PerInstance => None,
+                };
+                match existing {
+                    Some(idx) => groups[idx].positions.push(position.clone()),
+                    None => {
+                        group_index.insert(key, groups.len());
+                        groups.push(RoleGroup {
+                            team: team.name.clone(),
+                            qualification: position.qualification.clone(),
+                            mandatory: position.mandatory,
+                            positions: vec![position.clone()],
+                            all_positions: vec![],
+                            class: MemberClass::ANYONE,
+                            base: 0,
+                        });
+                    }
+                }
+            }
+        }
+
+        let classes: HashMap<&str, Vec<MemberClass>> = teams
+            .iter()
+            .map(|team| (team.name.as_str(), MemberClass::split_by(&team.composition)))
+            .collect();
+        let mut split = vec![];
+        for mut group in groups {
+            group.positions.sort_by_key(|p| p.instance);
+            group.all_positions = group.positions.clone();
+            let base = split.len();
+            for &class in &classes[group.team.as_str()] {
+                split.push(RoleGroup {
+                    class,
+                    base,
+                    ..group.clone()
+                });
+            }
         }
+        split
+    }
+}
+
+/// The people of one kind a team's composition rules tell apart; `None` leaves that
+/// side unchecked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MemberClass {
+    selres: Option<bool>,
+    aw: Option<bool>,
+}
+
+impl MemberClass {
+    const ANYONE: MemberClass = MemberClass {
+        selres: None,
+        aw: None,
+    };
+
+    /// Classes `rules` count separately, TAR before SELRES and non-AW before AW
+    fn split_by(rules: &CompositionRules) -> Vec<MemberClass> {
+        let both = [Some(false), Some(true)];
+        let statuses: &[Option<bool>] = if rules.max_selres.is_some() || rules.min_tar.is_some() {
+            &both
+        } else {
+            &[None]
+        };
+        let aw: &[Option<bool>] = if rules.max_aw.is_some() {
+            &both
+        } else {
+            &[None]
+        };
+        statuses
+            .iter()
+            .flat_map(|&selres| aw.iter().map(move |&aw| MemberClass { selres, aw }))
+            .collect()
+    }
+
+    fn admits(&self, person: &Person) -> bool {
+        self.selres
+            .is_none_or(|selres| selres == (person.duty_status == DutyStatus::Selres))
+            && self.aw.is_none_or(|aw| aw == person.raterank.is_aw())
+    }
+}
+
+/// Edge that carries a team composition rule: a cap as its capacity, a floor as its
+/// lower bound
+#[derive(Debug, Clone)]
+struct CompositionEdge {
+    team: String,
+    rule: CompositionRule,
+    edge_idx: usize,
+}
+
+/// Edges whose total is held to one limit: the team edges of a group's member classes,
+/// which share its instances, and a team's AW edges when its people are also split by
+/// duty status. Each edge alone may carry the whole limit until `divide_shared_limits`
+/// gives it a share.
+#[derive(Debug, Clone)]
+struct SharedCapacity {
+    edges: Vec<usize>,
+    limit: i32,
+}
+
+/// A composition floor no plan can meet, left out of the solve that found so
+#[derive(Debug, Clone, PartialEq)]
+pub struct DroppedFloor {
+    pub team_name: String,
+    pub rule: CompositionRule,
+    /// What the network could not route with the floor in place
+    pub error: FlowError,
+}
+
+impl Display for DroppedFloor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no plan gives {} {}", self.team_name, self.rule)
+    }
+}
+
+impl PlanSlot {
+    fn is_for(&self, group: &RoleGroup) -> bool {
+        self.team_name == group.team && self.qualification == group.qualification
     }
 }
 
+/// Min-cost max-flow assignment: source -> person -> role group -> team -> sink.
+///
+/// A team with composition rules routes its role groups through sub-nodes instead:
+/// role group -> member class -> duty status -> team. Each group is split into one
+/// node per member class, the AW classes are capped by `max_aw` and the SELRES and TAR
+/// nodes carry `max_selres` as capacity and `min_tar` as lower bound.
+///
+/// The result depends only on the order of its inputs, never on hashing. When several
+/// plans cost the same, the tie goes by that order:
+/// - person nodes follow the roster (`parse_asm_file` sorts it by name) and each
+///   person's edges follow their qualifications alphabetically
+/// - role groups follow the teams as listed in the Requirements file, then each team's
+///   positions in file order
+/// - Dijkstra settles equal distances on the lower node first
+/// - flow through a role group fills its instances in ascending order
 pub struct AssignmentSolver {
     graph: FlowGraph,
 
     person_to_node: HashMap<String, usize>,
     node_to_person: HashMap<usize, String>,
 
-    role_to_node: HashMap<RoleId, usize>,
-    node_to_role: HashMap<usize, RoleId>,
+    role_groups: Vec<RoleGroup>,
+    group_nodes: Vec<usize>,
+    groups_by_qualification: HashMap<String, Vec<usize>>,
+    /// Person -> role edges, in the order they were added
+    assignment_edges: Vec<usize>,
 
     team_to_node: HashMap<String, usize>,
     node_to_team: HashMap<usize, String>,
+    /// Edges holding composition rules, in team order
+    composition_edges: Vec<CompositionEdge>,
+    /// Floors the last solve had to leave out
+    dropped_floors: Vec<DroppedFloor>,
+    shared_capacities: Vec<SharedCapacity>,
 
     source_node: usize,
     sink_node: usize,
+
+    cost_policy: CostPolicy,
+    /// Slots the cost model rewards keeping people in
+    baseline: PlanBaseline,
+    /// Algorithm `solve` and `reoptimize` run
+    backend: BackendKind,
 }
 
 impl AssignmentSolver {
@@ -46,41 +263,30 @@ impl AssignmentSolver {
         teams: &[Team],
         analysis_date: chrono::NaiveDate,
         assignment_locks: Option<Vec<AssignmentLock>>,
+        exclusions: &[AssignmentExclusion],
+        cost_policy: &CostPolicy,
     ) -> Self {
-        let num_people = people.len();
-        let num_roles = teams
-            .iter()
-            .map(|t| t.required_positions.len())
-            .sum::<usize>();
-        let num_teams = teams.len();
-        let total_nodes = 1 + num_people + num_roles + num_teams + 1;
-
-        let mut solver = AssignmentSolver {
-            graph: FlowGraph::new(total_nodes),
-            person_to_node: HashMap::with_capacity(num_people),
-            node_to_person: HashMap::with_capacity(num_people),
-            role_to_node: HashMap::with_capacity(num_roles),
-            node_to_role: HashMap::with_capacity(num_roles),
-            team_to_node: HashMap::with_capacity(num_teams),
-            node_to_team: HashMap::with_capacity(num_teams),
-            source_node: 0,
-            sink_node: total_nodes - 1,
-        };
-
-        solver.build_network(people, teams, analysis_date, assignment_locks);
-        solver
+        Self::with_network_mode(
+            people,
+            teams,
+            analysis_date,
+            assignment_locks,
+            exclusions,
+            cost_policy,
+            NetworkMode::default(),
+        )
     }
 
-    fn build_network(
-        &mut self,
+    pub fn with_network_mode(
         people: &[Person],
         teams: &[Team],
         analysis_date: chrono::NaiveDate,
         assignment_locks: Option<Vec<AssignmentLock>>,
-    ) {
-        let mut node_idx = 1; // source is 0
-
-        let (locked_people_set, locked_role_ids) = if let Some(locks) = assignment_locks {
+        exclusions: &[AssignmentExclusion],
+        cost_policy: &CostPolicy,
+        network_mode: NetworkMode,
+    ) -> Self {
+        let (locked_people_set, locked_role_ids) = if let Some(locks) = &assignment_locks {
             let locked_people: HashSet<String> =
                 locks.iter().map(|al| al.person_name.clone()).collect();
 
@@ -100,6 +306,59 @@ impl AssignmentSolver {
         } else {
             (HashSet::new(), HashSet::new())
         };
+
+        let role_groups = RoleGroup::group_positions(teams, &locked_role_ids, network_mode);
+        let num_people = people
+            .iter()
+            .filter(|p| !locked_people_set.contains(&p.name))
+            .count();
+        let num_roles = role_groups.len();
+        let num_teams = teams.len();
+        let total_nodes = 1 + num_people + num_roles + num_teams + 1;
+
+        let mut groups_by_qualification: HashMap<String, Vec<usize>> = HashMap::new();
+        for (idx, group) in role_groups.iter().enumerate() {
+            groups_by_qualification
+                .entry(group.qualification.clone())
+                .or_default()
+                .push(idx);
+        }
+
+        let mut solver = AssignmentSolver {
+            graph: FlowGraph::new(total_nodes),
+            person_to_node: HashMap::with_capacity(num_people),
+            node_to_person: HashMap::with_capacity(num_people),
+            role_groups,
+            group_nodes: Vec::with_capacity(num_roles),
+            groups_by_qualification,
+            assignment_edges: vec![],
+            team_to_node: HashMap::with_capacity(num_teams),
+            node_to_team: HashMap::with_capacity(num_teams),
+            composition_edges: vec![],
+            dropped_floors: vec![],
+            shared_capacities: vec![],
+            source_node: 0,
+            sink_node: total_nodes - 1,
+            cost_policy: cost_policy.clone(),
+            baseline: PlanBaseline::new(),
+            backend: BackendKind::default(),
+        };
+
+        solver.build_network(people, teams, analysis_date, &locked_people_set, exclusions);
+        solver.set_composition_limits(people, assignment_locks.as_deref().unwrap_or_default());
+        solver
+    }
+
+    fn build_network(
+        &mut self,
+        people: &[Person],
+        teams: &[Team],
+        analysis_date: chrono::NaiveDate,
+        locked_people_set: &HashSet<String>,
+        exclusions: &[AssignmentExclusion],
+    ) {
+        let mut node_idx = 1; // source is 0
+
         // person nodes
         for person in people {
             if !locked_people_set.contains(&person.name) {
@@ -112,16 +371,9 @@ impl AssignmentSolver {
         }
 
         // role nodes
-        for team in teams {
-            for position in &team.required_positions {
-                let role_id = position.as_role_id(&team.name);
-
-                if !locked_role_ids.contains(&role_id) {
-                    self.role_to_node.insert(role_id.clone(), node_idx);
-                    self.node_to_role.insert(node_idx, role_id);
-                    node_idx += 1;
-                }
-            }
+        for _ in &self.role_groups {
+            self.group_nodes.push(node_idx);
+            node_idx += 1;
         }
 
         // team nodes
@@ -132,203 +384,1264 @@ impl AssignmentSolver {
         }
 
         // add edges between layers
-        self.add_person_to_role_edges(people, analysis_date);
-        self.add_role_to_team_edges(teams);
+        self.add_person_to_role_edges(people, analysis_date, exclusions);
+        let class_nodes: HashMap<&str, Vec<(MemberClass, usize)>> = teams
+            .iter()
+            .map(|team| (team.name.as_str(), self.add_composition_nodes(team)))
+            .collect();
+        self.add_role_to_team_edges(&class_nodes);
         self.add_team_to_sink_edges(teams);
     }
 
-    fn add_person_to_role_edges(&mut self, people: &[Person], analysis_date: chrono::NaiveDate) {
+    fn add_person_to_role_edges(
+        &mut self,
+        people: &[Person],
+        analysis_date: chrono::NaiveDate,
+        exclusions: &[AssignmentExclusion],
+    ) {
         for person in people {
-            if let Some(&person_node) = self.person_to_node.get(&person.name) {
-                for (role_id, &role_node) in &self.role_to_node {
-                    if person.qualifications.contains(&role_id.qualification) {
-                        let cost = self.calculate_assignment_cost(person, role_id, analysis_date);
-
-                        self.graph.add_edge(person_node, role_node, 1, cost);
+            let Some(&person_node) = self.person_to_node.get(&person.name) else {
+                continue;
+            };
+            let exclusions: Vec<&AssignmentExclusion> = exclusions
+                .iter()
+                .filter(|e| e.person_name == person.name)
+                .collect();
+            // only visit roles that need one of the person's quals
+            for qualification in &person.qualifications {
+                let Some(group_indices) = self.groups_by_qualification.get(qualification) else {
+                    continue;
+                };
+                let cost = self.calculate_assignment_cost(person, qualification, analysis_date);
+                for &group_idx in group_indices {
+                    let group = &self.role_groups[group_idx];
+                    if !group.all_positions[0].admits(person)
+                        || !group.class.admits(person)
+                        || exclusions
+                            .iter()
+                            .any(|e| e.excludes(&person.name, &group.team, qualification))
+                    {
+                        continue;
                     }
+                    let cost = cost - self.continuity_bonus(&person.name, group);
+                    // an expired qualification keeps its edge, closed, for later dates
+                    let capacity = person.is_qualified(qualification, analysis_date) as i32;
+                    self.assignment_edges.push(self.graph.edges.len());
+                    self.graph
+                        .add_edge(person_node, self.group_nodes[group_idx], capacity, cost);
                 }
             }
         }
     }
 
-    fn add_role_to_team_edges(&mut self, teams: &[Team]) {
-        for team in teams {
-            let team_node = self.team_to_node[&team.name];
+    /// Nodes between a team's role groups and its team node, one path per member class
+    /// its composition rules count; a team without such rules takes every group straight
+    fn add_composition_nodes(&mut self, team: &Team) -> Vec<(MemberClass, usize)> {
+        let team_node = self.team_to_node[&team.name];
+        let rules = &team.composition;
+        let classes = MemberClass::split_by(rules);
 
-            for (role_id, &role_node) in &self.role_to_node {
-                if role_id.team == team.name {
-                    self.graph.add_edge(role_node, team_node, 1, 0);
+        let mut status_nodes: Vec<(Option<bool>, usize)> = vec![];
+        for selres in classes.iter().map(|class| class.selres).dedup() {
+            let node = match selres {
+                None => team_node,
+                Some(selres) => {
+                    let rule = if selres {
+                        rules.max_selres.map(CompositionRule::MaxSelres)
+                    } else {
+                        rules.min_tar.map(CompositionRule::MinTar)
+                    };
+                    self.add_composition_edge(team, rule, team_node)
                 }
-            }
+            };
+            status_nodes.push((selres, node));
+        }
+
+        let mut aw_edges = vec![];
+        let class_nodes = classes
+            .into_iter()
+            .map(|class| {
+                let status_node = status_nodes
+                    .iter()
+                    .find(|(selres, _)| *selres == class.selres)
+                    .map(|&(_, node)| node)
+                    .expect("every class has a status node");
+                let node = match class.aw {
+                    None => status_node,
+                    Some(aw) => {
+                        let rule = rules.max_aw.filter(|_| aw).map(CompositionRule::MaxAw);
+                        if rule.is_some() {
+                            aw_edges.push(self.graph.edges.len());
+                        }
+                        self.add_composition_edge(team, rule, status_node)
+                    }
+                };
+                (class, node)
+            })
+            .collect();
+
+        if aw_edges.len() > 1 {
+            self.shared_capacities.push(SharedCapacity {
+                edges: aw_edges,
+                limit: 0,
+            });
+        }
+        class_nodes
+    }
+
+    /// New node with an edge on to `to` that holds `rule`, if any; the edge opens to
+    /// the whole team until `set_composition_limits` applies the rule
+    fn add_composition_edge(
+        &mut self,
+        team: &Team,
+        rule: Option<CompositionRule>,
+        to: usize,
+    ) -> usize {
+        let node = self.graph.graph.len();
+        self.graph.graph.push(vec![]);
+        if let Some(rule) = rule {
+            self.composition_edges.push(CompositionEdge {
+                team: team.name.clone(),
+                rule,
+                edge_idx: self.graph.edges.len(),
+            });
+        }
+        self.graph
+            .add_edge(node, to, team.required_positions.len() as i32, 0);
+        node
+    }
+
+    fn add_role_to_team_edges(&mut self, class_nodes: &HashMap<&str, Vec<(MemberClass, usize)>>) {
+        let mut siblings: Vec<Vec<usize>> = vec![vec![]; self.role_groups.len()];
+        for (group, &role_node) in self.role_groups.iter().zip(&self.group_nodes) {
+            let (_, class_node) = class_nodes[group.team.as_str()]
+                .iter()
+                .find(|(class, _)| *class == group.class)
+                .expect("every group's class has a node");
+            // optional positions only win a person when no mandatory one can use them
+            let cost = if group.mandatory {
+                0
+            } else {
+                self.cost_policy.optional_position_penalty
+            };
+            siblings[group.base].push(self.graph.edges.len());
+            self.graph
+                .add_edge(role_node, *class_node, group.positions.len() as i32, cost);
         }
+
+        self.shared_capacities.extend(
+            siblings
+                .into_iter()
+                .filter(|edges| edges.len() > 1)
+                .map(|edges| SharedCapacity { edges, limit: 0 }),
+        );
     }
 
     fn add_team_to_sink_edges(&mut self, teams: &[Team]) {
+        // rank distinct priorities so the cost grows by tier, not by the raw number
+        let tiers: Vec<u32> = teams
+            .iter()
+            .filter_map(|t| t.priority)
+            .sorted()
+            .dedup()
+            .collect();
+
         for team in teams {
             let team_node = self.team_to_node[&team.name];
             let team_capacity = team.required_positions.len() as i32;
+            let tier = match team.priority {
+                Some(priority) => tiers.iter().position(|&p| p == priority).unwrap_or(0),
+                None => tiers.len(),
+            };
+            let cost = tier as i64 * self.cost_policy.team_priority_step;
 
-            self.graph
-                .add_edge(team_node, self.sink_node, team_capacity, 0);
+            match team.composition.min_staff {
+                Some(limit) => {
+                    self.composition_edges.push(CompositionEdge {
+                        team: team.name.clone(),
+                        rule: CompositionRule::MinStaff(limit),
+                        edge_idx: self.graph.edges.len(),
+                    });
+                    let floor = (limit as i32).min(team_capacity);
+                    self.graph.add_bounded_edge(
+                        team_node,
+                        self.sink_node,
+                        floor,
+                        team_capacity,
+                        cost,
+                    );
+                }
+                None => self
+                    .graph
+                    .add_edge(team_node, self.sink_node, team_capacity, cost),
+            }
+        }
+    }
+
+    /// Set each composition edge for the members `assignment_locks` already placed
+    /// outside the network: a cap leaves that many fewer places, and a floor needs
+    /// that many fewer but never more than its edge can carry
+    fn set_composition_limits(&mut self, people: &[Person], assignment_locks: &[AssignmentLock]) {
+        self.dropped_floors.clear();
+        for edge in &self.composition_edges {
+            let locked = assignment_locks
+                .iter()
+                .filter(|lock| {
+                    lock.position.is_some() && lock.team_name.as_deref() == Some(&edge.team)
+                })
+                .filter_map(|lock| people.iter().find(|p| p.name == lock.person_name))
+                .filter(|person| edge.rule.counts(person))
+                .count() as i32;
+            match edge.rule {
+                CompositionRule::MinTar(limit) | CompositionRule::MinStaff(limit) => {
+                    let capacity = self.graph.edges[edge.edge_idx].capacity;
+                    let floor = (limit as i32 - locked).clamp(0, capacity.max(0));
+                    self.graph.set_lower_bound(edge.edge_idx, floor);
+                }
+                CompositionRule::MaxSelres(limit) | CompositionRule::MaxAw(limit) => {
+                    self.graph
+                        .set_capacity(edge.edge_idx, (limit as i32 - locked).max(0));
+                }
+            }
+        }
+        for shared in &mut self.shared_capacities {
+            shared.limit = self.graph.edges[shared.edges[0]].capacity;
         }
     }
 
     fn calculate_assignment_cost(
         &self,
         person: &Person,
-        role_id: &RoleId,
+        qualification: &str,
         analysis_date: chrono::NaiveDate,
-    ) -> i32 {
-        let mut cost = 0;
+    ) -> i64 {
+        self.cost_policy
+            .assignment_cost(person, qualification, analysis_date)
+            .total()
+    }
 
-        match person.duty_status {
-            DutyStatus::Tar => cost += 0,
-            DutyStatus::Selres => cost += 15_000,
+    /// Bonus for placing a person in the slot they held in the baseline plan
+    fn continuity_bonus(&self, person_name: &str, group: &RoleGroup) -> i64 {
+        match self.baseline.get(person_name) {
+            Some(slot) if slot.is_for(group) => self.cost_policy.continuity_bonus,
+            _ => 0,
         }
+    }
 
-        if let Some(prd) = person.prd {
-            let days_remaining = (prd - analysis_date).num_days();
-            match days_remaining {
-                d if d < 0 => cost += 20_000,
-                d if d < 90 => cost += 11_000,
-                d if d < 180 => cost += 5_000,
-                d if d < 365 => cost += 1_000,
-                _ => cost += 0,
-            }
+    /// Plan to stay close to. Reprices the person edges in place, so it applies to the
+    /// next `solve` or `reoptimize` without rebuilding the network.
+    pub fn set_baseline(&mut self, baseline: PlanBaseline) {
+        let old_baseline = std::mem::replace(&mut self.baseline, baseline);
+        for &edge_idx in &self.assignment_edges {
+            let person_node = self.graph.edges[edge_idx ^ 1].to;
+            let Ok(group_idx) = self
+                .group_nodes
+                .binary_search(&self.graph.edges[edge_idx].to)
+            else {
+                continue;
+            };
+            let person_name = &self.node_to_person[&person_node];
+            let group = &self.role_groups[group_idx];
+            let old_bonus = match old_baseline.get(person_name) {
+                Some(slot) if slot.is_for(group) => self.cost_policy.continuity_bonus,
+                _ => 0,
+            };
+            let cost = self.graph.edges[edge_idx].cost + old_bonus
+                - self.continuity_bonus(person_name, group);
+            self.graph.set_cost(edge_idx, cost);
         }
+    }
 
-        if person.raterank.starts_with("AW") {
-            cost += 10_000;
-        }
+    /// Algorithm the next `solve` or `reoptimize` runs; every backend finds a plan of
+    /// the same cost
+    pub fn set_backend(&mut self, backend: BackendKind) {
+        self.backend = backend;
+    }
 
-        if !person.raterank.starts_with("A") {
-            cost += 10_000;
-        }
+    pub fn backend(&self) -> BackendKind {
+        self.backend
+    }
 
-        if person.raterank.ends_with("C")
-            || person.raterank.ends_with("CS")
-            || person.raterank.ends_with("CM")
-        {
-            cost += 5_000;
-        }
+    /// Min-cost max flow that meets every composition floor it can. While any floor is
+    /// in place the backends are bypassed for `FlowGraph::max_flow_with_bounds`; a
+    /// floor no flow can meet is dropped, and listed in `dropped_floors`, before
+    /// solving again without it.
+    ///
+    /// A limit shared by several edges crosses the limits of the nodes those edges
+    /// leave, so no single flow network holds both; `divide_shared_limits` searches
+    /// the ways of sharing it out instead, and the plan returned is optimal over all of
+    /// them.
+    ///
+    /// Of several plans that cost the same, the one `FlowGraph::canonical_flow` picks is
+    /// returned, so every backend and every warm or cold path ends on the same plan.
+    pub fn solve(&mut self) -> (i32, i64) {
+        self.set_shares(&vec![vec![]; self.shared_capacities.len()]);
+        let result = self.solve_network();
+        self.finish_solve(result)
+    }
 
-        if person.raterank.ends_with("CM") {
-            cost += 5_000;
+    fn finish_solve(&mut self, result: (i32, i64)) -> (i32, i64) {
+        self.graph.canonical_flow(self.source_node, self.sink_node);
+        if self.overfilled_limit().is_none() {
+            return result;
         }
+        self.divide_shared_limits(result)
+    }
 
-        if person.raterank.ends_with("CMD") {
-            cost += 10_000;
+    fn solve_network(&mut self) -> (i32, i64) {
+        while self.graph.has_lower_bounds() {
+            match self
+                .graph
+                .max_flow_with_bounds(self.source_node, self.sink_node)
+            {
+                Ok(result) => return result,
+                Err(error) => self.drop_floors(error),
+            }
         }
+        self.backend
+            .backend()
+            .solve(&mut self.graph, self.source_node, self.sink_node)
+    }
 
-        if ["SFF", "Chief", "F/S QAR"].contains(&role_id.qualification.as_str()) {
-            // incentive filling these positions over others
-            cost -= 1_000;
+    fn reoptimize_network(&mut self) -> (i32, i64) {
+        // a floor that rose can leave the previous flow short of it; start afresh
+        if self.graph.has_lower_bounds() {
+            return self.solve_network();
         }
-
-        cost
+        self.backend
+            .backend()
+            .reoptimize(&mut self.graph, self.source_node, self.sink_node)
     }
 
-    pub fn solve(&mut self) -> (i32, i32) {
-        self.graph
-            .min_cost_max_flow(self.source_node, self.sink_node)
-    }
+    /// Best plan over every way of dividing the shared limits between their edges,
+    /// found by branch and bound from the solved network in which each edge may carry
+    /// its whole limit.
+    ///
+    /// A branch fixes the shares of the first few edges of each limit and lets every
+    /// other edge take all that is left, so its solve is at least as good as any plan
+    /// under it. A branch whose plan overfills no limit is feasible; one whose plan is
+    /// no better than the best feasible plan so far is cut. Otherwise it splits on the
+    /// next edge of the first overfilled limit, trying the share that edge carries
+    /// first. Plans compare by floors dropped, then positions filled, then cost.
+    fn divide_shared_limits(&mut self, result: (i32, i64)) -> (i32, i64) {
+        let floors: Vec<(usize, i32)> = self
+            .composition_edges
+            .iter()
+            .map(|edge| (edge.edge_idx, self.graph.edges[edge.edge_idx].lower_bound))
+            .collect();
+        let dropped_floors = self.dropped_floors.clone();
 
-    pub fn extract_assignments(&self) -> Vec<FlowAssignment> {
-        let mut assignments = vec![];
-
-        for (person_name, &person_node) in &self.person_to_node {
-            for &edge_idx in &self.graph.graph[person_node] {
-                let edge = &self.graph.edges[edge_idx];
-                if edge.flow == 1 {
-                    if let Some(role_id) = self.node_to_role.get(&edge.to) {
-                        assignments.push(FlowAssignment {
-                            person_name: person_name.clone(),
-                            team: role_id.team.clone(),
-                            position: role_id.clone().into(),
-                            manual_override: false,
-                        });
+        let mut best: Option<((usize, i32, i64), SolvedFlow)> = None;
+        let mut branches = vec![vec![vec![]; self.shared_capacities.len()]];
+        let mut solved = Some(result);
+        while let Some(shares) = branches.pop() {
+            let (flow, cost) = match solved.take() {
+                Some(result) => result,
+                None => {
+                    for &(edge_idx, floor) in &floors {
+                        self.graph.set_lower_bound(edge_idx, floor);
                     }
+                    self.dropped_floors = dropped_floors.clone();
+                    self.set_shares(&shares);
+                    let result = self.reoptimize_network();
+                    self.graph.canonical_flow(self.source_node, self.sink_node);
+                    result
                 }
+            };
+            let rank = (self.dropped_floors.len(), -flow, cost);
+            if best
+                .as_ref()
+                .is_some_and(|(best_rank, _)| rank >= *best_rank)
+            {
+                continue;
+            }
+            let Some(limit_idx) = self.overfilled_limit() else {
+                best = Some((rank, self.solved_flow()));
+                continue;
+            };
+
+            let shared = &self.shared_capacities[limit_idx];
+            let fixed = &shares[limit_idx];
+            let edge_idx = shared.edges[fixed.len()];
+            let left = shared.limit - fixed.iter().sum::<i32>();
+            let carried = self.graph.edges[edge_idx].flow.min(left);
+            // pushed in reverse so the share closest to what the edge carries pops first
+            for share in (0..=left)
+                .sorted_by_key(|&share| ((share - carried).abs(), -share))
+                .rev()
+            {
+                let mut branch = shares.clone();
+                branch[limit_idx].push(share);
+                branches.push(branch);
             }
         }
-        assignments
+
+        let ((_, flow, cost), solved) = best.expect("some division of every limit is feasible");
+        for &(edge_idx, floor) in &floors {
+            self.graph.set_lower_bound(edge_idx, floor);
+        }
+        self.restore(solved);
+        (-flow, cost)
     }
 
-    // pub fn into_assignment_plan(self, people: &[Person], teams: &[Team]) -> AssignmentPlan {
-    //     let flow_assignments = self.extract_assignments();
-    //     let assigned_names: Vec<_> = flow_assignments.iter().map(|a| &a.person_name).collect();
+    /// First shared limit whose edges carry more than it allows
+    fn overfilled_limit(&self) -> Option<usize> {
+        self.shared_capacities.iter().position(|shared| {
+            let carried: i32 = shared
+                .edges
+                .iter()
+                .map(|&edge_idx| self.graph.edges[edge_idx].flow)
+                .sum();
+            carried > shared.limit
+        })
+    }
 
-    //     let (_assigned_people, unassigned_people): (Vec<&Person>,Vec<&Person>) = people.iter()
-    //         .partition(|p| assigned_names.contains( &&p.get_name().to_string() ));
+    /// Give the first edges of each shared limit the shares listed for it and every
+    /// other edge all that is left of the limit
+    fn set_shares(&mut self, shares: &[Vec<i32>]) {
+        for (shared, fixed) in self.shared_capacities.iter().zip(shares) {
+            let left = shared.limit - fixed.iter().sum::<i32>();
+            for (position, &edge_idx) in shared.edges.iter().enumerate() {
+                let share = fixed.get(position).copied().unwrap_or(left);
+                self.graph.set_capacity(edge_idx, share);
+            }
+        }
+    }
 
-    //     let assignments = flow_assignments.iter()
-    //         .map(|a| {
-    //             Assignment {
-    //                 person: Rc::new(people.iter().find(|p| p.get_name() == a.person_name).unwrap().clone()),
-    //                 team_name: a.team.clone(),
-    //                 qualification: a.qualification.clone(),
-    //                 score: 1 }
-    //         }).collect();
+    /// Drop the floors `error` blames: those whose edge starts at a node left short of
+    /// flow, or every floor when none does
+    fn drop_floors(&mut self, error: FlowError) {
+        let short: Vec<usize> = match &error {
+            FlowError::Infeasible { unmet } => unmet
+                .iter()
+                .filter(|(_, amount)| *amount < 0)
+                .map(|&(node, _)| node)
+                .collect(),
+            FlowError::InvalidBounds { edge_idx, .. } => {
+                vec![self.graph.edges[edge_idx ^ 1].to]
+            }
+        };
+        let floors: Vec<&CompositionEdge> = self
+            .composition_edges
+            .iter()
+            .filter(|edge| self.graph.edges[edge.edge_idx].lower_bound > 0)
+            .collect();
+        let blamed: Vec<&CompositionEdge> = floors
+            .iter()
+            .copied()
+            .filter(|edge| short.contains(&self.graph.edges[edge.edge_idx ^ 1].to))
+            .collect();
+        let dropped = if blamed.is_empty() { floors } else { blamed };
 
-    //     let mut unfilled_positions = vec![];
-    //     for team in teams {
-    //         for position in &team.required_positions {
-    //             let req = position.count;
-    //             let have = flow_assignments.iter()
-    //                 .filter(|a| a.qualification == position.qualification && a.team == team.name)
-    //                 .count();
-    //             if have < req {
-    //                 unfilled_positions.push((team.name.clone(), position.qualification.clone()))
-    //             }
-    //         }
-    //     }
+        let dropped: Vec<(usize, DroppedFloor)> = dropped
+            .into_iter()
+            .map(|edge| {
+                let floor = DroppedFloor {
+                    team_name: edge.team.clone(),
+                    rule: edge.rule,
+                    error: error.clone(),
+                };
+                (edge.edge_idx, floor)
+            })
+            .collect();
+        for (edge_idx, floor) in dropped {
+            self.graph.set_lower_bound(edge_idx, 0);
+            self.dropped_floors.push(floor);
+        }
+    }
 
-    //     AssignmentPlan{
-    //         unassigned_people: Rc::new(unassigned_people.iter().map(|p| *p).cloned().collect()),
-    //         assignments,
-    //         unfilled_positions,
-    //     }
-    // }
-}
+    /// Floors left out of the flow the network holds
+    pub fn dropped_floors(&self) -> &[DroppedFloor] {
+        &self.dropped_floors
+    }
 
-#[derive(Debug)]
-pub struct FlowAssignment {
-    pub person_name: String,
-    pub team: String,
-    pub position: Position,
-    pub manual_override: bool,
-}
+    /// The flow the network holds and the limits it was solved under
+    pub fn solved_flow(&self) -> SolvedFlow {
+        SolvedFlow {
+            flows: self.flows(),
+            dropped_floors: self.dropped_floors.clone(),
+            shares: self
+                .shared_capacities
+                .iter()
+                .map(|shared| {
+                    shared
+                        .edges
+                        .iter()
+                        .map(|&edge_idx| self.graph.edges[edge_idx].capacity)
+                        .collect()
+                })
+                .collect(),
+        }
+    }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Assignment {
-    pub person: Rc<Person>,
-    pub team_name: String,
-    pub position: Position,
-    pub score: i32,
-    pub manual_override: bool,
-}
+    /// Load `solved` back after `apply_constraints` reset the network to the inputs it
+    /// was solved under
+    pub fn restore(&mut self, solved: SolvedFlow) {
+        for floor in &solved.dropped_floors {
+            if let Some(edge) = self
+                .composition_edges
+                .iter()
+                .find(|edge| edge.team == floor.team_name && edge.rule == floor.rule)
+            {
+                self.graph.set_lower_bound(edge.edge_idx, 0);
+            }
+        }
+        self.dropped_floors = solved.dropped_floors;
+        self.set_shares(&solved.shares);
+        self.load_flows(&solved.flows);
+    }
 
-impl Assignment {
-    pub fn role_id(&self) -> String {
-        self.position.role_id(&self.team_name)
+    /// Solve a copy of the network with every other backend and report where its flow
+    /// or cost differs from the flow the network holds now. Floors bypass every
+    /// backend alike, so a network with any has nothing to compare.
+    pub fn compare_backends(&self) -> Vec<BackendMismatch> {
+        if self.graph.has_lower_bounds() {
+            return vec![];
+        }
+        let flow = self.graph.flow_value(self.source_node);
+        let cost = self.graph.total_cost();
+        BackendKind::ALL
+            .into_iter()
+            .filter(|&backend| backend != self.backend)
+            .filter_map(|backend| {
+                let mut graph = self.graph.clone();
+                let (other_flow, other_cost) =
+                    backend
+                        .backend()
+                        .solve(&mut graph, self.source_node, self.sink_node);
+                ((other_flow, other_cost) != (flow, cost)).then_some(BackendMismatch {
+                    backend,
+                    flow: other_flow,
+                    cost: other_cost,
+                    chosen: self.backend,
+                    chosen_flow: flow,
+                    chosen_cost: cost,
+                })
+            })
+            .collect()
     }
-}
 
-impl Display for Assignment {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "<{}> {} as {}",
-            &self.score, &self.person, &self.position.qualification
-        )
+    /// Start measuring a run; hand the timer to `finish_run` once the flow is final
+    pub fn start_run(&self) -> RunTimer {
+        RunTimer {
+            started: chrono::Utc::now(),
+            augmentations: self.graph.augmentations(),
+        }
     }
-}
+
+    /// Stats for the run `timer` started, with the flow the network holds now
+    pub fn finish_run(&self, timer: RunTimer, locks_applied: usize) -> SolveStats {
+        SolveStats {
+            nodes: self.graph.graph.len(),
+            edges: self.graph.edges.len() / 2,
+            augmenting_iterations: self.graph.augmentations() - timer.augmentations,
+            total_flow: self.graph.flow_value(self.source_node),
+            total_cost: self.graph.total_cost(),
+            wall_time: chrono::Utc::now() - timer.started,
+            locks_applied,
+        }
+    }
+
+    /// Re-apply locks, exclusions and the analysis date to the network, keeping the
+    /// current flow so `reoptimize` only has to repair it.
+    ///
+    /// Meant for a solver built over the whole roster with no locks or exclusions:
+    /// people who are locked or past their PRD lose their source edge and every person
+    /// edge, so nothing of them is left to sway `FlowGraph::canonical_flow`; other person
+    /// edges close when excluded or when the qualification has expired, locked instances
+    /// close their slot in the role node, and every person edge is repriced for the new
+    /// date.
+    pub fn apply_constraints(
+        &mut self,
+        people: &[Person],
+        analysis_date: chrono::NaiveDate,
+        assignment_locks: &[AssignmentLock],
+        exclusions: &[AssignmentExclusion],
+    ) {
+        let locked_people: HashSet<&str> = assignment_locks
+            .iter()
+            .map(|lock| lock.person_name.as_str())
+            .collect();
+        let locked_role_ids: HashSet<RoleId> = assignment_locks
+            .iter()
+            .filter_map(|lock| match (&lock.team_name, &lock.position) {
+                (Some(team), Some(position)) => Some(position.as_role_id(team)),
+                _ => None,
+            })
+            .collect();
+
+        for person in people {
+            let Some(&person_node) = self.person_to_node.get(&person.name) else {
+                continue;
+            };
+            let available = !locked_people.contains(person.name.as_str())
+                && person.prd.is_none_or(|prd| prd > analysis_date);
+
+            for edge_idx in self.graph.graph[person_node].clone() {
+                let to = self.graph.edges[edge_idx].to;
+                if to == self.source_node {
+                    self.graph.set_capacity(edge_idx ^ 1, available as i32);
+                } else if let Ok(group_idx) = self.group_nodes.binary_search(&to) {
+                    let group = &self.role_groups[group_idx];
+                    let excluded = exclusions
+                        .iter()
+                        .any(|e| e.excludes(&person.name, &group.team, &group.qualification))
+                        || !person.is_qualified(&group.qualification, analysis_date);
+                    let cost =
+                        self.calculate_assignment_cost(person, &group.qualification, analysis_date)
+                            - self.continuity_bonus(&person.name, group);
+                    self.graph
+                        .set_capacity(edge_idx, (available && !excluded) as i32);
+                    self.graph.set_cost(edge_idx, cost);
+                }
+            }
+        }
+
+        for (group, &role_node) in self.role_groups.iter_mut().zip(&self.group_nodes) {
+            group.positions = group
+                .all_positions
+                .iter()
+                .filter(|p| !locked_role_ids.contains(&p.as_role_id(&group.team)))
+                .cloned()
+                .collect();
+
+            let team_edge = self.graph.graph[role_node]
+                .iter()
+                .copied()
+                .find(|&edge_idx| edge_idx % 2 == 0)
+                .expect("every role node has a team edge");
+            self.graph
+                .set_capacity(team_edge, group.positions.len() as i32);
+        }
+        self.set_composition_limits(people, assignment_locks);
+    }
+
+    /// Repair the previous flow after `apply_constraints`; same result as `solve`
+    pub fn reoptimize(&mut self) -> (i32, i64) {
+        self.set_shares(&vec![vec![]; self.shared_capacities.len()]);
+        let result = self.reoptimize_network();
+        self.finish_solve(result)
+    }
+
+    /// Up to `k` distinct plans, best first: most positions filled, then lowest cost.
+    ///
+    /// Murty's partitioning over the person -> role edges: each ranked plan spawns one
+    /// subproblem per assignment it made, keeping the earlier assignments fixed and
+    /// forbidding that one. Every subproblem is answered from its parent's optimal flow
+    /// by the cheapest residual cycle that undoes the forbidden assignment, so no
+    /// subproblem needs a full re-solve. The graph is left holding the best plan.
+    pub fn top_k_plans(&mut self, k: usize) -> Vec<RankedPlan> {
+        let (flow, cost) = self.solve();
+        if k == 0 {
+            return vec![];
+        }
+
+        // sink -> source edge carrying the total flow; its reverse lets a cycle give up
+        // a filled position at a cost larger than any real plan difference
+        let num_edges = self.graph.edges.len();
+        self.graph.add_edge(
+            self.sink_node,
+            self.source_node,
+            flow,
+            -DROPPED_POSITION_COST,
+        );
+        self.graph.edges[num_edges].flow = flow;
+        self.graph.edges[num_edges + 1].flow = -flow;
+        let drop_edge = num_edges + 1;
+
+        let mut plans = vec![PlanNode {
+            flows: self.graph.edges.iter().map(|e| e.flow).collect(),
+            flow,
+            cost,
+            forced: vec![],
+            excluded: vec![],
+        }];
+        let mut candidates = self.partition_plan(&plans[0], 0, drop_edge);
+
+        while plans.len() < k {
+            // min_by_key keeps the first of equal keys, so ties go to the oldest candidate
+            let Some(best) = candidates
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, c)| (-c.flow, c.cost))
+                .map(|(idx, _)| idx)
+            else {
+                break;
+            };
+            let candidate = candidates.remove(best);
+
+            self.load_flows(&plans[candidate.parent].flows);
+            self.graph.push_flow(&candidate.cycle, 1);
+            let plan = PlanNode {
+                flows: self.graph.edges.iter().map(|e| e.flow).collect(),
+                flow: candidate.flow,
+                cost: candidate.cost,
+                forced: candidate.forced,
+                excluded: candidate.excluded,
+            };
+            candidates.extend(self.partition_plan(&plan, plans.len(), drop_edge));
+            plans.push(plan);
+        }
+
+        let ranked = plans
+            .iter()
+            .map(|plan| {
+                self.load_flows(&plan.flows);
+                RankedPlan {
+                    cost: plan.cost,
+                    assignments: self.extract_assignments(),
+                    unfilled_reasons: self.explain_unfilled(),
+                }
+            })
+            .collect();
+
+        self.load_flows(&plans[0].flows);
+        self.graph.truncate_edges(num_edges);
+        ranked
+    }
+
+    /// What one more holder of each required qualification would gain, most valuable
+    /// first. Must follow a solve; the network is left holding the same flow.
+    ///
+    /// Each probe adds a notional TAR E-5 with no PRD who holds only that
+    /// qualification and meets every rate limit, reoptimizes as `reoptimize` does, then
+    /// removes them again. Locks, exclusions and composition rules hold; pairing rules
+    /// are not re-enforced, so the gains are an upper bound when those rules bind.
+    pub fn shadow_prices(&mut self, analysis_date: chrono::NaiveDate) -> Vec<ShadowPrice> {
+        let solved = self.solved_flow();
+        let floors: Vec<(usize, i32)> = self
+            .composition_edges
+            .iter()
+            .map(|edge| (edge.edge_idx, self.graph.edges[edge.edge_idx].lower_bound))
+            .collect();
+        let num_edges = self.graph.edges.len();
+        let num_nodes = self.graph.graph.len();
+        let filled = self.graph.flow_value(self.source_node);
+        let mandatory_filled = self.mandatory_flow();
+        let cost = self.graph.total_cost();
+        let total_positions: usize = self
+            .base_groups()
+            .map(|(_, group)| group.all_positions.len())
+            .sum();
+
+        let qualifications: Vec<String> = self
+            .groups_by_qualification
+            .keys()
+            .cloned()
+            .sorted()
+            .collect();
+        let mut prices = vec![];
+        for qualification in qualifications {
+            let notional = Person {
+                name: "Notional".to_string(),
+                raterank: "AM2".into(),
+                duty_status: DutyStatus::Tar,
+                qualifications: [qualification.clone()].into(),
+                prd: None,
+                qualification_expirations: Default::default(),
+                qualification_completions: Default::default(),
+            };
+            let edge_cost = self
+                .cost_policy
+                .assignment_cost(&notional, &qualification, analysis_date)
+                .total();
+
+            let person_node = self.graph.graph.len();
+            self.graph.graph.push(vec![]);
+            self.graph.add_edge(self.source_node, person_node, 1, 0);
+            for &group_idx in &self.groups_by_qualification[&qualification] {
+                if self.role_groups[group_idx].class.admits(&notional) {
+                    self.graph
+                        .add_edge(person_node, self.group_nodes[group_idx], 1, edge_cost);
+                }
+            }
+            let (probe_filled, probe_cost) = self.reoptimize();
+            let positions_gained = probe_filled - filled;
+
+            prices.push(ShadowPrice {
+                qualification,
+                positions_gained,
+                mandatory_gained: self.mandatory_flow() - mandatory_filled,
+                fill_rate_gain: if total_positions > 0 {
+                    positions_gained as f64 / total_positions as f64 * 100.0
+                } else {
+                    0.0
+                },
+                cost_change: probe_cost - cost,
+            });
+
+            self.graph.truncate_edges(num_edges);
+            self.graph.graph.truncate(num_nodes);
+            for &(edge_idx, floor) in &floors {
+                self.graph.set_lower_bound(edge_idx, floor);
+            }
+            self.restore(solved.clone());
+            self.graph.settle_potentials();
+        }
+
+        prices.sort_by_key(|p| {
+            (
+                std::cmp::Reverse(p.mandatory_gained),
+                std::cmp::Reverse(p.positions_gained),
+                p.cost_change,
+            )
+        });
+        prices
+    }
+
+    /// Flow into mandatory role groups
+    fn mandatory_flow(&self) -> i32 {
+        self.role_groups
+            .iter()
+            .zip(&self.group_nodes)
+            .filter(|(group, _)| group.mandatory)
+            .flat_map(|(_, &role_node)| &self.graph.graph[role_node])
+            .filter(|&&edge_idx| edge_idx % 2 == 0)
+            .map(|&edge_idx| self.graph.edges[edge_idx].flow)
+            .sum()
+    }
+
+    /// Flow on every edge, to hand back to `load_flows` later
+    pub fn flows(&self) -> Vec<i32> {
+        self.graph.edges.iter().map(|e| e.flow).collect()
+    }
+
+    /// Restore a flow from `flows`; it must fit the current capacities
+    pub fn load_flows(&mut self, flows: &[i32]) {
+        for (edge, &flow) in self.graph.edges.iter_mut().zip(flows) {
+            edge.flow = flow;
+        }
+    }
+
+    /// Best plan of each Murty subproblem under `plan`
+    fn partition_plan(
+        &mut self,
+        plan: &PlanNode,
+        plan_idx: usize,
+        drop_edge: usize,
+    ) -> Vec<PlanCandidate> {
+        self.load_flows(&plan.flows);
+
+        // freeze an edge by letting neither it nor its reverse change flow
+        let mut saved_capacities = vec![];
+        let mut freeze = |graph: &mut FlowGraph, edge_idx: usize| {
+            for idx in [edge_idx, edge_idx ^ 1] {
+                saved_capacities.push((idx, graph.edges[idx].capacity));
+                graph.edges[idx].capacity = graph.edges[idx].flow;
+            }
+        };
+        for &edge_idx in plan.forced.iter().chain(&plan.excluded) {
+            freeze(&mut self.graph, edge_idx);
+        }
+
+        let potentials = self.graph.residual_potentials();
+        let free_edges: Vec<usize> = self
+            .assignment_edges
+            .iter()
+            .copied()
+            .filter(|&idx| self.graph.edges[idx].flow > 0 && !plan.forced.contains(&idx))
+            .collect();
+
+        let mut candidates = vec![];
+        for (i, &edge_idx) in free_edges.iter().enumerate() {
+            // undo this assignment while every earlier one stays fixed
+            if let Some((cycle, cycle_cost)) =
+                self.graph.cheapest_cycle_through(edge_idx ^ 1, &potentials)
+            {
+                let dropped = cycle.contains(&drop_edge);
+                let mut forced = plan.forced.clone();
+                forced.extend_from_slice(&free_edges[..i]);
+                let mut excluded = plan.excluded.clone();
+                excluded.push(edge_idx);
+
+                candidates.push(PlanCandidate {
+                    parent: plan_idx,
+                    flow: plan.flow - dropped as i32,
+                    cost: plan.cost + cycle_cost - if dropped { DROPPED_POSITION_COST } else { 0 },
+                    cycle,
+                    forced,
+                    excluded,
+                });
+            }
+            freeze(&mut self.graph, edge_idx);
+        }
+
+        for (idx, capacity) in saved_capacities.into_iter().rev() {
+            self.graph.edges[idx].capacity = capacity;
+        }
+        candidates
+    }
+
+    /// Indices of the groups sharing `base`'s instances, `base` first
+    fn siblings(&self, base: usize) -> impl Iterator<Item = usize> + '_ {
+        (base..self.role_groups.len()).take_while(move |&idx| self.role_groups[idx].base == base)
+    }
+
+    /// People whose flow ends in the role node of `base` or one of its siblings, in
+    /// person node order
+    fn group_members(&self, base: usize) -> Vec<&String> {
+        self.siblings(base)
+            .flat_map(|group_idx| &self.graph.graph[self.group_nodes[group_idx]])
+            // the paired forward edge person -> role carries the flow
+            .filter(|&&edge_idx| self.graph.edges[edge_idx ^ 1].flow > 0)
+            .filter_map(|&edge_idx| {
+                let person_node = self.graph.edges[edge_idx].to;
+                Some((person_node, self.node_to_person.get(&person_node)?))
+            })
+            .sorted()
+            .map(|(_, person_name)| person_name)
+            .collect()
+    }
+
+    /// Groups that own their instances: every group but the extra member classes
+    fn base_groups(&self) -> impl Iterator<Item = (usize, &RoleGroup)> {
+        self.role_groups
+            .iter()
+            .enumerate()
+            .filter(|(group_idx, group)| group.base == *group_idx)
+    }
+
+    /// Expand the flow through each role node back into per-instance roles: members
+    /// take the group's instances in ascending order
+    fn expand_role_flows(&self) -> Vec<(&String, &RoleGroup, &Position)> {
+        self.base_groups()
+            .flat_map(|(group_idx, group)| {
+                self.group_members(group_idx)
+                    .into_iter()
+                    .zip(&group.positions)
+                    .map(move |(person_name, position)| (person_name, group, position))
+            })
+            .collect()
+    }
+
+    /// Explain every position instance left without flow by walking the residual
+    /// graph: each reverse edge into the role node leads back to a qualified person,
+    /// and the role that person's flow reached shows who claimed them instead.
+    pub fn explain_unfilled(&self) -> HashMap<RoleId, UnfilledReason> {
+        let claimed_roles: HashMap<&String, RoleId> = self
+            .expand_role_flows()
+            .into_iter()
+            .map(|(person_name, group, position)| (person_name, position.as_role_id(&group.team)))
+            .collect();
+
+        let mut reasons = HashMap::new();
+        for (group_idx, group) in self.base_groups() {
+            let filled = self.group_members(group_idx).len();
+            if filled >= group.positions.len() {
+                continue;
+            }
+
+            let mut competitors: Vec<Competitor> = self
+                .siblings(group_idx)
+                .flat_map(|sibling| &self.graph.graph[self.group_nodes[sibling]])
+                // closed person edges belong to excluded people or expired qualifications
+                .filter(|&&edge_idx| self.graph.edges[edge_idx ^ 1].capacity > 0)
+                .filter_map(|&edge_idx| {
+                    let person_name = self.node_to_person.get(&self.graph.edges[edge_idx].to)?;
+                    let other_role = claimed_roles.get(person_name)?;
+                    Some(Competitor {
+                        person_name: person_name.clone(),
+                        role_id: other_role.to_string(),
+                        locked: false,
+                    })
+                })
+                .collect();
+
+            let reason = if competitors.is_empty() {
+                UnfilledReason::NoQualifiedPersonnel
+            } else {
+                competitors.sort_by(|a, b| a.person_name.cmp(&b.person_name));
+                UnfilledReason::UsedElsewhere { competitors }
+            };
+            for position in &group.positions[filled..] {
+                reasons.insert(position.as_role_id(&group.team), reason.clone());
+            }
+        }
+
+        reasons
+    }
+
+    pub fn extract_assignments(&self) -> Vec<FlowAssignment> {
+        self.expand_role_flows()
+            .into_iter()
+            .map(|(person_name, group, position)| FlowAssignment {
+                person_name: person_name.clone(),
+                team: group.team.clone(),
+                position: position.clone(),
+                manual_override: false,
+            })
+            .collect()
+    }
+
+    // pub fn into_assignment_plan(self, people: &[Person], teams: &[Team]) -> AssignmentPlan {
+    //     let flow_assignments = self.extract_assignments();
+    //     let assigned_names: Vec<_> = flow_assignments.iter().map(|a| &a.person_name).collect();
+
+    //     let (_assigned_people, unassigned_people): (Vec<&Person>,Vec<&Person>) = people.iter()
+    //         .partition(|p| assigned_names.contains( &&p.get_name().to_string() ));
+
+    //     let assignments = flow_assignments.iter()
+    //         .map(|a| {
+    //             Assignment {
+    //                 person: Rc::new(people.iter().find(|p| p.get_name() == a.person_name).unwrap().clone()),
+    //                 team_name: a.team.clone(),
+    //                 qualification: a.qualification.clone(),
+    //                 score: 1 }
+    //         }).collect();
+
+    //     let mut unfilled_positions = vec![];
+    //     for team in teams {
+    //         for position in &team.required_positions {
+    //             let req = position.count;
+    //             let have = flow_assignments.iter()
+    //                 .filter(|a| a.qualification == position.qualification && a.team == team.name)
+    //                 .count();
+    //             if have < req {
+    //                 unfilled_positions.push((team.name.clone(), position.qualification.clone()))
+    //             }
+    //         }
+    //     }
+
+    //     AssignmentPlan{
+    //         unassigned_people: Rc::new(unassigned_people.iter().map(|p| *p).cloned().collect()),
+    //         assignments,
+    //         unfilled_positions,
+    //     }
+    // }
+}
+
+/// A solved network's flow and limits, from `AssignmentSolver::solved_flow`
+#[derive(Clone)]
+pub struct SolvedFlow {
+    flows: Vec<i32>,
+    dropped_floors: Vec<DroppedFloor>,
+    /// Share of each edge of every shared limit
+    shares: Vec<Vec<i32>>,
+}
+
+/// Penalty `top_k_plans` charges for leaving one more position empty; dwarfs any
+/// real plan cost so plans rank by positions filled first, like `min_cost_max_flow`
+const DROPPED_POSITION_COST: i64 = 1_000_000_000_000_000;
+
+/// A ranked plan as flows over the solver's graph plus the Murty constraints it
+/// was found under (edge indices)
+struct PlanNode {
+    flows: Vec<i32>,
+    flow: i32,
+    cost: i64,
+    forced: Vec<usize>,
+    excluded: Vec<usize>,
+}
+
+/// Best plan of one Murty subproblem: its parent plan plus one residual cycle
+struct PlanCandidate {
+    parent: usize,
+    cycle: Vec<usize>,
+    flow: i32,
+    cost: i64,
+    forced: Vec<usize>,
+    excluded: Vec<usize>,
+}
+
+/// When a solver run started, and how much work its network had done by then
+pub struct RunTimer {
+    started: chrono::DateTime<chrono::Utc>,
+    augmentations: usize,
+}
+
+/// Size of the solver's network and the work one run took
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SolveStats {
+    pub nodes: usize,
+    /// Forward edges; each has a reverse twin in the residual graph
+    pub edges: usize,
+    /// Augmenting paths and cycles pushed during the run
+    pub augmenting_iterations: usize,
+    /// People the solver placed; locked placements never enter the network
+    pub total_flow: i32,
+    pub total_cost: i64,
+    pub wall_time: chrono::TimeDelta,
+    pub locks_applied: usize,
+}
+
+impl SolveStats {
+    pub fn wall_time_ms(&self) -> f64 {
+        self.wall_time
+            .num_microseconds()
+            .map_or(f64::INFINITY, |us| us as f64 / 1000.0)
+    }
+}
+
+/// Marginal value of one more qualified person, from `AssignmentSolver::shadow_prices`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadowPrice {
+    pub qualification: String,
+    /// Extra positions the plan would fill; 0 or 1
+    pub positions_gained: i32,
+    pub mandatory_gained: i32,
+    /// Percentage points added to the plan's fill rate
+    pub fill_rate_gain: f64,
+    /// Change in total cost; negative when the same positions fill more cheaply.
+    /// Filling an extra position usually adds cost.
+    pub cost_change: i64,
+}
+
+/// One of the plans returned by `AssignmentSolver::top_k_plans`
+#[derive(Debug)]
+pub struct RankedPlan {
+    pub cost: i64,
+    pub assignments: Vec<FlowAssignment>,
+    pub unfilled_reasons: HashMap<RoleId, UnfilledReason>,
+}
+
+#[derive(Debug)]
+pub struct FlowAssignment {
+    pub person_name: String,
+    pub team: String,
+    pub position: Position,
+    pub manual_override: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub person: Rc<Person>,
+    pub team_name: String,
+    pub position: Position,
+    /// Cost of the person -> role edge; locked placements show what the edge would cost
+    pub score: i64,
+    pub cost: CostBreakdown,
+    pub manual_override: bool,
+}
+
+impl Assignment {
+    pub fn role_id(&self) -> String {
+        self.position.role_id(&self.team_name)
+    }
+}
+
+impl Display for Assignment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<{}> {} as {}",
+            &self.score, &self.person, &self.position.qualification
+        )
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct AssignmentPlan {
     pub assignments: Vec<Assignment>,
-    pub unfilled_positions: Vec<(String, String)>,
+    /// Optional positions left empty
+    pub unfilled_positions: Vec<UnfilledPosition>,
+    /// Mandatory positions left empty - these are critical gaps
+    pub unfilled_mandatory_positions: Vec<UnfilledPosition>,
     pub unassigned_people: Rc<Vec<Person>>,
+    /// Pairing rules the solver could not keep
+    pub unsatisfied_pairings: Vec<PairingConstraint>,
+    /// Team composition rules the solver could not keep
+    pub composition_violations: Vec<CompositionViolation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnfilledPosition {
+    pub team_name: String,
+    pub role_id: String,
+    pub position: Position,
+    pub reason: UnfilledReason,
+}
+
+/// Why the solver could not fill a position
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnfilledReason {
+    /// Nobody on the roster holds the qualification
+    NoQualifiedPersonnel,
+    /// Everyone qualified is filling a position the solver valued more, or is locked elsewhere
+    UsedElsewhere { competitors: Vec<Competitor> },
+    /// The only qualified people are excluded from this team or qualification
+    Excluded { people: Vec<String> },
+    /// The only qualified people let the qualification expire before the analysis date
+    Expired { people: Vec<String> },
+    /// The only qualified people are still in training on the analysis date
+    InTraining { people: Vec<String> },
+    /// The only qualified people have a PRD on or before the analysis date
+    RotatedOut { people: Vec<String> },
+    /// The only qualified people fall outside the position's paygrade or rate limits
+    Ineligible { people: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Competitor {
+    pub person_name: String,
+    pub role_id: String,
+    pub locked: bool,
+}
+
+impl UnfilledReason {
+    pub fn summary(&self) -> String {
+        match self {
+            UnfilledReason::NoQualifiedPersonnel => "Nobody holds this qualification".to_string(),
+            UnfilledReason::UsedElsewhere { competitors } => {
+                format!("All {} qualified in higher-value roles", competitors.len())
+            }
+            UnfilledReason::Excluded { people } => {
+                format!("{} qualified excluded", people.len())
+            }
+            UnfilledReason::Expired { people } => {
+                format!("{} qualified expired", people.len())
+            }
+            UnfilledReason::InTraining { people } => {
+                format!("{} still in training", people.len())
+            }
+            UnfilledReason::RotatedOut { people } => {
+                format!("{} qualified rotated out (PRD)", people.len())
+            }
+            UnfilledReason::Ineligible { people } => {
+                format!("{} qualified outside paygrade/rate", people.len())
+            }
+        }
+    }
+}
+
+impl Display for UnfilledReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnfilledReason::NoQualifiedPersonnel => write!(f, "{}", self.summary()),
+            UnfilledReason::UsedElsewhere { competitors } => {
+                let details = competitors
+                    .iter()
+                    .map(|c| {
+                        let lock = if c.locked { " (locked)" } else { "" };
+                        format!("{} in {}{}", c.person_name, c.role_id, lock)
+                    })
+                    .join(", ");
+                write!(f, "{}: {}", self.summary(), details)
+            }
+            UnfilledReason::Excluded { people }
+            | UnfilledReason::Expired { people }
+            | UnfilledReason::InTraining { people }
+            | UnfilledReason::RotatedOut { people }
+            | UnfilledReason::Ineligible { people } => {
+                write!(f, "{}: {}", self.summary(), people.join(", "))
+            }
+        }
+    }
+}
+
+impl AssignmentPlan {
+    /// Every empty position, mandatory or optional
+    pub fn total_unfilled(&self) -> usize {
+        self.unfilled_positions.len() + self.unfilled_mandatory_positions.len()
+    }
+
+    /// Share of positions filled, 0 to 100
+    pub fn fill_rate(&self) -> f64 {
+        let total_positions = self.assignments.len() + self.total_unfilled();
+        if total_positions == 0 {
+            return 100.0;
+        }
+        self.assignments.len() as f64 / total_positions as f64 * 100.0
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -337,3 +1650,1076 @@ pub struct AssignmentLock {
     pub team_name: Option<String>,
     pub position: Option<Position>,
 }
+
+/// Keeps a person off a team, off a qualification, or off one qualification on one
+/// team, while the solver stays free to place them anywhere else
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AssignmentExclusion {
+    pub person_name: String,
+    /// `None` excludes the person from every team
+    pub team_name: Option<String>,
+    /// `None` excludes the person from every qualification
+    pub qualification: Option<String>,
+}
+
+impl AssignmentExclusion {
+    pub fn excludes(&self, person_name: &str, team_name: &str, qualification: &str) -> bool {
+        self.person_name == person_name
+            && self
+                .team_name
+                .as_deref()
+                .is_none_or(|team| team == team_name)
+            && self
+                .qualification
+                .as_deref()
+                .is_none_or(|qual| qual == qualification)
+    }
+
+    /// What the person is kept off, e.g. "on Det", "as SFF" or "as SFF on Det"
+    pub fn target(&self) -> String {
+        match (&self.team_name, &self.qualification) {
+            (Some(team), Some(qual)) => format!("as {} on {}", qual, team),
+            (Some(team), None) => format!("on {}", team),
+            (None, Some(qual)) => format!("as {}", qual),
+            (None, None) => "anywhere".to_string(),
+        }
+    }
+}
+
+impl Display for AssignmentExclusion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} not {}", self.person_name, self.target())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::engine::flow_graph::tests::Rng;
+    use crate::engine::person::DutyStatus;
+    use crate::engine::rules::composition_violations;
+    use chrono::NaiveDate;
+
+    pub(crate) fn person(name: &str, quals: &[&str]) -> Person {
+        Person {
+            name: name.to_string(),
+            raterank: "AM2".into(),
+            duty_status: DutyStatus::Tar,
+            qualifications: quals.iter().map(|q| q.to_string()).collect(),
+            prd: None,
+            qualification_expirations: Default::default(),
+            qualification_completions: Default::default(),
+        }
+    }
+
+    pub(crate) fn team(name: &str, priority: Option<u32>, quals: &[&str]) -> Team {
+        Team {
+            name: name.to_string(),
+            priority,
+            required_positions: quals
+                .iter()
+                .map(|q| Position {
+                    qualification: q.to_string(),
+                    instance: 1,
+                    mandatory: false,
+                    ..Default::default()
+                })
+                .collect(),
+            composition: CompositionRules::default(),
+        }
+    }
+
+    impl Person {
+        pub(crate) fn with_raterank(mut self, raterank: &str) -> Self {
+            self.raterank = raterank.into();
+            self
+        }
+
+        pub(crate) fn with_status(mut self, duty_status: DutyStatus) -> Self {
+            self.duty_status = duty_status;
+            self
+        }
+
+        pub(crate) fn with_prd(mut self, prd: Option<NaiveDate>) -> Self {
+            self.prd = prd;
+            self
+        }
+    }
+
+    impl Team {
+        pub(crate) fn with_composition(mut self, composition: CompositionRules) -> Self {
+            self.composition = composition;
+            self
+        }
+
+        /// Number the positions of each qualification 1, 2, 3...
+        pub(crate) fn numbered(mut self) -> Self {
+            let mut counts: HashMap<String, u32> = HashMap::new();
+            for position in &mut self.required_positions {
+                let count = counts.entry(position.qualification.clone()).or_default();
+                *count += 1;
+                position.instance = *count;
+            }
+            self
+        }
+
+        pub(crate) fn mandatory(mut self) -> Self {
+            for position in &mut self.required_positions {
+                position.mandatory = true;
+            }
+            self
+        }
+    }
+
+    #[test]
+    fn test_baseline_keeps_people_in_place() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        // Baker rotates within the year, so a fresh solve prefers Adams
+        let baker = person("Baker", &["PC"]).with_prd(NaiveDate::from_ymd_opt(2025, 10, 1));
+        let people = vec![person("Adams", &["PC"]), baker];
+        let teams = vec![team("Det", Some(1), &["PC"])];
+        let placed = |solver: &AssignmentSolver| -> Vec<String> {
+            solver
+                .extract_assignments()
+                .into_iter()
+                .map(|a| a.person_name)
+                .collect()
+        };
+
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        solver.solve();
+        assert_eq!(placed(&solver), vec!["Adams"]);
+
+        let baseline = PlanBaseline::from([(
+            "Baker".to_string(),
+            PlanSlot {
+                team_name: "Det".to_string(),
+                qualification: "PC".to_string(),
+            },
+        )]);
+        solver.set_baseline(baseline.clone());
+        solver.reoptimize();
+        assert_eq!(placed(&solver), vec!["Baker"]);
+
+        solver.set_baseline(PlanBaseline::new());
+        solver.reoptimize();
+        assert_eq!(placed(&solver), vec!["Adams"]);
+
+        // staying is only worth the configured bonus
+        let policy = CostPolicy {
+            continuity_bonus: 500,
+            ..Default::default()
+        };
+        let mut solver = AssignmentSolver::new(&people, &teams, date, None, &[], &policy);
+        solver.set_baseline(baseline);
+        solver.solve();
+        assert_eq!(placed(&solver), vec!["Adams"]);
+    }
+
+    #[test]
+    fn test_scarce_person_goes_to_higher_priority_team() {
+        let people = vec![person("Smith", &["SFF"])];
+        // listed first so input order alone would favor Det
+        let teams = vec![
+            team("Det", Some(2), &["SFF"]),
+            team("Home Guard", Some(1), &["SFF"]),
+        ];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        let (flow, _cost) = solver.solve();
+        let assignments = solver.extract_assignments();
+
+        assert_eq!(flow, 1);
+        assert_eq!(assignments[0].team, "Home Guard");
+    }
+
+    #[test]
+    fn test_min_staff_floor_is_met_or_reported() {
+        let people = vec![person("Adams", &["SFF"]), person("Baker", &["SFF"])];
+        let mut det = team("Det", Some(2), &["SFF", "QAR"]);
+        det.composition.min_staff = Some(1);
+        let teams = vec![team("Alpha", Some(1), &["SFF", "SFF"]), det];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let team_counts = |solver: &AssignmentSolver| {
+            solver
+                .extract_assignments()
+                .into_iter()
+                .counts_by(|a| a.team)
+                .into_iter()
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        // priority alone would send both to Alpha
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        assert_eq!(solver.solve().0, 2);
+        assert_eq!(
+            team_counts(&solver),
+            vec![("Alpha".to_string(), 1), ("Det".to_string(), 1)]
+        );
+        assert_eq!(solver.dropped_floors(), &[]);
+
+        // a lock on Det meets the floor, so the rest go by priority again
+        let lock = AssignmentLock {
+            person_name: "Adams".to_string(),
+            team_name: Some("Det".to_string()),
+            position: Some(teams[1].required_positions[0].clone()),
+        };
+        solver.apply_constraints(&people, date, std::slice::from_ref(&lock), &[]);
+        solver.reoptimize();
+        assert_eq!(team_counts(&solver), vec![("Alpha".to_string(), 1)]);
+
+        // nobody can take Det's QAR once its SFF is gone, so the floor is dropped
+        let mut short = teams.clone();
+        short[1].required_positions.remove(0);
+        let mut solver =
+            AssignmentSolver::new(&people, &short, date, None, &[], &CostPolicy::default());
+        assert_eq!(solver.solve().0, 2);
+        assert_eq!(team_counts(&solver), vec![("Alpha".to_string(), 2)]);
+        let dropped = solver.dropped_floors();
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].rule, CompositionRule::MinStaff(1));
+        assert!(matches!(dropped[0].error, FlowError::Infeasible { .. }));
+    }
+
+    #[test]
+    fn test_composition_caps_and_floors_hold_in_the_network() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        // TAR cost less than SELRES, so without rules Det would take both TAR PCs;
+        // three floors and the cap can only all hold with one Det position empty
+        let people = vec![
+            person("Adams", &["PC"]),
+            person("Baker", &["PC"]),
+            person("Clark", &["PC"]).with_status(DutyStatus::Selres),
+            person("Diaz", &["PC"]).with_status(DutyStatus::Selres),
+            person("Evans", &["PC"]).with_status(DutyStatus::Selres),
+        ];
+        let floor = CompositionRules {
+            min_tar: Some(1),
+            ..Default::default()
+        };
+        let teams = vec![
+            team("Det", Some(1), &["PC", "PC"]).with_composition(CompositionRules {
+                max_selres: Some(1),
+                ..Default::default()
+            }),
+            team("Day Check", Some(2), &["PC", "PC"]).with_composition(floor),
+            team("Night Check", Some(3), &["PC"]).with_composition(floor),
+        ];
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        solver.solve();
+        let assignments = solver.extract_assignments();
+        assert_eq!(assignments.len(), 4);
+        assert_eq!(solver.dropped_floors(), &[]);
+        assert_eq!(
+            composition_violations(
+                &teams,
+                &people,
+                assignments
+                    .iter()
+                    .map(|a| (a.person_name.as_str(), a.team.as_str())),
+            ),
+            vec![]
+        );
+
+        // the AW cap spans both duty statuses and the PC instances span every class, so
+        // each first admits more than its share; dividing them by what the first solve
+        // carried would seat Adams and leave CDI empty, but only Baker can take CDI
+        let people = vec![
+            person("Adams", &["PC"]).with_raterank("AWS2"),
+            person("Baker", &["CDI"])
+                .with_raterank("AWS2")
+                .with_status(DutyStatus::Selres),
+            person("Clark", &["PC"]).with_status(DutyStatus::Selres),
+            person("Diaz", &["PC"]),
+        ];
+        let teams = vec![team("Det", Some(1), &["PC", "PC", "CDI"])
+            .numbered()
+            .with_composition(CompositionRules {
+                max_selres: Some(2),
+                max_aw: Some(1),
+                ..Default::default()
+            })];
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        solver.solve();
+        let assignments = solver.extract_assignments();
+        let placed: Vec<(String, String)> = assignments
+            .iter()
+            .map(|a| (a.person_name.clone(), a.position.role_id(&a.team)))
+            .sorted()
+            .collect();
+        assert_eq!(
+            placed,
+            vec![
+                ("Baker".to_string(), "Det-CDI-001".to_string()),
+                ("Clark".to_string(), "Det-PC-001".to_string()),
+                ("Diaz".to_string(), "Det-PC-002".to_string()),
+            ]
+        );
+        assert_eq!(
+            composition_violations(
+                &teams,
+                &people,
+                assignments
+                    .iter()
+                    .map(|a| (a.person_name.as_str(), a.team.as_str())),
+            ),
+            vec![]
+        );
+    }
+
+    /// A person placed by `best_composed_plan`: (person, team, base group, cost)
+    type Placement<'a> = (&'a str, &'a str, usize, i64);
+
+    /// (flow, cost) of the best plan that honors every composition rule, found by
+    /// giving each person one of their role groups (or none) in every possible way;
+    /// `None` when no plan meets every floor
+    fn best_composed_plan(
+        solver: &AssignmentSolver,
+        people: &[Person],
+        teams: &[Team],
+    ) -> Option<(i32, i64)> {
+        let graph = &solver.graph;
+        // each open person edge as (person node, placement), costed all the way to the sink
+        let routes: Vec<(usize, Placement)> = solver
+            .assignment_edges
+            .iter()
+            .filter(|&&edge_idx| graph.edges[edge_idx].capacity > 0)
+            .map(|&edge_idx| {
+                let person_node = graph.edges[edge_idx ^ 1].to;
+                let role_node = graph.edges[edge_idx].to;
+                let group_idx = solver.group_nodes.binary_search(&role_node).unwrap();
+                let (mut node, mut team_node) = (role_node, role_node);
+                let mut cost = graph.edges[edge_idx].cost;
+                while node != solver.sink_node {
+                    let next = graph.graph[node]
+                        .iter()
+                        .copied()
+                        .find(|&idx| idx % 2 == 0)
+                        .unwrap();
+                    cost += graph.edges[next].cost;
+                    team_node = node;
+                    node = graph.edges[next].to;
+                }
+                let placement = (
+                    solver.node_to_person[&person_node].as_str(),
+                    solver.node_to_team[&team_node].as_str(),
+                    solver.role_groups[group_idx].base,
+                    cost,
+                );
+                (person_node, placement)
+            })
+            .collect();
+        let person_nodes: Vec<usize> = routes.iter().map(|r| r.0).sorted().dedup().collect();
+
+        let is_feasible = |placed: &[Placement]| {
+            placed
+                .iter()
+                .counts_by(|p| p.2)
+                .into_iter()
+                .all(|(base, count)| count <= solver.role_groups[base].positions.len())
+                && composition_violations(teams, people, placed.iter().map(|p| (p.0, p.1)))
+                    .is_empty()
+        };
+        let mut best: Option<(i32, i64)> = None;
+        let mut placed: Vec<Placement> = vec![];
+        // odometer over the people: each takes none, then each of their routes
+        let mut choice: Vec<Option<usize>> = vec![None; person_nodes.len()];
+        loop {
+            placed.clear();
+            placed.extend(choice.iter().flatten().map(|&route| routes[route].1));
+            if is_feasible(&placed) {
+                let plan = (placed.len() as i32, placed.iter().map(|p| p.3).sum::<i64>());
+                if best.is_none_or(|b| (-plan.0, plan.1) < (-b.0, b.1)) {
+                    best = Some(plan);
+                }
+            }
+
+            let mut i = 0;
+            loop {
+                if i == person_nodes.len() {
+                    return best;
+                }
+                let options: Vec<usize> = (0..routes.len())
+                    .filter(|&r| routes[r].0 == person_nodes[i])
+                    .collect();
+                choice[i] = match choice[i] {
+                    None => options.first().copied(),
+                    Some(r) => options.iter().copied().skip_while(|&o| o != r).nth(1),
+                };
+                if choice[i].is_some() {
+                    break;
+                }
+                i += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_composition_rules_match_brute_force() {
+        fn rule(rng: &mut Rng, low: i64, high: i64) -> Option<u32> {
+            (rng.range(0, 1) == 1).then(|| rng.range(low, high) as u32)
+        }
+
+        let mut rng = Rng(0xC0A1_5EED_0000_0012);
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let quals = ["PC", "CDI"];
+        let rateranks = ["AM3", "AM1", "AMC", "AWS3", "AWS1", "AWFC"];
+        let mut divided = 0;
+        for round in 0..300 {
+            let people: Vec<Person> = (0..rng.range(2, 5))
+                .map(|i| {
+                    let held: Vec<&str> = quals
+                        .iter()
+                        .copied()
+                        .filter(|_| rng.range(0, 2) > 0)
+                        .collect();
+                    let mut p = person(&format!("Sailor {}", i), &held);
+                    p.raterank = rateranks[rng.range(0, 5) as usize].into();
+                    if rng.range(0, 1) == 1 {
+                        p.duty_status = DutyStatus::Selres;
+                    }
+                    if rng.range(0, 2) == 0 {
+                        p.prd = date.checked_add_days(chrono::Days::new(rng.range(30, 400) as u64));
+                    }
+                    p
+                })
+                .collect();
+            let teams: Vec<Team> = (0..rng.range(1, 2))
+                .map(|t| {
+                    let wanted: Vec<&str> = (0..rng.range(1, 3))
+                        .map(|_| quals[rng.range(0, 1) as usize])
+                        .collect();
+                    // the network holds a floor to the team's size, so keep them within it
+                    let size = wanted.len() as i64;
+                    team(&format!("Team {}", t), Some(t as u32), &wanted)
+                        .numbered()
+                        .with_composition(CompositionRules {
+                            max_selres: rule(&mut rng, 0, 2),
+                            min_tar: rule(&mut rng, 1, size),
+                            max_aw: rule(&mut rng, 0, 1),
+                            min_staff: rule(&mut rng, 1, size),
+                        })
+                })
+                .collect();
+
+            let mut solver =
+                AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+            let result = solver.solve();
+            let divided_here = solver.shared_capacities.iter().any(|shared| {
+                shared
+                    .edges
+                    .iter()
+                    .any(|&edge_idx| solver.graph.edges[edge_idx].capacity < shared.limit)
+            });
+            divided += divided_here as usize;
+            match best_composed_plan(&solver, &people, &teams) {
+                Some(best) => {
+                    assert_eq!(
+                        solver.dropped_floors(),
+                        &[],
+                        "instance {} dropped a floor",
+                        round
+                    );
+                    assert_eq!(
+                        result, best,
+                        "instance {} disagrees with brute force",
+                        round
+                    );
+                }
+                None => assert_ne!(
+                    solver.dropped_floors(),
+                    &[],
+                    "instance {} met floors no plan can meet",
+                    round
+                ),
+            }
+        }
+        // the shared limits had to be divided often enough to test that
+        assert!(divided > 20, "only {} instances divided a limit", divided);
+    }
+
+    #[test]
+    fn test_only_eligible_paygrades_and_rates_get_edges() {
+        let people = vec![
+            person("Adams", &["CDI"]),
+            person("Baker", &["CDI"]).with_raterank("AMCS"),
+        ];
+        let mut det = team("Det", Some(1), &["CDI"]);
+        det.required_positions[0].min_paygrade = Some(7);
+        let mut line_shack = team("Line Shack", Some(2), &["CDI"]);
+        line_shack.required_positions[0].allowed_rates = vec!["AD".to_string()];
+        let teams = vec![det, line_shack];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        let (flow, _cost) = solver.solve();
+        let assignments = solver.extract_assignments();
+
+        // Adams is an E-5 AM, so neither position admits them
+        assert_eq!(flow, 1);
+        assert_eq!(assignments[0].person_name, "Baker");
+        assert_eq!(assignments[0].team, "Det");
+    }
+
+    #[test]
+    fn test_mandatory_position_filled_before_higher_priority_optional() {
+        let people = vec![person("Smith", &["SFF"])];
+        let mut det = team("Det", Some(2), &["SFF"]);
+        det.required_positions[0].mandatory = true;
+        let teams = vec![team("Home Guard", Some(1), &["SFF"]), det];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        solver.solve();
+        let assignments = solver.extract_assignments();
+
+        assert_eq!(assignments[0].team, "Det");
+        assert!(assignments[0].position.mandatory);
+    }
+
+    #[test]
+    fn test_aggregated_network_matches_per_instance() {
+        let quals = ["SFF", "CDI", "QAR", "Chief"];
+        let people: Vec<Person> = (0..12)
+            .map(|i| {
+                let held: Vec<&str> = quals
+                    .iter()
+                    .enumerate()
+                    .filter(|(q, _)| (i + q) % 3 != 0)
+                    .map(|(_, q)| *q)
+                    .collect();
+                let mut p = person(&format!("Sailor {:02}", i), &held);
+                if i % 4 == 0 {
+                    p.duty_status = DutyStatus::Selres;
+                }
+                p
+            })
+            .collect();
+        let mut det = team("Det", Some(2), &["SFF", "SFF", "CDI", "CDI", "CDI", "QAR"]);
+        for (instance, position) in det.required_positions.iter_mut().enumerate() {
+            position.instance = instance as u32 + 1;
+            position.mandatory = instance < 2;
+        }
+        let teams = vec![
+            det,
+            team("Home Guard", Some(1), &["Chief", "QAR", "SFF"]),
+            team("Day Check", None, &["CDI", "QAR"]),
+        ];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let policy = CostPolicy::default();
+
+        let mut aggregated = AssignmentSolver::with_network_mode(
+            &people,
+            &teams,
+            date,
+            None,
+            &[],
+            &policy,
+            NetworkMode::Aggregated,
+        );
+        let mut per_instance = AssignmentSolver::with_network_mode(
+            &people,
+            &teams,
+            date,
+            None,
+            &[],
+            &policy,
+            NetworkMode::PerInstance,
+        );
+
+        assert!(aggregated.graph.edges.len() < per_instance.graph.edges.len());
+        assert_eq!(aggregated.solve(), per_instance.solve());
+
+        // every extracted assignment lands on its own instance
+        let assignments = aggregated.extract_assignments();
+        let roles: HashSet<RoleId> = assignments
+            .iter()
+            .map(|a| a.position.as_role_id(&a.team))
+            .collect();
+        assert_eq!(roles.len(), assignments.len());
+        assert_eq!(
+            aggregated.explain_unfilled().len() + assignments.len(),
+            teams
+                .iter()
+                .map(|t| t.required_positions.len())
+                .sum::<usize>()
+        );
+    }
+
+    /// (flow, cost) of every feasible plan, found by choosing at most one person -> role
+    /// edge per person and routing each choice straight through to the sink
+    fn enumerate_plans(solver: &mut AssignmentSolver) -> Vec<(i32, i64)> {
+        let graph = &solver.graph;
+        let forward_edge = |node: usize| -> usize {
+            graph.graph[node]
+                .iter()
+                .copied()
+                .find(|&idx| idx % 2 == 0)
+                .unwrap()
+        };
+        // source edge, person -> role edge, role -> team edge, team -> sink edge
+        let routes: Vec<(usize, [usize; 4])> = solver
+            .assignment_edges
+            .iter()
+            .map(|&edge_idx| {
+                let person_node = graph.edges[edge_idx ^ 1].to;
+                let source_edge = graph.graph[person_node]
+                    .iter()
+                    .find(|&&idx| idx % 2 == 1)
+                    .unwrap()
+                    ^ 1;
+                let role_edge = forward_edge(graph.edges[edge_idx].to);
+                let team_edge = forward_edge(graph.edges[role_edge].to);
+                (person_node, [source_edge, edge_idx, role_edge, team_edge])
+            })
+            .collect();
+        let people: Vec<usize> = routes.iter().map(|(p, _)| *p).sorted().dedup().collect();
+
+        let mut plans = vec![];
+        let mut choice = vec![None; people.len()];
+        loop {
+            for edge in solver.graph.edges.iter_mut() {
+                edge.flow = 0;
+            }
+            for route in choice.iter().flatten() {
+                let (_, path): &(usize, [usize; 4]) = &routes[*route];
+                solver.graph.push_flow(path, 1);
+            }
+            let feasible = solver
+                .graph
+                .edges
+                .iter()
+                .step_by(2)
+                .all(|e| e.flow <= e.capacity);
+            if feasible {
+                let flow = choice.iter().flatten().count() as i32;
+                plans.push((flow, solver.graph.total_cost()));
+            }
+
+            // advance the odometer: each person takes none, then each of their edges
+            let mut i = 0;
+            loop {
+                if i == people.len() {
+                    plans.sort_by_key(|&(flow, cost)| (-flow, cost));
+                    return plans;
+                }
+                let options: Vec<usize> = (0..routes.len())
+                    .filter(|&r| routes[r].0 == people[i])
+                    .collect();
+                let next = match choice[i] {
+                    None => options.first().copied(),
+                    Some(r) => options.iter().copied().skip_while(|&o| o != r).nth(1),
+                };
+                choice[i] = next;
+                if next.is_some() {
+                    break;
+                }
+                i += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_top_k_plans_match_enumeration() {
+        let people = vec![
+            person("Smith", &["SFF", "QAR"]),
+            person("Jones", &["SFF", "CDI"]).with_status(DutyStatus::Selres),
+            person("Lee", &["Chief", "SFF"]).with_raterank("AMC"),
+            person("Brown", &["CDI", "QAR"]),
+        ];
+        let mut det = team("Det", Some(2), &["SFF", "CDI"]);
+        det.required_positions[0].mandatory = true;
+        let teams = vec![team("Home Guard", Some(1), &["Chief", "SFF", "QAR"]), det];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        let plans = solver.top_k_plans(6);
+        let expected = enumerate_plans(&mut solver);
+
+        let ranked: Vec<(i32, i64)> = plans
+            .iter()
+            .map(|p| (p.assignments.len() as i32, p.cost))
+            .collect();
+        assert_eq!(ranked, expected[..6]);
+
+        // no two plans put the same people in the same roles
+        let signatures: HashSet<Vec<(String, String, String)>> = plans
+            .iter()
+            .map(|plan| {
+                plan.assignments
+                    .iter()
+                    .map(|a| {
+                        (
+                            a.person_name.clone(),
+                            a.team.clone(),
+                            a.position.qualification.clone(),
+                        )
+                    })
+                    .sorted()
+                    .collect()
+            })
+            .collect();
+        assert_eq!(signatures.len(), plans.len());
+    }
+
+    #[test]
+    fn test_reoptimize_after_lock_and_date_changes_matches_full_solve() {
+        let mut people: Vec<Person> = (0..8)
+            .map(|i| {
+                let quals: &[&str] = match i % 3 {
+                    0 => &["SFF", "CDI"],
+                    1 => &["CDI", "QAR"],
+                    _ => &["SFF", "QAR", "Chief"],
+                };
+                person(&format!("Sailor {}", i), quals)
+            })
+            .collect();
+        people[1].prd = NaiveDate::from_ymd_opt(2025, 3, 1);
+        people[4].prd = NaiveDate::from_ymd_opt(2025, 9, 1);
+        people[5].duty_status = DutyStatus::Selres;
+        let teams = vec![
+            team("Home Guard", Some(1), &["SFF", "CDI", "Chief"]),
+            team("Det", Some(2), &["SFF", "CDI", "QAR", "QAR"]),
+        ];
+        let policy = CostPolicy::default();
+        let lock = AssignmentLock {
+            person_name: "Sailor 2".to_string(),
+            team_name: Some("Det".to_string()),
+            position: Some(teams[1].required_positions[0].clone()),
+        };
+
+        let mut warm = AssignmentSolver::new(
+            &people,
+            &teams,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            None,
+            &[],
+            &policy,
+        );
+        warm.solve();
+
+        let exclusions = vec![
+            AssignmentExclusion {
+                person_name: "Sailor 1".to_string(),
+                team_name: Some("Home Guard".to_string()),
+                qualification: None,
+            },
+            AssignmentExclusion {
+                person_name: "Sailor 3".to_string(),
+                team_name: None,
+                qualification: Some("QAR".to_string()),
+            },
+        ];
+        let steps = [
+            (
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                vec![lock.clone()],
+                vec![],
+            ),
+            (
+                NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+                vec![lock.clone()],
+                exclusions.clone(),
+            ),
+            (
+                NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+                vec![],
+                exclusions,
+            ),
+            (
+                NaiveDate::from_ymd_opt(2025, 10, 1).unwrap(),
+                vec![],
+                vec![],
+            ),
+        ];
+        for (date, locks, exclusions) in steps {
+            warm.apply_constraints(&people, date, &locks, &exclusions);
+            let available: Vec<Person> = people
+                .iter()
+                .filter(|p| p.prd.is_none_or(|prd| prd > date))
+                .cloned()
+                .collect();
+            let mut full = AssignmentSolver::new(
+                &available,
+                &teams,
+                date,
+                Some(locks.clone()),
+                &exclusions,
+                &policy,
+            );
+
+            assert_eq!(warm.reoptimize(), full.solve(), "diverged on {}", date);
+        }
+    }
+
+    #[test]
+    fn test_large_roster_solves_and_reoptimizes_quickly() {
+        // a thousand people over a handful of ranks and quals tie almost everywhere,
+        // which is where settling ties one person at a time used to take seconds
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let quals = [
+            "PC", "CDI", "QAR", "CDQAR", "SFF", "F/S QAR", "Chief", "LSO",
+        ];
+        let mut rng = Rng(0x1A46_E805_7E55_0008);
+        let people: Vec<Person> = (0..1000)
+            .map(|i| {
+                let held: Vec<&str> = quals
+                    .iter()
+                    .copied()
+                    .filter(|_| rng.range(0, 2) == 0)
+                    .collect();
+                let mut sailor = person(&format!("Sailor {}", i), &held);
+                sailor.raterank =
+                    ["AM2", "AM1", "AMC", "AD2", "AE1"][rng.range(0, 4) as usize].into();
+                sailor
+            })
+            .collect();
+        let teams: Vec<Team> = (0..80)
+            .map(|i| {
+                let needed: Vec<&str> = (0..10).map(|_| quals[rng.range(0, 7) as usize]).collect();
+                let mut team = team(&format!("Det {}", i), Some(1 + i % 5), &needed);
+                let mut instances: HashMap<String, u32> = HashMap::new();
+                for position in &mut team.required_positions {
+                    let instance = instances.entry(position.qualification.clone()).or_default();
+                    *instance += 1;
+                    position.instance = *instance;
+                }
+                team
+            })
+            .collect();
+        let policy = CostPolicy::default();
+        let plan = |solver: &AssignmentSolver| -> Vec<(String, String, String, u32)> {
+            solver
+                .extract_assignments()
+                .into_iter()
+                .map(|a| {
+                    (
+                        a.person_name,
+                        a.team,
+                        a.position.qualification,
+                        a.position.instance,
+                    )
+                })
+                .sorted()
+                .collect()
+        };
+
+        let started = std::time::Instant::now();
+        let mut warm = AssignmentSolver::new(&people, &teams, date, None, &[], &policy);
+        warm.solve();
+        let placed = plan(&warm);
+        let (sailor, det, qualification, instance) = &placed[0];
+        let locks = vec![
+            AssignmentLock {
+                person_name: sailor.clone(),
+                team_name: Some(det.clone()),
+                position: Some(Position {
+                    qualification: qualification.clone(),
+                    instance: *instance,
+                    ..Default::default()
+                }),
+            },
+            AssignmentLock {
+                person_name: placed[1].0.clone(),
+                team_name: None,
+                position: None,
+            },
+        ];
+        warm.apply_constraints(&people, date, &locks, &[]);
+        warm.reoptimize();
+        let elapsed = started.elapsed();
+
+        let mut full = AssignmentSolver::new(&people, &teams, date, Some(locks), &[], &policy);
+        full.solve();
+        assert_eq!(plan(&warm), plan(&full));
+        assert!(
+            elapsed < std::time::Duration::from_secs(10),
+            "solve and reoptimize took {:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn test_backends_agree_on_cost() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut teams = vec![
+            team("Home Guard", Some(1), &["SFF", "CDI", "Chief"]),
+            team("Det", Some(2), &["SFF", "CDI", "QAR", "QAR"]),
+            team("Day Check", None, &["QAR", "SFF"]),
+        ];
+        teams[1].required_positions[1].mandatory = true;
+        let exclusions = vec![AssignmentExclusion {
+            person_name: "Sailor 0".to_string(),
+            team_name: Some("Home Guard".to_string()),
+            qualification: None,
+        }];
+
+        // short-handed, exactly manned and overmanned rosters
+        for size in [4, 9, 14] {
+            let mut people: Vec<Person> = (0..size)
+                .map(|i| {
+                    let quals: &[&str] = match i % 4 {
+                        0 => &["SFF", "CDI"],
+                        1 => &["CDI", "QAR"],
+                        2 => &["SFF", "QAR", "Chief"],
+                        _ => &["QAR"],
+                    };
+                    person(&format!("Sailor {}", i), quals)
+                })
+                .collect();
+            people[1].prd = NaiveDate::from_ymd_opt(2025, 3, 1);
+            people[2].duty_status = DutyStatus::Selres;
+            let lock = AssignmentLock {
+                person_name: "Sailor 3".to_string(),
+                team_name: Some("Det".to_string()),
+                position: Some(teams[1].required_positions[2].clone()),
+            };
+
+            let results: Vec<(i32, i64)> = BackendKind::ALL
+                .iter()
+                .map(|&backend| {
+                    let mut solver = AssignmentSolver::new(
+                        &people,
+                        &teams,
+                        date,
+                        Some(vec![lock.clone()]),
+                        &exclusions,
+                        &CostPolicy::default(),
+                    );
+                    solver.set_backend(backend);
+                    let result = solver.solve();
+                    assert_eq!(solver.compare_backends(), vec![], "{} people", size);
+                    result
+                })
+                .collect();
+            assert!(results.iter().all_equal(), "{} people: {:?}", size, results);
+
+            // and again after the constraints change under a warm solver
+            let later = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+            let [hungarian, min_cost_flow] = BackendKind::ALL.map(|backend| {
+                let policy = CostPolicy::default();
+                let mut solver = AssignmentSolver::new(&people, &teams, date, None, &[], &policy);
+                solver.set_backend(backend);
+                solver.solve();
+                solver.apply_constraints(&people, later, std::slice::from_ref(&lock), &exclusions);
+                solver.reoptimize()
+            });
+            assert_eq!(hungarian, min_cost_flow, "{} people", size);
+        }
+    }
+
+    #[test]
+    fn test_shadow_prices_match_solving_with_one_more_holder() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let people = vec![person("Adams", &["CDI"]), person("Baker", &["SFF"])];
+        let teams = vec![
+            team("Alpha", Some(1), &["CDI", "SFF"]),
+            team("Bravo", Some(2), &["CDI"]),
+        ];
+        let policy = CostPolicy::default();
+        let mut solver = AssignmentSolver::new(&people, &teams, date, None, &[], &policy);
+        let (flow, cost) = solver.solve();
+
+        let prices = solver.shadow_prices(date);
+        let qualifications: Vec<&str> = prices.iter().map(|p| p.qualification.as_str()).collect();
+        assert_eq!(qualifications, vec!["CDI", "SFF"]);
+        assert_eq!(prices[0].positions_gained, 1);
+        assert_eq!(prices[1].positions_gained, 0);
+        assert_eq!(prices[1].cost_change, 0);
+
+        let mut more_people = people.clone();
+        more_people.push(person("Notional", &["CDI"]));
+        let mut with_holder = AssignmentSolver::new(&more_people, &teams, date, None, &[], &policy);
+        assert_eq!(
+            with_holder.solve(),
+            (flow + 1, cost + prices[0].cost_change)
+        );
+
+        // the probes leave the solved flow in place
+        assert_eq!(solver.reoptimize(), (flow, cost));
+    }
+
+    #[test]
+    fn test_shadow_prices_hold_composition_rules() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        // the AW cap is shared between the duty statuses, and Det's SELRES cap leaves
+        // room for one more TAR PC only
+        let people = vec![
+            person("Adams", &["PC"]).with_raterank("AWS2"),
+            person("Baker", &["CDI"])
+                .with_raterank("AWS2")
+                .with_status(DutyStatus::Selres),
+            person("Clark", &["PC"]).with_status(DutyStatus::Selres),
+            person("Diaz", &["PC"]).with_status(DutyStatus::Selres),
+        ];
+        let teams = vec![team("Det", Some(1), &["PC", "PC", "PC", "CDI"])
+            .numbered()
+            .with_composition(CompositionRules {
+                max_selres: Some(2),
+                max_aw: Some(1),
+                min_tar: Some(1),
+                ..Default::default()
+            })];
+        let policy = CostPolicy::default();
+        let mut solver = AssignmentSolver::new(&people, &teams, date, None, &[], &policy);
+        let (flow, cost) = solver.solve();
+
+        let prices = solver.shadow_prices(date);
+        for price in &prices {
+            let mut more_people = people.clone();
+            more_people.push(person("Notional", &[price.qualification.as_str()]));
+            let mut with_holder =
+                AssignmentSolver::new(&more_people, &teams, date, None, &[], &policy);
+            assert_eq!(
+                with_holder.solve(),
+                (flow + price.positions_gained, cost + price.cost_change),
+                "{}",
+                price.qualification
+            );
+        }
+        assert_eq!(solver.reoptimize(), (flow, cost));
+    }
+
+    #[test]
+    fn test_explain_unfilled_from_residual_graph() {
+        let people = vec![person("Smith", &["SFF"])];
+        let teams = vec![
+            team("Home Guard", Some(1), &["SFF", "CDI"]),
+            team("Det", Some(2), &["SFF"]),
+        ];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        solver.solve();
+        let reasons = solver.explain_unfilled();
+
+        assert_eq!(reasons.len(), 2);
+        assert_eq!(
+            reasons[&teams[0].required_positions[1].as_role_id("Home Guard")],
+            UnfilledReason::NoQualifiedPersonnel
+        );
+        assert_eq!(
+            reasons[&teams[1].required_positions[0].as_role_id("Det")],
+            UnfilledReason::UsedElsewhere {
+                competitors: vec![Competitor {
+                    person_name: "Smith".to_string(),
+                    role_id: "Home Guard-SFF-001".to_string(),
+                    locked: false,
+                }]
+            }
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::flow_graph::FlowGraph;
+use crate::engine::hungarian::{min_cost_assignment, FORBIDDEN};
+
+/// Solves the min-cost max-flow problem an `AssignmentSolver` network poses. Every
+/// backend must leave a maximum flow of minimum cost on the graph; plans of equal cost
+/// may differ between backends.
+pub trait AssignmentBackend {
+    /// Solve from scratch; returns the flow value and its cost
+    fn solve(&self, graph: &mut FlowGraph, source: usize, sink: usize) -> (i32, i64);
+
+    /// Restore an optimal flow after capacities or costs changed
+    fn reoptimize(&self, graph: &mut FlowGraph, source: usize, sink: usize) -> (i32, i64) {
+        self.solve(graph, source, sink)
+    }
+}
+
+/// Primal-dual successive shortest paths on the `FlowGraph`, with warm restarts
+pub struct MinCostFlowBackend;
+
+impl AssignmentBackend for MinCostFlowBackend {
+    fn solve(&self, graph: &mut FlowGraph, source: usize, sink: usize) -> (i32, i64) {
+        graph.min_cost_max_flow(source, sink)
+    }
+
+    fn reoptimize(&self, graph: &mut FlowGraph, source: usize, sink: usize) -> (i32, i64) {
+        graph.reoptimize(source, sink)
+    }
+}
+
+/// Kuhn-Munkres over people x position slots. Reads the network as source -> person
+/// (capacity 1) -> role, where each role has a single route on to the sink. Falls
+/// back to min-cost flow for any network it cannot read that way.
+pub struct HungarianBackend;
+
+impl AssignmentBackend for HungarianBackend {
+    fn solve(&self, graph: &mut FlowGraph, source: usize, sink: usize) -> (i32, i64) {
+        let Some(problem) = SlotProblem::from_graph(graph, source, sink) else {
+            return graph.min_cost_max_flow(source, sink);
+        };
+
+        for edge in &mut graph.edges {
+            edge.flow = 0;
+        }
+        for (row, column) in min_cost_assignment(&problem.costs).into_iter().enumerate() {
+            let Some(&role) = problem.slot_roles.get(column) else {
+                continue; // left on the bench
+            };
+            let mut path = vec![problem.source_edges[row], problem.person_edges[row][&role]];
+            path.extend_from_slice(&problem.routes[&role]);
+            graph.push_flow(&path, 1);
+        }
+        graph.settle_potentials();
+
+        (graph.flow_value(source), graph.total_cost())
+    }
+}
+
+/// The network as a rectangular cost matrix: one row per person, one column per open
+/// position slot, then one bench column per person
+struct SlotProblem {
+    costs: Vec<Vec<i64>>,
+    /// Role node behind each slot column
+    slot_roles: Vec<usize>,
+    /// Source -> person edge of each row
+    source_edges: Vec<usize>,
+    /// Person -> role edge of each row, by role node
+    person_edges: Vec<BTreeMap<usize, usize>>,
+    /// Edges from each role node on to the sink
+    routes: BTreeMap<usize, Vec<usize>>,
+}
+
+impl SlotProblem {
+    fn from_graph(graph: &FlowGraph, source: usize, sink: usize) -> Option<Self> {
+        let open_edges = |node: usize| -> Vec<usize> {
+            graph.graph[node]
+                .iter()
+                .copied()
+                .filter(|&edge_idx| edge_idx % 2 == 0 && graph.edges[edge_idx].capacity > 0)
+                .collect()
+        };
+
+        let source_edges = open_edges(source);
+        if source_edges
+            .iter()
+            .any(|&edge_idx| graph.edges[edge_idx].capacity != 1)
+        {
+            return None;
+        }
+
+        let mut person_edges = vec![];
+        let mut routes: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        let mut role_order = vec![];
+        for &source_edge in &source_edges {
+            let mut edges = BTreeMap::new();
+            for edge_idx in open_edges(graph.edges[source_edge].to) {
+                let role = graph.edges[edge_idx].to;
+                if let Entry::Vacant(entry) = routes.entry(role) {
+                    entry.insert(route_to_sink(graph, role, sink, &open_edges)?);
+                    role_order.push(role);
+                }
+                edges.insert(role, edge_idx);
+            }
+            person_edges.push(edges);
+        }
+
+        // a slot per unit of the role's first edge; later edges must never bind
+        let mut slot_roles = vec![];
+        let mut load: BTreeMap<usize, i32> = BTreeMap::new();
+        for &role in &role_order {
+            let route = &routes[&role];
+            let slots = route
+                .first()
+                .map_or(0, |&edge_idx| graph.edges[edge_idx].capacity);
+            for &edge_idx in route {
+                *load.entry(edge_idx).or_default() += slots;
+            }
+            slot_roles.extend(std::iter::repeat_n(role, slots as usize));
+        }
+        if load
+            .iter()
+            .any(|(&edge_idx, &slots)| slots > graph.edges[edge_idx].capacity)
+        {
+            return None;
+        }
+
+        let route_cost = |role: usize| -> i64 {
+            routes[&role]
+                .iter()
+                .map(|&edge_idx| graph.edges[edge_idx].cost)
+                .sum()
+        };
+        let pair_costs: Vec<BTreeMap<usize, i64>> = person_edges
+            .iter()
+            .map(|edges| {
+                edges
+                    .iter()
+                    .map(|(&role, &edge_idx)| (role, graph.edges[edge_idx].cost + route_cost(role)))
+                    .collect()
+            })
+            .collect();
+
+        // every extra person placed outweighs any spread in cost, so the matrix ranks
+        // plans by flow first like min-cost max-flow does
+        let rows = source_edges.len();
+        let largest = pair_costs
+            .iter()
+            .flat_map(|costs| costs.values())
+            .map(|cost| cost.abs())
+            .max()
+            .unwrap_or(0);
+        let placement_bonus = (largest + 1) * (2 * rows as i64 + 1);
+
+        let costs = pair_costs
+            .iter()
+            .map(|costs| {
+                slot_roles
+                    .iter()
+                    .map(|role| {
+                        costs
+                            .get(role)
+                            .map_or(FORBIDDEN, |cost| cost - placement_bonus)
+                    })
+                    .chain(std::iter::repeat_n(0, rows))
+                    .collect()
+            })
+            .collect();
+
+        Some(SlotProblem {
+            costs,
+            slot_roles,
+            source_edges,
+            person_edges,
+            routes,
+        })
+    }
+}
+
+/// The only open path from `node` to `sink`; empty when the path is closed, e.g.
+/// every instance of the role is locked, and `None` where it branches
+fn route_to_sink(
+    graph: &FlowGraph,
+    mut node: usize,
+    sink: usize,
+    open_edges: &impl Fn(usize) -> Vec<usize>,
+) -> Option<Vec<usize>> {
+    let mut route = vec![];
+    while node != sink {
+        match open_edges(node)[..] {
+            [] => return Some(vec![]),
+            [edge_idx] if route.len() < graph.graph.len() => {
+                route.push(edge_idx);
+                node = graph.edges[edge_idx].to;
+            }
+            // branches, or loops back on itself
+            _ => return None,
+        }
+    }
+    Some(route)
+}
+
+/// Which `AssignmentBackend` the solver uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendKind {
+    #[default]
+    MinCostFlow,
+    Hungarian,
+}
+
+impl BackendKind {
+    pub const ALL: [BackendKind; 2] = [BackendKind::MinCostFlow, BackendKind::Hungarian];
+
+    pub fn backend(self) -> &'static dyn AssignmentBackend {
+        match self {
+            BackendKind::MinCostFlow => &MinCostFlowBackend,
+            BackendKind::Hungarian => &HungarianBackend,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BackendKind::MinCostFlow => "Min-cost flow",
+            BackendKind::Hungarian => "Hungarian",
+        }
+    }
+}
+
+/// A backend that disagreed with the chosen one on the same network
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackendMismatch {
+    pub backend: BackendKind,
+    pub flow: i32,
+    pub cost: i64,
+    pub chosen: BackendKind,
+    pub chosen_flow: i32,
+    pub chosen_cost: i64,
+}
+
+impl Display for BackendMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} filled {} at cost {}, {} filled {} at cost {}",
+            self.backend.label(),
+            self.flow,
+            self.cost,
+            self.chosen.label(),
+            self.chosen_flow,
+            self.chosen_cost
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hungarian_falls_back_where_a_role_branches() {
+        // source -> two people -> one role, which can reach the sink through either
+        // of two teams; the cheap team only has room for one
+        let (source, role, cheap, dear, sink) = (0, 3, 4, 5, 6);
+        let mut graph = FlowGraph::new(7);
+        for person in [1, 2] {
+            graph.add_edge(source, person, 1, 0);
+            graph.add_edge(person, role, 1, 10 * person as i64);
+        }
+        graph.add_edge(role, cheap, 2, 0);
+        graph.add_edge(role, dear, 2, 0);
+        graph.add_edge(cheap, sink, 1, 1);
+        graph.add_edge(dear, sink, 2, 100);
+        assert!(SlotProblem::from_graph(&graph, source, sink).is_none());
+
+        let mut expected = graph.clone();
+        assert_eq!(
+            HungarianBackend.solve(&mut graph, source, sink),
+            expected.min_cost_max_flow(source, sink)
+        );
+        assert_eq!(graph.flow_value(source), 2);
+        assert_eq!(graph.total_cost(), 10 + 20 + 1 + 100);
+    }
+}
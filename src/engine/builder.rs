@@ -1,50 +1,76 @@
 use crate::engine::assignment::{
-    Assignment, AssignmentLock, AssignmentPlan, AssignmentSolver, FlowAssignment,
+    Assignment, AssignmentExclusion, AssignmentLock, AssignmentPlan, AssignmentSolver, Competitor,
+    DroppedFloor, FlowAssignment, RoleId, ShadowPrice, SolveStats, UnfilledPosition,
+    UnfilledReason,
 };
-use crate::engine::person::Person;
-use crate::engine::team::Team;
-use crate::utilities::config::{AppState, ParsedData};
+use crate::engine::backend::{BackendKind, BackendMismatch};
+use crate::engine::cost_policy::{CostBreakdown, CostPolicy};
+use crate::engine::pairing::{
+    enforce_pairings, placements, unsatisfied_pairings, PairingConstraint,
+};
+use crate::engine::person::{apply_pending_qualifications, PendingQualification, Person};
+use crate::engine::plan_diff::PlanBaseline;
+use crate::engine::rate_rank::RateRank;
+use crate::engine::rules::composition_violations;
+use crate::engine::team::{Position, Team};
+use crate::utilities::config::{AppState, ParsedData, PAGES};
+use crate::utilities::parsing::QualTable;
 
-use once_cell::sync::Lazy;
-use regex::Regex;
 use std::collections::HashMap;
 
 use anyhow::{anyhow, bail, Context, Result};
-use std::collections::HashSet;
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::rc::Rc;
 
 pub struct AssignmentResult {
+    pub analysis_date: NaiveDate,
     pub people: Rc<Vec<Person>>,
     pub teams: Rc<Vec<Team>>,
     pub flow_assignments: Vec<FlowAssignment>,
+    /// People filtered out because their PRD is on or before the analysis date
+    pub departed_people: Rc<Vec<Person>>,
+    /// Solver explanations for role nodes left without flow
+    pub unfilled_reasons: HashMap<RoleId, UnfilledReason>,
+    /// Exclusions the user set, which the solver honored
+    pub exclusions: Vec<AssignmentExclusion>,
+    /// Exclusions the solver added on top to keep pairing rules; never shown as a reason
+    /// a position is empty
+    pub pairing_exclusions: Vec<AssignmentExclusion>,
+    /// Pairing rules the plan was solved under
+    pub pairings: Vec<PairingConstraint>,
+    /// Earlier plan the solver favored staying close to
+    pub baseline: PlanBaseline,
+    /// Algorithm the plan was solved with
+    pub backend: BackendKind,
+    /// Backends that found a different flow or cost, when comparing them was asked for
+    pub backend_mismatches: Vec<BackendMismatch>,
+    /// Composition floors no plan could meet, which the plan was solved without
+    pub dropped_floors: Vec<DroppedFloor>,
+    /// Weights the plan was priced with
+    pub cost_policy: CostPolicy,
+    /// Network size and solver work behind this result; ranked alternatives share the
+    /// stats of the one run that found them all
+    pub solve_stats: SolveStats,
 }
 
-static SUPPLY_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^[a-zA-Z,\s]*\sLS+[a-zA-Z0-9]$").expect("Invalid SUPPLY_REGEX pattern")
-});
-
-static CHIEF_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^[a-zA-z\s,]*[cC][S|M|MD]*$").expect("Invalid CHIEF_REGEX pattern"));
-
-static MMCPO_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^[a-zA-z\s,]*[cC][M|MD]+$").expect("Invalid MMCPO_REGEX pattern"));
-
-static AZ_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^[a-zA-Z,\s]*\sAZ+[a-zA-Z0-9]$").expect("Invalid AZ_REGEX pattern"));
-
-fn get_qual_table(data: &HashMap<String, Vec<String>>) -> Result<HashMap<String, String>> {
+/// ASM name -> local name; an ASM name listed under several local names maps to the
+/// first of them alphabetically
+fn get_qual_table(data: &QualTable) -> Result<HashMap<String, String>> {
     let mut qual_table: HashMap<String, String> = HashMap::new();
 
     for (common_name, asm_names) in data {
         for asm_name in asm_names {
-            qual_table.insert(asm_name.to_string(), common_name.to_string());
+            qual_table
+                .entry(asm_name.to_string())
+                .or_insert_with(|| common_name.to_string());
         }
     }
 
     Ok(qual_table)
 }
 
-pub fn build_people(analysis_date: chrono::NaiveDate, app_state: &AppState) -> Result<Rc<Vec<Person>>> {
+pub fn build_roster(app_state: &AppState) -> Result<Vec<Person>> {
     let files = &app_state.files;
 
     let parsed_quals = files
@@ -69,52 +95,156 @@ pub fn build_people(analysis_date: chrono::NaiveDate, app_state: &AppState) -> R
         .context("ASM data not parsed")?;
 
     let mut people = match parsed_asm {
-        ParsedData::Personnel(people) => people.clone(),
+        ParsedData::Personnel(people) => people.as_ref().clone(),
         _ => bail!("Error extracting ASM data"),
     };
 
-    let people_mut = Rc::make_mut(&mut people);
-
-    for person in people_mut.iter_mut() {
-        person.qualifications = person
-            .qualifications
-            .iter()
-            .filter_map(|q| qual_table.get(q))
-            .cloned()
+    for person in people.iter_mut() {
+        // a local qualification lasts as long as the longest-lived ASM one behind it
+        let mut expirations: BTreeMap<String, Option<NaiveDate>> = BTreeMap::new();
+        for asm_name in &person.qualifications {
+            let Some(local_name) = qual_table.get(asm_name) else {
+                continue;
+            };
+            let expiration = person.expiration(asm_name);
+            expirations
+                .entry(local_name.clone())
+                .and_modify(|current| {
+                    *current = current.zip(expiration).map(|(a, b)| a.max(b));
+                })
+                .or_insert(expiration);
+        }
+        person.qualifications = expirations.keys().cloned().collect();
+        person.qualification_expirations = expirations
+            .into_iter()
+            .filter_map(|(qualification, expiration)| Some((qualification, expiration?)))
             .collect();
-
-        let temp_name = format!("{}  {}", &person.name, &person.raterank);
-        let derivative_quals = get_derivative_quals(&temp_name, &person.qualifications);
-        person.qualifications.extend(derivative_quals);
     }
-
-    people_mut.retain(|person| match person.prd {
-        Some(prd_date) => prd_date > analysis_date,
-        None => true,
-    });
+    add_pending_qualifications(&mut people, &app_state.pending_qualifications);
 
     Ok(people)
 }
 
-fn is_supply(name: &str) -> bool {
-    SUPPLY_REGEX.is_match(name.trim())
+/// Add each pending qualification to the person it names, then the composite
+/// qualifications held and pending ones complete between them
+pub fn add_pending_qualifications(people: &mut [Person], pending: &[PendingQualification]) {
+    apply_pending_qualifications(people, pending);
+    for person in people.iter_mut() {
+        for derived in get_derivative_quals(&person.raterank, &person.qualifications) {
+            match derivative_expiration(&derived, person) {
+                Some(expiration) => person
+                    .qualification_expirations
+                    .insert(derived.clone(), expiration),
+                None => person.qualification_expirations.remove(&derived),
+            };
+            match derivative_completion(&derived, person) {
+                Some(completion) => person
+                    .qualification_completions
+                    .insert(derived.clone(), completion),
+                None => person.qualification_completions.remove(&derived),
+            };
+            person.qualifications.insert(derived);
+        }
+    }
 }
 
-fn is_chief(name: &str) -> bool {
-    CHIEF_REGEX.is_match(name.trim())
+/// What each composite qualification is derived from: every group is required, and
+/// any one qualification within a group satisfies it
+const DERIVATIVE_SOURCES: [(&str, &[&[&str]]); 4] = [
+    (
+        "F/S QAR",
+        &[
+            &["220 QAR"],
+            &["210 QAR"],
+            &["120 QAR"],
+            &["110 QAR"],
+            &["13A QAR", "13B QAR", "130 Crossrate"],
+        ],
+    ),
+    ("200 CDI", &[&["210 CDI"], &["220 CDI"]]),
+    ("100 CDI", &[&["110 CDI"], &["120 CDI"]]),
+    ("130 CDI", &[&["13A CDI", "13B CDI"]]),
+];
+
+/// Whether two qualifications belong to one family: the same aircraft or system, as
+/// the first word of 110 CDI and 110 QAR, or parts of one composite qualification
+pub fn same_qualification_family(a: &str, b: &str) -> bool {
+    let system = |qualification: &str| {
+        qualification
+            .split_whitespace()
+            .next()
+            .map(str::to_uppercase)
+    };
+    let composite_of = |qualification: &str| {
+        DERIVATIVE_SOURCES.iter().position(|(name, groups)| {
+            name.eq_ignore_ascii_case(qualification)
+                || groups
+                    .iter()
+                    .flat_map(|alternatives| alternatives.iter())
+                    .any(|source| source.eq_ignore_ascii_case(qualification))
+        })
+    };
+    system(a).is_some_and(|system_a| system(b) == Some(system_a))
+        || composite_of(a).is_some_and(|composite| composite_of(b) == Some(composite))
 }
 
-fn is_mmcpo(name: &str) -> bool {
-    // matches any senior or master chief
-    MMCPO_REGEX.is_match(name.trim())
+/// Sources `person` holds for each required group of `derived`; empty for rate and
+/// rank based composites
+fn derivative_groups<'a>(derived: &str, person: &'a Person) -> Vec<Vec<&'a String>> {
+    let groups: &[&[&str]] = DERIVATIVE_SOURCES
+        .iter()
+        .find(|(name, _)| *name == derived)
+        .map_or(&[], |(_, groups)| groups);
+    groups
+        .iter()
+        .map(|alternatives| {
+            person
+                .qualifications
+                .iter()
+                .filter(|qualification| {
+                    alternatives
+                        .iter()
+                        .any(|source| source.eq_ignore_ascii_case(qualification))
+                })
+                .collect()
+        })
+        .collect()
 }
 
-fn is_az(name: &str) -> bool {
-    // C-40 ASM report doesn't have a qual that matches logs and records. New plan is to just include all AZs.
-    AZ_REGEX.is_match(name.trim()) //&& quals.contains(&"L&R".to_string())
+/// A composite qualification lapses once any required group has no source left: the
+/// first group to run out, each lasting as long as its longest-lived source. Sources
+/// without an expiration never lapse, nor do rate and rank based composites
+fn derivative_expiration(derived: &str, person: &Person) -> Option<NaiveDate> {
+    derivative_groups(derived, person)
+        .into_iter()
+        .filter_map(|held| {
+            // a group with a source that never expires never runs out
+            held.into_iter()
+                .map(|source| person.expiration(source))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .max()
+        })
+        .min()
+}
+
+/// A composite qualification counts once every required group has a source that
+/// does: the last group to get one, each ready with its first source to complete.
+/// Sources already held outright make their group ready now
+fn derivative_completion(derived: &str, person: &Person) -> Option<NaiveDate> {
+    derivative_groups(derived, person)
+        .into_iter()
+        .filter_map(|held| {
+            held.into_iter()
+                .map(|source| person.completion(source))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .min()
+        })
+        .max()
 }
 
-fn is_fs_qar(_name: &str, quals: &[String]) -> bool {
+fn is_fs_qar(quals: &[String]) -> bool {
     let onethirty = ["13A QAR", "13B QAR", "130 Crossrate"];
     let allothers = ["220 QAR", "210 QAR", "120 QAR", "110 QAR"];
 
@@ -128,60 +258,61 @@ fn is_fs_qar(_name: &str, quals: &[String]) -> bool {
     has_all_others && has_one_thiry
 }
 
-fn is_twohundred_cdi(_name: &str, quals: &[String]) -> bool {
+fn is_twohundred_cdi(quals: &[String]) -> bool {
     let all = ["210 CDI", "220 CDI"];
 
     all.iter().all(|item| quals.contains(&(*item).to_string()))
 }
 
-fn is_onehundred_cdi(_name: &str, quals: &[String]) -> bool {
+fn is_onehundred_cdi(quals: &[String]) -> bool {
     let all = ["110 CDI", "120 CDI"];
 
     all.iter().all(|item| quals.contains(&(*item).to_string()))
 }
 
-fn is_onethirty_cdi(_name: &str, quals: &[String]) -> bool {
+fn is_onethirty_cdi(quals: &[String]) -> bool {
     let all = ["13A CDI", "13B CDI"];
 
     all.iter().any(|item| quals.contains(&(*item).to_string()))
 }
 
-fn get_derivative_quals(name: &str, quals: &HashSet<String>) -> Vec<String> {
+fn get_derivative_quals(raterank: &RateRank, quals: &BTreeSet<String>) -> Vec<String> {
     let mut extra_quals = vec![];
     let quals: Vec<_> = quals.iter().map(|q| q.to_uppercase()).collect();
 
-    if is_chief(name) {
+    if raterank.is_chief {
         extra_quals.push("Chief".to_string());
         extra_quals.push("QAS".to_string());
     }
-    if is_mmcpo(name) {
+    if raterank.is_master {
         extra_quals.push("MMCPO".to_string());
     }
-    if is_az(name) {
+    // C-40 ASM report doesn't have a qual that matches logs and records. New plan is to just include all AZs.
+    if raterank.rate == "AZ" {
         extra_quals.push("AZ".to_string());
     }
-    if is_supply(name) {
+    if raterank.rate == "LS" {
         extra_quals.push("Supply".to_string());
         extra_quals.push("020 SUP".to_string());
     }
 
-    if is_fs_qar(name, &quals) {
+    if is_fs_qar(&quals) {
         extra_quals.push("F/S QAR".to_string());
     }
-    if is_twohundred_cdi(name, &quals) {
+    if is_twohundred_cdi(&quals) {
         extra_quals.push("200 CDI".to_string());
     }
-    if is_onehundred_cdi(name, &quals) {
+    if is_onehundred_cdi(&quals) {
         extra_quals.push("100 CDI".to_string());
     }
-    if is_onethirty_cdi(name, &quals) {
+    if is_onethirty_cdi(&quals) {
         extra_quals.push("130 CDI".to_string());
     }
 
     extra_quals
 }
 
-fn build_teams(app_state: &AppState) -> Result<Vec<Team>> {
+pub fn build_teams(app_state: &AppState) -> Result<Vec<Team>> {
     let files = &app_state.files;
 
     let parsed_requirements = files
@@ -201,122 +332,1001 @@ fn build_teams(app_state: &AppState) -> Result<Vec<Team>> {
     Ok(teams.clone())
 }
 
-pub fn generate_assignments(
-    analysis_date: chrono::NaiveDate,
-    assignment_locks: Option<Vec<AssignmentLock>>,
-    app_state: &AppState,
-) -> Result<AssignmentResult> {
-    let people = build_people(analysis_date, app_state)?;
-    let teams = Rc::new(build_teams(app_state)?);
-    let mut solver =
-        AssignmentSolver::new(&people, &teams, analysis_date, assignment_locks.clone());
-    let (_flow_count, _flow_cost) = solver.solve();
-    let mut flow_assignments = solver.extract_assignments();
-    if let Some(locks) = assignment_locks {
-        for lock in locks {
-            if let (Some(team_name), Some(position)) = (lock.team_name, lock.position) {
-                flow_assignments.push(FlowAssignment {
-                    person_name: lock.person_name,
-                    team: team_name,
-                    position,
-                    manual_override: true,
-                });
-            }
+/// What the roster and teams are built from, cheap to compare: parsed files are shared
+/// behind `Rc`s, so the same pointers mean the same data
+#[derive(Clone)]
+pub struct SolverInputs {
+    files: Vec<Option<ParsedData>>,
+    pending_qualifications: Vec<PendingQualification>,
+    cost_policy: CostPolicy,
+}
+
+impl SolverInputs {
+    pub fn of(app_state: &AppState) -> Self {
+        SolverInputs {
+            files: PAGES
+                .iter()
+                .map(|page| app_state.files.get(*page)?.parsed_data.clone())
+                .collect(),
+            pending_qualifications: app_state.pending_qualifications.clone(),
+            cost_policy: app_state.cost_policy.clone(),
         }
     }
-    Ok(AssignmentResult {
-        flow_assignments,
-        people, // No clone needed - Rc is designed for sharing
-        teams,  // No clone needed - Rc is designed for sharing  
-    })
 }
 
-/// Generate assignments using pre-processed people and teams data (for loading from save states)
-pub fn generate_assignments_from_processed_data(
+impl PartialEq for SolverInputs {
+    fn eq(&self, other: &Self) -> bool {
+        let same_file = |a: &Option<ParsedData>, b: &Option<ParsedData>| match (a, b) {
+            (None, None) => true,
+            (Some(a), Some(b)) => a.ptr_eq(b),
+            _ => false,
+        };
+        self.files.len() == other.files.len()
+            && self
+                .files
+                .iter()
+                .zip(&other.files)
+                .all(|(a, b)| same_file(a, b))
+            && self.pending_qualifications == other.pending_qualifications
+            && self.cost_policy == other.cost_policy
+    }
+}
+
+/// Keeps one solver alive across runs so that changing locks or the analysis date
+/// repairs the previous flow instead of rebuilding and re-solving from scratch.
+///
+/// It is also the one way to solve pre-processed people and teams, e.g. from a save
+/// state: `WarmStartSolver::new(people, teams, &policy).solve(date, locks, &[], &[])`
+/// does what `generate_assignments_from_processed_data` used to, and sharing the path
+/// keeps those plans from drifting from the ones on screen.
+pub struct WarmStartSolver {
+    people: Vec<Person>,
+    teams: Rc<Vec<Team>>,
+    cost_policy: CostPolicy,
+    baseline: PlanBaseline,
+    /// Also solve every run's final network with the other backends
+    compare_backends: bool,
+    solver: AssignmentSolver,
+}
+
+impl WarmStartSolver {
+    /// The network covers every person and every position; locks and PRDs are applied
+    /// per run through `AssignmentSolver::apply_constraints`
+    pub fn new(people: Vec<Person>, teams: Vec<Team>, cost_policy: &CostPolicy) -> Self {
+        // the date only prices the edges; every run reprices them for its own date
+        let solver = AssignmentSolver::new(
+            &people,
+            &teams,
+            chrono::NaiveDate::MIN,
+            None,
+            &[],
+            cost_policy,
+        );
+        WarmStartSolver {
+            people,
+            teams: Rc::new(teams),
+            cost_policy: cost_policy.clone(),
+            baseline: PlanBaseline::new(),
+            compare_backends: false,
+            solver,
+        }
+    }
+
+    /// Algorithm the next runs solve with, and whether to check it against the others
+    /// once each run is final
+    pub fn set_backend(&mut self, backend: BackendKind, compare_backends: bool) {
+        self.solver.set_backend(backend);
+        self.compare_backends = compare_backends;
+    }
+
+    /// Everyone the network covers, departed or not
+    pub fn people(&self) -> &[Person] {
+        &self.people
+    }
+
+    /// Plan the next runs should stay close to; an empty baseline turns continuity off
+    pub fn set_baseline(&mut self, baseline: PlanBaseline) {
+        if self.baseline != baseline {
+            self.baseline = baseline.clone();
+            self.solver.set_baseline(baseline);
+        }
+    }
+
+    /// Plan for these inputs; the same plan whatever runs came before
+    pub fn solve(
+        &mut self,
+        analysis_date: chrono::NaiveDate,
+        assignment_locks: Option<Vec<AssignmentLock>>,
+        exclusions: &[AssignmentExclusion],
+        pairings: &[PairingConstraint],
+    ) -> Result<AssignmentResult> {
+        let locks = assignment_locks.unwrap_or_default();
+        let timer = self.solver.start_run();
+        let pairing_exclusions = enforce_pairings(
+            &mut self.solver,
+            &self.people,
+            &self.teams,
+            analysis_date,
+            &locks,
+            exclusions,
+            pairings,
+        );
+        let solve_stats = self.solver.finish_run(timer, locks.len());
+
+        let mut flow_assignments = self.solver.extract_assignments();
+        flow_assignments.extend(lock_assignments(Some(&locks)));
+        let (people, departed_people) = partition_by_prd(self.people.clone(), analysis_date);
+
+        Ok(AssignmentResult {
+            analysis_date,
+            people,
+            teams: self.teams.clone(),
+            flow_assignments,
+            departed_people,
+            unfilled_reasons: self.solver.explain_unfilled(),
+            exclusions: exclusions.to_vec(),
+            pairing_exclusions,
+            pairings: pairings.to_vec(),
+            baseline: self.baseline.clone(),
+            backend: self.solver.backend(),
+            backend_mismatches: if self.compare_backends {
+                self.solver.compare_backends()
+            } else {
+                vec![]
+            },
+            dropped_floors: self.solver.dropped_floors().to_vec(),
+            cost_policy: self.cost_policy.clone(),
+            solve_stats,
+        })
+    }
+
+    /// Marginal value of one more holder of each qualification under the last `solve`
+    pub fn shadow_prices(&mut self, analysis_date: chrono::NaiveDate) -> Vec<ShadowPrice> {
+        self.solver.shadow_prices(analysis_date)
+    }
+}
+
+/// One of several ranked plans for the same inputs
+#[derive(Clone)]
+pub struct AlternativePlan {
+    pub result: Rc<AssignmentResult>,
+    pub cost: i64,
+}
+
+/// Up to `count` distinct plans for the same inputs as `base`, best first.
+///
+/// The exclusions `base` added for its pairing rules are kept, so the search stays
+/// near plans that honor them, but an alternative can still break a rule in a way
+/// those exclusions do not cover. Each is checked again and dropped if it breaks a
+/// pairing rule `base` keeps, so fewer than `count` plans may come back.
+pub fn generate_alternative_plans(
+    base: &AssignmentResult,
     analysis_date: chrono::NaiveDate,
     assignment_locks: Option<Vec<AssignmentLock>>,
+    cost_policy: &CostPolicy,
+    backend: BackendKind,
+    count: usize,
+) -> Result<Vec<AlternativePlan>> {
+    let people = base.people.clone();
+    let departed_people = base.departed_people.clone();
+    let teams = base.teams.clone();
+    let exclusions: Vec<AssignmentExclusion> = base
+        .exclusions
+        .iter()
+        .chain(&base.pairing_exclusions)
+        .cloned()
+        .collect();
+    let mut solver = AssignmentSolver::new(
+        &people,
+        &teams,
+        analysis_date,
+        assignment_locks.clone(),
+        &exclusions,
+        cost_policy,
+    );
+    solver.set_baseline(base.baseline.clone());
+    solver.set_backend(backend);
+    let timer = solver.start_run();
+    let ranked = solver.top_k_plans(count);
+    let solve_stats = solver.finish_run(timer, assignment_locks.as_ref().map_or(0, Vec::len));
+    let dropped_floors = solver.dropped_floors().to_vec();
+
+    let base_broken = unsatisfied_pairings(&base.pairings, placements(&base.flow_assignments, &[]));
+    Ok(ranked
+        .into_iter()
+        .map(|plan| {
+            let mut flow_assignments = plan.assignments;
+            flow_assignments.extend(lock_assignments(assignment_locks.as_deref()));
+            (flow_assignments, plan.unfilled_reasons, plan.cost)
+        })
+        .filter(|(flow_assignments, _, _)| {
+            unsatisfied_pairings(&base.pairings, placements(flow_assignments, &[]))
+                .iter()
+                .all(|pairing| base_broken.contains(pairing))
+        })
+        .map(
+            |(flow_assignments, unfilled_reasons, cost)| AlternativePlan {
+                result: Rc::new(AssignmentResult {
+                    analysis_date,
+                    people: people.clone(),
+                    teams: teams.clone(),
+                    flow_assignments,
+                    departed_people: departed_people.clone(),
+                    unfilled_reasons,
+                    exclusions: base.exclusions.clone(),
+                    pairing_exclusions: base.pairing_exclusions.clone(),
+                    pairings: base.pairings.clone(),
+                    baseline: base.baseline.clone(),
+                    backend,
+                    backend_mismatches: vec![],
+                    dropped_floors: dropped_floors.clone(),
+                    cost_policy: cost_policy.clone(),
+                    solve_stats,
+                }),
+                cost,
+            },
+        )
+        .collect())
+}
+
+/// People whose PRD has passed are kept aside to explain unfilled positions
+fn partition_by_prd(
     people: Vec<Person>,
-    teams: Vec<Team>,
-) -> Result<AssignmentResult> {
-    // Filter people by PRD date (same logic as build_people)
-    let mut filtered_people = people;
-    filtered_people.retain(|person| match person.prd {
-        Some(prd_date) => prd_date > analysis_date,
-        None => true,
+    analysis_date: chrono::NaiveDate,
+) -> (Rc<Vec<Person>>, Rc<Vec<Person>>) {
+    let (available_people, departed_people): (Vec<Person>, Vec<Person>) =
+        people.into_iter().partition(|person| match person.prd {
+            Some(prd_date) => prd_date > analysis_date,
+            None => true,
+        });
+    (Rc::new(available_people), Rc::new(departed_people))
+}
+
+/// Locked positions the solver never saw, as assignments in team, qualification and
+/// instance order - locks usually come out of a `HashMap`
+fn lock_assignments(assignment_locks: Option<&[AssignmentLock]>) -> Vec<FlowAssignment> {
+    let mut assignments: Vec<FlowAssignment> = assignment_locks
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|lock| match (&lock.team_name, &lock.position) {
+            (Some(team_name), Some(position)) => Some(FlowAssignment {
+                person_name: lock.person_name.clone(),
+                team: team_name.clone(),
+                position: position.clone(),
+                manual_override: true,
+            }),
+            _ => None,
+        })
+        .collect();
+    assignments.sort_by(|a, b| {
+        (&a.team, &a.position.qualification, a.position.instance).cmp(&(
+            &b.team,
+            &b.position.qualification,
+            b.position.instance,
+        ))
     });
+    assignments
+}
 
-    let people = Rc::new(filtered_people);
-    let teams = Rc::new(teams);
-    let mut solver =
-        AssignmentSolver::new(&people, &teams, analysis_date, assignment_locks.clone());
-    let (_flow_count, _flow_cost) = solver.solve();
-    let mut flow_assignments = solver.extract_assignments();
-    if let Some(locks) = assignment_locks {
-        for lock in locks {
-            if let (Some(team_name), Some(position)) = (lock.team_name, lock.position) {
-                flow_assignments.push(FlowAssignment {
-                    person_name: lock.person_name,
-                    team: team_name,
-                    position,
-                    manual_override: true,
-                });
-            }
-        }
+/// What the solver charged for `person` filling `qualification` on `team_name`
+fn assignment_cost(
+    result: &AssignmentResult,
+    person: &Person,
+    team_name: &str,
+    qualification: &str,
+) -> CostBreakdown {
+    let policy = &result.cost_policy;
+    let mut cost = policy.assignment_cost(person, qualification, result.analysis_date);
+    let stays = result
+        .baseline
+        .get(&person.name)
+        .is_some_and(|slot| slot.team_name == team_name && slot.qualification == qualification);
+    if stays {
+        cost.continuity = -policy.continuity_bonus;
     }
-
-    Ok(AssignmentResult {
-        flow_assignments,
-        people,
-        teams,
-    })
+    cost
 }
 
-pub fn build_assignment_plan(
-    people: &[Person],
-    teams: &[Team],
-    flow_assignments: &[FlowAssignment],
-) -> Result<AssignmentPlan, anyhow::Error> {
-    let assigned_names: Vec<_> = flow_assignments.iter().map(|a| &a.person_name).collect();
+pub fn build_assignment_plan(result: &AssignmentResult) -> Result<AssignmentPlan, anyhow::Error> {
+    let people = result.people.as_slice();
+    let flow_assignments = result.flow_assignments.as_slice();
+    let assigned_names: HashSet<&str> = flow_assignments
+        .iter()
+        .map(|a| a.person_name.as_str())
+        .collect();
+    let people_by_name: HashMap<&str, &Person> = people.iter().map(|p| (p.get_name(), p)).collect();
 
     let (_assigned_people, unassigned_people): (Vec<&Person>, Vec<&Person>) = people
         .iter()
-        .partition(|p| assigned_names.contains(&&p.get_name().to_string()));
+        .partition(|p| assigned_names.contains(p.get_name()));
 
     let mut assignments = vec![];
     for a in flow_assignments {
-        let person = people
-            .iter()
-            .find(|p| p.get_name() == a.person_name)
-            .ok_or_else(|| {
-                anyhow!(
-                    "Person {} in assignment not found in people list",
-                    a.person_name
-                )
-            })?;
+        let person = people_by_name.get(a.person_name.as_str()).ok_or_else(|| {
+            anyhow!(
+                "Person {} in assignment not found in people list",
+                a.person_name
+            )
+        })?;
 
+        let cost = assignment_cost(result, person, &a.team, &a.position.qualification);
         assignments.push(Assignment {
-            person: Rc::new(person.clone()), // TODO: Could be optimized further with person Rc sharing
+            person: Rc::new((*person).clone()), // TODO: Could be optimized further with person Rc sharing
             team_name: a.team.clone(),
             position: a.position.clone(),
-            score: 1,
+            score: cost.total(),
+            cost,
             manual_override: a.manual_override,
         });
     }
 
+    let filled_roles: HashSet<String> = assignments.iter().map(|a| a.role_id()).collect();
     let mut unfilled_positions = vec![];
-    for team in teams {
+    let mut unfilled_mandatory_positions = vec![];
+    for team in result.teams.iter() {
         for position in &team.required_positions {
             let role_id = position.role_id(&team.name);
-            let is_filled = assignments.iter().any(|a| a.role_id() == role_id);
+            let is_filled = filled_roles.contains(&role_id);
             if !is_filled {
-                unfilled_positions.push((team.name.clone(), role_id));
+                let unfilled = UnfilledPosition {
+                    team_name: team.name.clone(),
+                    role_id,
+                    position: position.clone(),
+                    reason: unfilled_reason(result, &team.name, position, &assignments),
+                };
+                if position.mandatory {
+                    unfilled_mandatory_positions.push(unfilled);
+                } else {
+                    unfilled_positions.push(unfilled);
+                }
             }
         }
     }
 
+    let placements = || {
+        assignments
+            .iter()
+            .map(|a| (a.person.name.as_str(), a.team_name.as_str()))
+    };
+    let unsatisfied_pairings = unsatisfied_pairings(&result.pairings, placements());
+    let composition_violations = composition_violations(&result.teams, people, placements());
+
     Ok(AssignmentPlan {
         unassigned_people: Rc::new(unassigned_people.into_iter().cloned().collect()),
         assignments,
         unfilled_positions,
+        unfilled_mandatory_positions,
+        unsatisfied_pairings,
+        composition_violations,
     })
 }
+
+/// Combine the solver's residual-graph explanation with what the solver never saw:
+/// locked people, lapsed qualifications and people who rotated out before the
+/// analysis date
+fn unfilled_reason(
+    result: &AssignmentResult,
+    team_name: &str,
+    position: &Position,
+    assignments: &[Assignment],
+) -> UnfilledReason {
+    let date = result.analysis_date;
+    let solver_reason = result
+        .unfilled_reasons
+        .get(&position.as_role_id(team_name))
+        .cloned()
+        .unwrap_or(UnfilledReason::NoQualifiedPersonnel);
+
+    let mut competitors = match solver_reason {
+        UnfilledReason::UsedElsewhere { competitors } => competitors,
+        _ => vec![],
+    };
+    competitors.extend(
+        assignments
+            .iter()
+            .filter(|a| {
+                a.manual_override
+                    && a.person.is_qualified(&position.qualification, date)
+                    && position.admits(&a.person)
+            })
+            .map(|a| Competitor {
+                person_name: a.person.name.clone(),
+                role_id: a.role_id(),
+                locked: true,
+            }),
+    );
+    if !competitors.is_empty() {
+        return UnfilledReason::UsedElsewhere { competitors };
+    }
+
+    let excluded: Vec<String> = result
+        .people
+        .iter()
+        .filter(|p| p.is_qualified(&position.qualification, date) && position.admits(p))
+        .filter(|p| {
+            result
+                .exclusions
+                .iter()
+                .any(|e| e.excludes(&p.name, team_name, &position.qualification))
+        })
+        .map(|p| p.name.clone())
+        .collect();
+    if !excluded.is_empty() {
+        return UnfilledReason::Excluded { people: excluded };
+    }
+
+    let expired: Vec<String> = result
+        .people
+        .iter()
+        .filter(|p| {
+            p.qualifications.contains(&position.qualification)
+                && p.expiration(&position.qualification)
+                    .is_some_and(|expiry| expiry < date)
+                && position.admits(p)
+        })
+        .map(|p| p.name.clone())
+        .collect();
+    if !expired.is_empty() {
+        return UnfilledReason::Expired { people: expired };
+    }
+
+    let in_training: Vec<String> = result
+        .people
+        .iter()
+        .filter(|p| {
+            p.completion(&position.qualification)
+                .is_some_and(|done| done > date)
+                && position.admits(p)
+        })
+        .map(|p| p.name.clone())
+        .collect();
+    if !in_training.is_empty() {
+        return UnfilledReason::InTraining {
+            people: in_training,
+        };
+    }
+
+    let rotated_out: Vec<String> = result
+        .departed_people
+        .iter()
+        .filter(|p| p.qualifications.contains(&position.qualification) && position.admits(p))
+        .map(|p| p.name.clone())
+        .collect();
+    if !rotated_out.is_empty() {
+        return UnfilledReason::RotatedOut {
+            people: rotated_out,
+        };
+    }
+
+    let ineligible: Vec<String> = result
+        .people
+        .iter()
+        .filter(|p| p.is_qualified(&position.qualification, date) && !position.admits(p))
+        .map(|p| p.name.clone())
+        .collect();
+    if !ineligible.is_empty() {
+        return UnfilledReason::Ineligible { people: ineligible };
+    }
+
+    UnfilledReason::NoQualifiedPersonnel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::assignment::tests::{person, team};
+    use crate::engine::person::{apply_pending_qualifications, DutyStatus, PendingQualification};
+    use crate::engine::team::CompositionRules;
+    use chrono::NaiveDate;
+    use itertools::Itertools;
+
+    fn position(qualification: &str, instance: u32) -> Position {
+        Position {
+            qualification: qualification.to_string(),
+            instance,
+            mandatory: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_warm_start_matches_full_solve() {
+        let people = vec![
+            person("Smith", &["SFF", "CDI"])
+                .with_raterank("AM1")
+                .with_prd(NaiveDate::from_ymd_opt(2025, 4, 1)),
+            person("Jones", &["CDI"])
+                .with_raterank("AD2")
+                .with_status(DutyStatus::Selres),
+            person("Lee", &["Chief", "SFF"])
+                .with_raterank("AMC")
+                .with_prd(NaiveDate::from_ymd_opt(2026, 1, 1)),
+            person("Brown", &["SFF", "QAR"])
+                .with_raterank("AE3")
+                .with_prd(NaiveDate::from_ymd_opt(2025, 8, 1)),
+            person("Davis", &["QAR", "CDI"])
+                .with_raterank("AW2")
+                .with_status(DutyStatus::Selres),
+        ];
+        let teams = vec![
+            Team {
+                name: "Home Guard".to_string(),
+                priority: Some(1),
+                required_positions: vec![position("SFF", 1), position("Chief", 1)],
+                composition: CompositionRules::default(),
+            },
+            Team {
+                name: "Det".to_string(),
+                priority: Some(2),
+                required_positions: vec![
+                    position("CDI", 1),
+                    position("CDI", 2),
+                    position("QAR", 1),
+                ],
+                composition: CompositionRules::default(),
+            },
+        ];
+        let policy = CostPolicy::default();
+        let lock = AssignmentLock {
+            person_name: "Jones".to_string(),
+            team_name: Some("Det".to_string()),
+            position: Some(position("CDI", 2)),
+        };
+
+        let mut warm = WarmStartSolver::new(people.clone(), teams.clone(), &policy);
+        let runs = [
+            (NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(), None),
+            (
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                Some(vec![lock.clone()]),
+            ),
+            (
+                NaiveDate::from_ymd_opt(2025, 5, 1).unwrap(),
+                Some(vec![lock]),
+            ),
+            (NaiveDate::from_ymd_opt(2025, 9, 1).unwrap(), None),
+        ];
+        for (date, locks) in runs {
+            let warm_result = warm.solve(date, locks.clone(), &[], &[]).unwrap();
+            let warm_plan = build_assignment_plan(&warm_result).unwrap();
+            let full = WarmStartSolver::new(people.clone(), teams.clone(), &policy)
+                .solve(date, locks, &[], &[])
+                .unwrap();
+            let full_plan = build_assignment_plan(&full).unwrap();
+
+            // ties settle the same way on both paths, so the whole plan matches: who
+            // fills which qualification on which team, what stays empty and why
+            assert_eq!(warm_plan, full_plan, "diverged on {}", date);
+            assert_eq!(warm_result.exclusions, full.exclusions);
+            assert_eq!(warm_result.pairing_exclusions, full.pairing_exclusions);
+            assert_eq!(warm_result.unfilled_reasons, full.unfilled_reasons);
+            assert_eq!(warm_result.dropped_floors, full.dropped_floors);
+
+            let (warm_stats, full_stats) = (warm_result.solve_stats, full.solve_stats);
+            assert_eq!(warm_stats.total_flow, full_stats.total_flow);
+            assert_eq!(warm_stats.total_cost, full_stats.total_cost);
+            assert_eq!(warm_stats.locks_applied, full_stats.locks_applied);
+            assert!(full_stats.augmenting_iterations > 0);
+        }
+    }
+
+    #[test]
+    fn test_expired_qualifications_are_dropped_and_expiring_ones_cost_more() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let expiring = |name: &str, qual: &str, expiry: NaiveDate| {
+            let mut person = person(name, &[qual]);
+            person
+                .qualification_expirations
+                .insert(qual.to_string(), expiry);
+            person
+        };
+        let people = vec![
+            // expires before the analysis date
+            expiring(
+                "Adams",
+                "QAR",
+                NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            ),
+            // still valid, but lapses next month
+            expiring("Baker", "CDI", NaiveDate::from_ymd_opt(2025, 2, 1).unwrap()),
+            person("Clark", &["CDI"]),
+        ];
+        let teams = vec![Team {
+            name: "Det".to_string(),
+            priority: Some(1),
+            required_positions: vec![position("CDI", 1), position("QAR", 1)],
+            composition: CompositionRules::default(),
+        }];
+
+        let result = WarmStartSolver::new(people, teams, &CostPolicy::default())
+            .solve(date, None, &[], &[])
+            .unwrap();
+        let plan = build_assignment_plan(&result).unwrap();
+
+        let placed: Vec<_> = plan
+            .assignments
+            .iter()
+            .map(|a| a.person.name.as_str())
+            .collect();
+        assert_eq!(placed, vec!["Clark"]);
+        assert_eq!(
+            plan.unfilled_positions[0].reason,
+            UnfilledReason::Expired {
+                people: vec!["Adams".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_composite_lasts_while_any_alternative_does() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut adams = person("Adams", &["13A CDI", "13B CDI", "110 CDI", "120 CDI"]);
+        adams.qualification_expirations = [
+            ("13A CDI", date(2024, 12, 31)),
+            ("13B CDI", date(2025, 6, 1)),
+            ("110 CDI", date(2025, 3, 1)),
+        ]
+        .into_iter()
+        .map(|(qualification, expiration)| (qualification.to_string(), expiration))
+        .collect();
+
+        // 13A lapsing leaves 13B to carry 130 CDI
+        assert_eq!(
+            derivative_expiration("130 CDI", &adams),
+            Some(date(2025, 6, 1))
+        );
+        // both halves of 100 CDI are required, so it goes with 110 CDI
+        assert_eq!(
+            derivative_expiration("100 CDI", &adams),
+            Some(date(2025, 3, 1))
+        );
+        assert_eq!(derivative_expiration("Chief", &adams), None);
+
+        let mut people = vec![adams];
+        add_pending_qualifications(&mut people, &[]);
+        assert!(people[0].is_qualified("130 CDI", date(2025, 1, 1)));
+        assert!(!people[0].is_qualified("100 CDI", date(2025, 4, 1)));
+    }
+
+    #[test]
+    fn test_pending_sources_complete_a_composite() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let mut app_state = AppState::default();
+        let mut set = |page: &str, data: ParsedData| {
+            app_state.files.get_mut(page).unwrap().parsed_data = Some(data);
+        };
+        let qual_defs: QualTable = [
+            ("110 CDI".to_string(), vec!["CDI 110 ASM".to_string()]),
+            ("120 CDI".to_string(), vec!["CDI 120 ASM".to_string()]),
+        ]
+        .into_iter()
+        .collect();
+        set("Qual Defs", ParsedData::QualDefs(Rc::new(qual_defs)));
+        set(
+            "ASM",
+            ParsedData::Personnel(Rc::new(vec![
+                person("Adams", &["CDI 110 ASM"]),
+                person("Baker", &[]),
+            ])),
+        );
+        let pending = |name: &str, qualification: &str, completion_date| PendingQualification {
+            person_name: name.to_string(),
+            qualification: qualification.to_string(),
+            completion_date,
+        };
+        app_state.pending_qualifications = vec![
+            pending("Adams", "120 CDI", date(2025, 3, 1)),
+            pending("Baker", "110 CDI", date(2025, 2, 1)),
+            pending("Baker", "120 CDI", date(2025, 4, 1)),
+        ];
+
+        let people = build_roster(&app_state).unwrap();
+        // held outright plus one pending: 100 CDI counts once the pending one does
+        assert_eq!(people[0].completion("100 CDI"), Some(date(2025, 3, 1)));
+        assert!(!people[0].is_qualified("100 CDI", date(2025, 2, 28)));
+        assert!(people[0].is_qualified("100 CDI", date(2025, 3, 1)));
+        // both pending: it waits for the later of the two
+        assert_eq!(people[1].completion("100 CDI"), Some(date(2025, 4, 1)));
+        assert!(people[1].is_qualified("100 CDI", date(2025, 4, 1)));
+    }
+
+    #[test]
+    fn test_pending_qualifications_count_from_completion_date() {
+        let mut people = vec![person("Adams", &[])];
+        let teams = vec![Team {
+            name: "Det".to_string(),
+            priority: Some(1),
+            required_positions: vec![position("QAR", 1)],
+            composition: CompositionRules::default(),
+        }];
+        apply_pending_qualifications(
+            &mut people,
+            &[PendingQualification {
+                person_name: "Adams".to_string(),
+                qualification: "QAR".to_string(),
+                completion_date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            }],
+        );
+
+        let plan_on = |date: NaiveDate| {
+            let result =
+                WarmStartSolver::new(people.clone(), teams.clone(), &CostPolicy::default())
+                    .solve(date, None, &[], &[])
+                    .unwrap();
+            build_assignment_plan(&result).unwrap()
+        };
+
+        let before = plan_on(NaiveDate::from_ymd_opt(2025, 2, 28).unwrap());
+        assert!(before.assignments.is_empty());
+        assert_eq!(
+            before.unfilled_positions[0].reason,
+            UnfilledReason::InTraining {
+                people: vec!["Adams".to_string()]
+            }
+        );
+
+        let after = plan_on(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap());
+        assert_eq!(after.assignments[0].person.name, "Adams");
+        // saved without the training, as ASM reported them
+        assert!(people[0]
+            .without_pending_qualifications()
+            .qualifications
+            .is_empty());
+    }
+
+    #[test]
+    fn test_alternatives_keep_the_pairings_the_plan_keeps() {
+        use crate::engine::pairing::PairingKind;
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let people = vec![
+            person("Adams", &["PC"]),
+            person("Baker", &["PC"]),
+            person("Clark", &["PC"]),
+        ];
+        let teams = vec![
+            team("Day Check", Some(1), &["PC", "PC"]),
+            team("Night Check", Some(2), &["PC", "PC"]),
+        ];
+        let pairings = vec![PairingConstraint {
+            first: "Adams".to_string(),
+            second: "Baker".to_string(),
+            kind: PairingKind::Apart,
+        }];
+        let policy = CostPolicy::default();
+        let base = WarmStartSolver::new(people, teams, &policy)
+            .solve(date, None, &[], &pairings)
+            .unwrap();
+        // keeping Adams off Day Check splits them up, but still lets both work nights
+        assert_eq!(base.exclusions, vec![]);
+        assert_eq!(base.pairing_exclusions.len(), 1);
+
+        let alternatives =
+            generate_alternative_plans(&base, date, None, &policy, BackendKind::default(), 5)
+                .unwrap();
+        assert!(!alternatives.is_empty());
+        for alternative in &alternatives {
+            let result = &alternative.result;
+            assert_eq!(
+                unsatisfied_pairings(&pairings, placements(&result.flow_assignments, &[])),
+                vec![]
+            );
+        }
+    }
+
+    #[test]
+    fn test_exclusions_move_people_elsewhere() {
+        let people = vec![
+            person("Jones", &["CDI"]).with_raterank("AD2"),
+            person("Davis", &["QAR"]),
+        ];
+        let teams = vec![
+            Team {
+                name: "Det".to_string(),
+                priority: Some(1),
+                required_positions: vec![position("CDI", 1), position("QAR", 1)],
+                composition: CompositionRules::default(),
+            },
+            Team {
+                name: "Day Check".to_string(),
+                priority: Some(2),
+                required_positions: vec![position("CDI", 1)],
+                composition: CompositionRules::default(),
+            },
+        ];
+        let exclusions = vec![
+            AssignmentExclusion {
+                person_name: "Jones".to_string(),
+                team_name: Some("Det".to_string()),
+                qualification: None,
+            },
+            AssignmentExclusion {
+                person_name: "Davis".to_string(),
+                team_name: None,
+                qualification: Some("QAR".to_string()),
+            },
+        ];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        let result = WarmStartSolver::new(people, teams, &CostPolicy::default())
+            .solve(date, None, &exclusions, &[])
+            .unwrap();
+        let plan = build_assignment_plan(&result).unwrap();
+
+        let placements: Vec<_> = plan
+            .assignments
+            .iter()
+            .map(|a| (a.person.name.as_str(), a.role_id()))
+            .collect();
+        assert_eq!(placements, vec![("Jones", "Day Check-CDI-001".to_string())]);
+
+        let reasons: Vec<_> = plan
+            .unfilled_positions
+            .iter()
+            .map(|u| (u.role_id.as_str(), &u.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                (
+                    "Det-CDI-001",
+                    &UnfilledReason::Excluded {
+                        people: vec!["Jones".to_string()]
+                    }
+                ),
+                (
+                    "Det-QAR-001",
+                    &UnfilledReason::Excluded {
+                        people: vec!["Davis".to_string()]
+                    }
+                ),
+            ]
+        );
+    }
+
+    fn demo_app_state() -> AppState {
+        use crate::utilities::parsing::{
+            enhance_personnel_with_prd, parse_asm_file, parse_fltmps_file, parse_qual_defs,
+            parse_requirements,
+        };
+
+        let bytes = |data: &[u8]| Rc::new(data.to_vec());
+        let teams = parse_requirements(bytes(include_bytes!("../../assets/demo/demoteams.csv")));
+        let quals = parse_qual_defs(bytes(include_bytes!("../../assets/demo/demoqualtable.csv")));
+        let mut people =
+            parse_asm_file(bytes(include_bytes!("../../assets/demo/demoasm.xlsx"))).unwrap();
+        let prds = parse_fltmps_file(bytes(include_bytes!("../../assets/demo/demofltmps.xlsx")));
+        enhance_personnel_with_prd(&mut people, prds.unwrap()).unwrap();
+
+        let mut app_state = AppState::default();
+        let mut set = |page: &str, data: ParsedData| {
+            app_state.files.get_mut(page).unwrap().parsed_data = Some(data);
+        };
+        set(
+            "Requirements",
+            ParsedData::Requirements(Rc::new(teams.unwrap())),
+        );
+        set("Qual Defs", ParsedData::QualDefs(Rc::new(quals.unwrap())));
+        set("ASM", ParsedData::Personnel(Rc::new(people)));
+        app_state
+    }
+
+    fn demo_plan(
+        date: NaiveDate,
+        locks: Option<Vec<AssignmentLock>>,
+        app_state: &AppState,
+    ) -> AssignmentPlan {
+        let result = WarmStartSolver::new(
+            build_roster(app_state).unwrap(),
+            build_teams(app_state).unwrap(),
+            &app_state.cost_policy,
+        )
+        .solve(date, locks, &app_state.exclusions, &app_state.pairings)
+        .unwrap();
+        build_assignment_plan(&result).unwrap()
+    }
+
+    #[test]
+    fn test_demo_plan_is_reproducible() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 1).unwrap();
+        let plan_output = |locks: &[(String, String, Position)]| {
+            // fresh parses and a fresh lock map each run, so nothing carries over
+            let mut app_state = demo_app_state();
+            for (person, team, position) in locks {
+                app_state
+                    .persistent_locks
+                    .insert((team.clone(), position.clone()), person.clone());
+            }
+            let locks: Vec<AssignmentLock> = app_state
+                .persistent_locks
+                .iter()
+                .map(|((team, position), person)| AssignmentLock {
+                    person_name: person.clone(),
+                    team_name: Some(team.clone()),
+                    position: Some(position.clone()),
+                })
+                .collect();
+            let locks = (!locks.is_empty()).then_some(locks);
+            format!("{:?}", demo_plan(date, locks, &app_state))
+        };
+
+        let baseline = plan_output(&[]);
+        for _ in 0..4 {
+            assert!(
+                plan_output(&[]) == baseline,
+                "unlocked plan changed between runs"
+            );
+        }
+
+        let plan = demo_plan(date, None, &demo_app_state());
+        assert!(plan.assignments.len() >= 3);
+        let locks: Vec<_> = plan
+            .assignments
+            .iter()
+            .step_by(plan.assignments.len() / 3)
+            .map(|a| {
+                (
+                    a.person.name.clone(),
+                    a.team_name.clone(),
+                    a.position.clone(),
+                )
+            })
+            .collect();
+        let locked_baseline = plan_output(&locks);
+        for _ in 0..4 {
+            assert!(
+                plan_output(&locks) == locked_baseline,
+                "locked plan changed between runs"
+            );
+        }
+    }
+
+    #[test]
+    fn test_warm_start_ignores_edit_order() {
+        let app_state = demo_app_state();
+        let (start, end) = (
+            NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 9, 1).unwrap(),
+        );
+        let plan = demo_plan(end, None, &app_state);
+        let locks: Vec<AssignmentLock> = plan
+            .assignments
+            .iter()
+            .step_by(plan.assignments.len() / 2)
+            .take(2)
+            .map(|a| AssignmentLock {
+                person_name: a.person.name.clone(),
+                team_name: Some(a.team_name.clone()),
+                position: Some(a.position.clone()),
+            })
+            .collect();
+        let expected = format!("{:?}", demo_plan(end, Some(locks.clone()), &app_state));
+
+        // two locks and a date change, applied one at a time in every order
+        let edits = [Some(0), Some(1), None];
+        for order in edits.iter().permutations(edits.len()) {
+            let mut warm = WarmStartSolver::new(
+                build_roster(&app_state).unwrap(),
+                build_teams(&app_state).unwrap(),
+                &app_state.cost_policy,
+            );
+            let (mut date, mut applied) = (start, vec![]);
+            warm.solve(date, None, &[], &[]).unwrap();
+            for edit in &order {
+                match edit {
+                    Some(lock) => applied.push(locks[*lock].clone()),
+                    None => date = end,
+                }
+                warm.solve(date, Some(applied.clone()), &[], &[]).unwrap();
+            }
+
+            let result = warm.solve(date, Some(applied), &[], &[]).unwrap();
+            assert_eq!(
+                format!("{:?}", build_assignment_plan(&result).unwrap()),
+                expected,
+                "plan depends on edit order {:?}",
+                order
+            );
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::person::{DutyStatus, Person};
+
+/// Weights the solver uses to price a person → role edge.
+///
+/// Every field has a default matching the original hard-coded weights, so a
+/// partial JSON document only needs to list the values a squadron wants to change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostPolicy {
+    /// Added for every TAR sailor
+    pub tar_penalty: i64,
+    /// Added for every SELRES sailor
+    pub selres_penalty: i64,
+    /// Added when the PRD has already passed on the analysis date
+    pub past_prd_penalty: i64,
+    /// PRD bands checked in order; the first band the sailor falls within applies
+    pub prd_bands: Vec<PrdBand>,
+    /// Added when the qualification expires fewer than `expiring_within_days` days
+    /// after the analysis date
+    pub expiring_qualification_penalty: i64,
+    pub expiring_within_days: i64,
+    /// Added for AW rates
+    pub aw_penalty: i64,
+    /// Added for rates outside the "A" aviation ratings
+    pub non_aviation_penalty: i64,
+    /// Added for chiefs, senior chiefs and master chiefs
+    pub chief_penalty: i64,
+    /// Added on top of `chief_penalty` for master chiefs
+    pub master_chief_penalty: i64,
+    /// Added for command master chiefs
+    pub command_master_chief_penalty: i64,
+    /// Qualifications the solver should favor filling over others
+    pub incentive_qualifications: Vec<String>,
+    /// Subtracted when filling one of `incentive_qualifications`
+    pub incentive_bonus: i64,
+    /// Subtracted when a person stays in the team and qualification they held in the
+    /// baseline plan, so a re-solve only moves people when it gains more than this
+    pub continuity_bonus: i64,
+    /// Training recommendations only: subtracted in full when every holder of the
+    /// qualification shares one of the candidate's quals in the same family, in
+    /// proportion otherwise
+    pub related_qualification_bonus: i64,
+    /// Added per priority tier below the most important team; large enough to
+    /// outweigh any person penalty so scarce people go to higher priority teams
+    pub team_priority_step: i64,
+    /// Added for filling an optional position; large enough to outweigh team
+    /// priorities so every mandatory position that can be filled is filled first
+    pub optional_position_penalty: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrdBand {
+    /// Band applies when the PRD is fewer than this many days after the analysis date
+    pub within_days: i64,
+    pub penalty: i64,
+}
+
+impl Default for CostPolicy {
+    fn default() -> Self {
+        CostPolicy {
+            tar_penalty: 0,
+            selres_penalty: 15_000,
+            past_prd_penalty: 20_000,
+            prd_bands: vec![
+                PrdBand {
+                    within_days: 90,
+                    penalty: 11_000,
+                },
+                PrdBand {
+                    within_days: 180,
+                    penalty: 5_000,
+                },
+                PrdBand {
+                    within_days: 365,
+                    penalty: 1_000,
+                },
+            ],
+            expiring_qualification_penalty: 5_000,
+            expiring_within_days: 90,
+            aw_penalty: 10_000,
+            non_aviation_penalty: 10_000,
+            chief_penalty: 5_000,
+            master_chief_penalty: 5_000,
+            command_master_chief_penalty: 10_000,
+            incentive_qualifications: vec![
+                "SFF".to_string(),
+                "Chief".to_string(),
+                "F/S QAR".to_string(),
+            ],
+            incentive_bonus: 1_000,
+            continuity_bonus: 5_000,
+            related_qualification_bonus: 5_000,
+            team_priority_step: 100_000,
+            optional_position_penalty: 10_000_000,
+        }
+    }
+}
+
+/// The parts of one person → role edge cost; they add up to the edge's cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CostBreakdown {
+    pub duty_status: i64,
+    pub prd_band: i64,
+    pub aw: i64,
+    pub non_aviation: i64,
+    /// Chief, master chief and command master chief penalties
+    pub seniority: i64,
+    pub expiring_qualification: i64,
+    /// Negative when the qualification is one of the incentive qualifications
+    pub role_incentive: i64,
+    /// Negative when the person stays in their baseline slot
+    pub continuity: i64,
+}
+
+impl CostBreakdown {
+    pub fn total(&self) -> i64 {
+        self.parts().iter().map(|(_, cost)| cost).sum()
+    }
+
+    /// Every part with a label, in the order the solver adds them
+    pub fn parts(&self) -> [(&'static str, i64); 8] {
+        [
+            ("Duty status", self.duty_status),
+            ("PRD band", self.prd_band),
+            ("AW", self.aw),
+            ("Non-aviation rate", self.non_aviation),
+            ("Seniority", self.seniority),
+            ("Expiring qual", self.expiring_qualification),
+            ("Role incentive", self.role_incentive),
+            ("Continuity", self.continuity),
+        ]
+    }
+}
+
+impl CostPolicy {
+    /// Load a policy from JSON; missing fields fall back to the defaults
+    pub fn from_json(json_content: &str) -> Result<Self> {
+        serde_json::from_str(json_content).context("Failed to parse cost policy JSON")
+    }
+
+    /// Penalty for a PRD `days_remaining` days after the analysis date
+    pub fn prd_penalty(&self, days_remaining: i64) -> i64 {
+        if days_remaining < 0 {
+            return self.past_prd_penalty;
+        }
+        self.prd_bands
+            .iter()
+            .find(|band| days_remaining < band.within_days)
+            .map_or(0, |band| band.penalty)
+    }
+
+    /// Price of placing `person` in a `qualification` role on `analysis_date`, before
+    /// any continuity bonus
+    pub fn assignment_cost(
+        &self,
+        person: &Person,
+        qualification: &str,
+        analysis_date: chrono::NaiveDate,
+    ) -> CostBreakdown {
+        let raterank = &person.raterank;
+        let is_expiring = person.expiration(qualification).is_some_and(|expiry| {
+            (0..self.expiring_within_days).contains(&(expiry - analysis_date).num_days())
+        });
+        let penalty_if = |applies: bool, penalty: i64| if applies { penalty } else { 0 };
+
+        CostBreakdown {
+            duty_status: match person.duty_status {
+                DutyStatus::Tar => self.tar_penalty,
+                DutyStatus::Selres => self.selres_penalty,
+            },
+            prd_band: person
+                .prd
+                .map_or(0, |prd| self.prd_penalty((prd - analysis_date).num_days())),
+            aw: penalty_if(raterank.is_aw(), self.aw_penalty),
+            non_aviation: penalty_if(!raterank.is_aviation(), self.non_aviation_penalty),
+            // a command master chief pays for the billet, not for being a chief as well
+            seniority: if raterank.is_command {
+                self.command_master_chief_penalty
+            } else {
+                penalty_if(raterank.is_chief, self.chief_penalty)
+                    + penalty_if(raterank.is_master, self.master_chief_penalty)
+            },
+            expiring_qualification: penalty_if(is_expiring, self.expiring_qualification_penalty),
+            // incentive filling these positions over others
+            role_incentive: -penalty_if(
+                self.is_incentive_qualification(qualification),
+                self.incentive_bonus,
+            ),
+            continuity: 0,
+        }
+    }
+
+    pub fn is_incentive_qualification(&self, qualification: &str) -> bool {
+        self.incentive_qualifications
+            .iter()
+            .any(|q| q == qualification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_prd_bands() {
+        let policy = CostPolicy::default();
+        assert_eq!(policy.prd_penalty(-1), 20_000);
+        assert_eq!(policy.prd_penalty(0), 11_000);
+        assert_eq!(policy.prd_penalty(89), 11_000);
+        assert_eq!(policy.prd_penalty(90), 5_000);
+        assert_eq!(policy.prd_penalty(364), 1_000);
+        assert_eq!(policy.prd_penalty(365), 0);
+    }
+
+    #[test]
+    fn test_partial_policy_json() {
+        let policy = CostPolicy::from_json(r#"{ "selres_penalty": 2000 }"#).unwrap();
+        assert_eq!(policy.selres_penalty, 2_000);
+        assert_eq!(policy.aw_penalty, CostPolicy::default().aw_penalty);
+    }
+
+    #[test]
+    fn test_assignment_cost_parts_add_up() {
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let person = Person {
+            name: "Adams".to_string(),
+            raterank: "AWC".into(),
+            duty_status: DutyStatus::Selres,
+            qualifications: ["SFF".to_string()].into(),
+            prd: chrono::NaiveDate::from_ymd_opt(2025, 3, 1),
+            qualification_expirations: Default::default(),
+            qualification_completions: Default::default(),
+        };
+        let cost = CostPolicy::default().assignment_cost(&person, "SFF", date);
+        assert_eq!(
+            cost,
+            CostBreakdown {
+                duty_status: 15_000,
+                prd_band: 11_000,
+                aw: 10_000,
+                non_aviation: 0,
+                seniority: 5_000,
+                expiring_qualification: 0,
+                role_incentive: -1_000,
+                continuity: 0,
+            }
+        );
+        assert_eq!(cost.total(), 40_000);
+    }
+
+    #[test]
+    fn test_rate_rank_penalties_keep_their_weights() {
+        // (raterank, non_aviation, seniority), as priced before rate/ranks were parsed
+        let cases = [
+            ("AM2", 0, 0),
+            ("AN", 0, 0),
+            ("AMC", 0, 5_000),
+            ("AMCS", 0, 5_000),
+            ("AMCM", 0, 10_000),
+            ("AMCMD", 0, 10_000),
+            ("CMDCM", 10_000, 10_000),
+            ("PR1", 10_000, 0),
+            ("PRC", 10_000, 5_000),
+            ("YN1", 10_000, 0),
+        ];
+        let date = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let policy = CostPolicy::default();
+        for (raterank, non_aviation, seniority) in cases {
+            let person = Person {
+                name: "Adams".to_string(),
+                raterank: raterank.into(),
+                duty_status: DutyStatus::Tar,
+                qualifications: Default::default(),
+                prd: None,
+                qualification_expirations: Default::default(),
+                qualification_completions: Default::default(),
+            };
+            let cost = policy.assignment_cost(&person, "PC", date);
+            assert_eq!(
+                (cost.non_aviation, cost.seniority),
+                (non_aviation, seniority),
+                "{}",
+                raterank
+            );
+        }
+    }
+}
@@ -3,7 +3,7 @@ use crate::engine::min_heap::MinHeap;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct DijkstraNode {
-    distance: i32,
+    distance: i64,
     node_id: usize,
 }
 
@@ -15,19 +15,57 @@ impl PartialOrd for DijkstraNode {
 
 impl Ord for DijkstraNode {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.distance.cmp(&other.distance)
+        self.distance
+            .cmp(&other.distance)
             .then_with(|| self.node_id.cmp(&other.node_id))
     }
 }
 
-pub fn dijkstra(graph: &FlowGraph, source: usize, sink: usize) -> Option<Vec<usize>> {
+pub struct ShortestPaths {
+    /// Reduced distance to every node; `i64::MAX` when never reached
+    pub distances: Vec<i64>,
+    parent_edge: Vec<Option<usize>>,
+}
+
+impl ShortestPaths {
+    /// Edge indices from the search source to `target`
+    pub fn path_to(&self, graph: &FlowGraph, target: usize) -> Vec<usize> {
+        let mut path = vec![];
+        let mut current = target;
+
+        while let Some(edge_idx) = self.parent_edge[current] {
+            path.push(edge_idx);
+            // the paired reverse edge points back at the node this edge leaves from
+            current = graph.edges[edge_idx ^ 1].to;
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Shortest paths from `source` using reduced costs `cost + p[u] - p[v]`.
+///
+/// Returns `None` when the sink is unreachable. The search stops once the sink is
+/// settled, so any distance beyond the sink's is only an upper bound (`i64::MAX` when
+/// never reached) - capping those at the sink's distance keeps the potentials valid.
+pub fn dijkstra(
+    graph: &FlowGraph,
+    source: usize,
+    sink: usize,
+    potentials: &[i64],
+) -> Option<ShortestPaths> {
     let num_nodes = graph.graph.len();
-    let mut distances = vec![i32::MAX; num_nodes];
-    let mut parent = vec![None; num_nodes];
+    let mut distances = vec![i64::MAX; num_nodes];
+    let mut parent_edge = vec![None; num_nodes];
     let mut heap = MinHeap::new();
 
     distances[source] = 0;
-    heap.insert(DijkstraNode { distance: 0, node_id: source}).ok()?;
+    heap.insert(DijkstraNode {
+        distance: 0,
+        node_id: source,
+    })
+    .ok()?;
 
     while heap.heap_size() > 0 {
         let current = heap.extract_min().ok()?;
@@ -36,7 +74,6 @@ pub fn dijkstra(graph: &FlowGraph, source: usize, sink: usize) -> Option<Vec<usi
         if current.distance > distances[u] {
             continue;
         }
-
         if u == sink {
             break;
         }
@@ -45,34 +82,28 @@ pub fn dijkstra(graph: &FlowGraph, source: usize, sink: usize) -> Option<Vec<usi
             let edge = &graph.edges[edge_idx];
 
             if graph.residual_capacity(edge_idx) > 0 {
-                let new_distance = distances[u].saturating_add(edge.cost);
+                let reduced_cost = edge.cost + potentials[u] - potentials[edge.to];
+                let new_distance = distances[u].saturating_add(reduced_cost);
 
                 if new_distance < distances[edge.to] {
                     distances[edge.to] = new_distance;
-                    parent[edge.to] = Some(u);
+                    parent_edge[edge.to] = Some(edge_idx);
                     heap.insert(DijkstraNode {
                         distance: new_distance,
-                        node_id: edge.to
-                    }).ok()?;
+                        node_id: edge.to,
+                    })
+                    .ok()?;
                 }
             }
         }
     }
 
-    if distances[sink] == i32::MAX {
+    if distances[sink] == i64::MAX {
         return None;
     }
 
-    let mut path = vec![];
-    let mut current = sink;
-
-    while let Some(prev) = parent[current] {
-        path.push(current);
-        current = prev;
-    }
-
-    path.push(source);
-    path.reverse();
-
-    Some(path)
-}
\ No newline at end of file
+    Some(ShortestPaths {
+        distances,
+        parent_edge,
+    })
+}
@@ -1,27 +1,80 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use crate::engine::djikstra::ShortestPaths;
+
 #[derive(Debug, Clone)]
 pub struct Edge {
     pub to: usize,
     pub capacity: i32, // max flow through edge
     pub flow: i32,     // current flow through edge
-    pub cost: i32,     // cost per unit of flow
+    pub cost: i64,     // cost per unit of flow
+    /// Least flow a forward edge must carry; always 0 on reverse edges
+    pub lower_bound: i32,
 }
 
+#[derive(Clone)]
 pub struct FlowGraph {
     pub edges: Vec<Edge>,
     pub graph: Vec<Vec<usize>>,
-    num_nodes: usize,
+    /// Node potentials left by the last solve, reused to warm-start `reoptimize`
+    potentials: Vec<i64>,
+    /// Augmenting paths and cycles pushed since the graph was built
+    augmentations: usize,
+}
+
+/// Why `min_cost_circulation` found no flow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlowError {
+    /// A forward edge whose lower bound is above its capacity
+    InvalidBounds {
+        edge_idx: usize,
+        lower_bound: i32,
+        capacity: i32,
+    },
+    /// No flow meets every lower bound. Lists each node left holding flow it could not
+    /// send (positive) or short of flow it needed (negative), with lower bounds counted
+    /// as flow their edges must carry.
+    Infeasible { unmet: Vec<(usize, i64)> },
+}
+
+impl Display for FlowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlowError::InvalidBounds {
+                edge_idx,
+                lower_bound,
+                capacity,
+            } => write!(
+                f,
+                "edge {} needs at least {} but holds at most {}",
+                edge_idx, lower_bound, capacity
+            ),
+            FlowError::Infeasible { unmet } => {
+                let nodes = unmet
+                    .iter()
+                    .map(|(node, amount)| format!("node {} ({:+})", node, amount))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "no feasible flow; unmet at {}", nodes)
+            }
+        }
+    }
 }
 
+impl std::error::Error for FlowError {}
+
 impl FlowGraph {
     pub fn new(num_nodes: usize) -> Self {
         FlowGraph {
             edges: vec![],
             graph: vec![vec![]; num_nodes],
-            num_nodes,
+            potentials: vec![],
+            augmentations: 0,
         }
     }
 
-    pub fn add_edge(&mut self, from: usize, to: usize, capacity: i32, cost: i32) {
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: i32, cost: i64) {
         let edge_idx = self.edges.len();
 
         self.edges.push(Edge {
@@ -29,6 +82,7 @@ impl FlowGraph {
             capacity,
             flow: 0,
             cost,
+            lower_bound: 0,
         });
         self.graph[from].push(edge_idx);
 
@@ -37,10 +91,39 @@ impl FlowGraph {
             capacity: 0,
             flow: 0,
             cost: -cost,
+            lower_bound: 0,
         });
         self.graph[to].push(edge_idx + 1);
     }
 
+    /// Edge that must carry at least `lower_bound` units. Residual searches never pull
+    /// flow below the bound; only `min_cost_circulation` and `max_flow_with_bounds`
+    /// start from a flow that meets it.
+    pub fn add_bounded_edge(
+        &mut self,
+        from: usize,
+        to: usize,
+        lower_bound: i32,
+        capacity: i32,
+        cost: i64,
+    ) {
+        self.add_edge(from, to, capacity, cost);
+        let forward_idx = self.edges.len() - 2;
+        self.edges[forward_idx].lower_bound = lower_bound;
+    }
+
+    pub fn set_lower_bound(&mut self, edge_idx: usize, lower_bound: i32) {
+        self.edges[edge_idx].lower_bound = lower_bound;
+    }
+
+    /// Whether any edge must carry flow
+    pub fn has_lower_bounds(&self) -> bool {
+        self.edges
+            .iter()
+            .step_by(2)
+            .any(|edge| edge.lower_bound > 0)
+    }
+
     // pub fn add_simple_edge(&mut self, from: usize, to: usize) {
     //     self.add_edge(from, to, 1, 0);
     // }
@@ -58,7 +141,13 @@ impl FlowGraph {
 
     pub fn residual_capacity(&self, edge_idx: usize) -> i32 {
         let edge = &self.edges[edge_idx];
-        edge.capacity - edge.flow
+        // a reverse edge only gives flow back down to its forward edge's lower bound
+        let floor = if edge_idx % 2 == 1 {
+            self.edges[edge_idx ^ 1].lower_bound
+        } else {
+            0
+        };
+        edge.capacity - edge.flow - floor
     }
 
     // pub fn ford_fulkerson(&mut self, source: usize, sink: usize) -> i32 {
@@ -79,104 +168,870 @@ impl FlowGraph {
     //     max_flow_value
     // }
 
-    fn find_augmenting_path(&self, source: usize, sink: usize) -> Option<Vec<usize>> {
-        // Use Dijkstra's algorithm for better O((V+E)log V) performance vs Bellman-Ford O(VE)
-        crate::engine::djikstra::dijkstra(self, source, sink)
+    fn shortest_paths(
+        &self,
+        source: usize,
+        sink: usize,
+        potentials: &[i64],
+    ) -> Option<ShortestPaths> {
+        // Dijkstra on reduced costs is only valid while the potentials keep every
+        // residual edge non-negative - see min_cost_max_flow
+        crate::engine::djikstra::dijkstra(self, source, sink, potentials)
+    }
+
+    /// Shortest distances from `source` over residual edges, tolerating negative costs.
+    /// Unreachable nodes get a potential of 0 - they can never join an augmenting path.
+    fn bellman_ford_potentials(&self, source: usize) -> Vec<i64> {
+        let mut distances = vec![i64::MAX; self.graph.len()];
+        distances[source] = 0;
+
+        self.relax_residual_edges(distances)
+            .into_iter()
+            .map(|d| if d == i64::MAX { 0 } else { d })
+            .collect()
+    }
+
+    /// Potentials that keep every residual edge non-negative, for any node. Only valid
+    /// while the residual graph has no negative cycle, i.e. the current flow is min-cost.
+    pub fn residual_potentials(&self) -> Vec<i64> {
+        self.relax_residual_edges(vec![0; self.graph.len()])
+    }
+
+    /// SPFA - queue-based Bellman-Ford starting from every node with a finite distance
+    fn relax_residual_edges(&self, mut distances: Vec<i64>) -> Vec<i64> {
+        let num_nodes = self.graph.len();
+        let mut in_queue = vec![false; num_nodes];
+        let mut queue = std::collections::VecDeque::new();
+
+        for node in 0..num_nodes {
+            if distances[node] != i64::MAX {
+                queue.push_back(node);
+                in_queue[node] = true;
+            }
+        }
+
+        while let Some(u) = queue.pop_front() {
+            in_queue[u] = false;
+            for &edge_idx in &self.graph[u] {
+                if self.residual_capacity(edge_idx) <= 0 {
+                    continue;
+                }
+                let edge = &self.edges[edge_idx];
+                let new_distance = distances[u] + edge.cost;
+                if new_distance < distances[edge.to] {
+                    distances[edge.to] = new_distance;
+                    if !in_queue[edge.to] {
+                        queue.push_back(edge.to);
+                        in_queue[edge.to] = true;
+                    }
+                }
+            }
+        }
+
+        distances
     }
 
-    pub fn min_cost_max_flow(&mut self, source: usize, sink: usize) -> (i32, i32) {
+    /// Cheapest residual cycle that pushes one unit through `edge_idx`, as edge indices
+    /// and its total cost. `potentials` must keep every residual edge non-negative.
+    pub fn cheapest_cycle_through(
+        &self,
+        edge_idx: usize,
+        potentials: &[i64],
+    ) -> Option<(Vec<usize>, i64)> {
+        if self.residual_capacity(edge_idx) <= 0 {
+            return None;
+        }
+        let from = self.edges[edge_idx ^ 1].to;
+        let to = self.edges[edge_idx].to;
+
+        let paths = self.shortest_paths(to, from, potentials)?;
+        let mut cycle = paths.path_to(self, from);
+        cycle.push(edge_idx);
+        let cost = self.path_cost(&cycle);
+        Some((cycle, cost))
+    }
+
+    /// Push `amount` of flow along each edge of `path`; negative amounts pull it back
+    pub fn push_flow(&mut self, path: &[usize], amount: i32) {
+        self.augmentations += 1;
+        for &edge_idx in path {
+            self.edges[edge_idx].flow += amount;
+            self.edges[edge_idx ^ 1].flow -= amount;
+        }
+    }
+
+    /// Drop every edge added after the graph held `num_edges` edges
+    pub fn truncate_edges(&mut self, num_edges: usize) {
+        while self.edges.len() > num_edges {
+            // edges come in forward/reverse pairs, each stored on the node it leaves
+            let forward_idx = self.edges.len() - 2;
+            let from = self.edges[forward_idx + 1].to;
+            let to = self.edges[forward_idx].to;
+            self.graph[from].retain(|&idx| idx != forward_idx);
+            self.graph[to].retain(|&idx| idx != forward_idx + 1);
+            self.edges.truncate(forward_idx);
+        }
+    }
+
+    /// Primal-dual min-cost flow with Johnson potentials.
+    ///
+    /// Forward edges may carry negative costs (role incentives) and every reverse edge
+    /// is the negation of its forward edge, so plain Dijkstra is not safe. A single
+    /// Bellman-Ford pass seeds the potentials, after which each Dijkstra runs on the
+    /// reduced costs `cost + p[u] - p[v]`, which stay non-negative after every augmentation.
+    /// Once the potentials are updated every shortest path uses only zero reduced-cost
+    /// edges, so each round saturates all of them with blocking flows before searching
+    /// again - rosters share only a handful of distinct costs, so this takes a few
+    /// Dijkstra runs rather than one per assigned person.
+    pub fn min_cost_max_flow(&mut self, source: usize, sink: usize) -> (i32, i64) {
         // initialize all flows to 0
         for edge in &mut self.edges {
             edge.flow = 0;
         }
 
-        let mut max_flow_value = 0;
-        let mut total_cost = 0;
+        let potentials = self.bellman_ford_potentials(source);
+        self.augment_shortest_paths(source, sink, potentials);
 
-        while let Some(path) = self.find_augmenting_path(source, sink) {
-            let path_flow = self.path_capacity(&path);
-            let path_cost = self.path_cost(&path);
+        (self.flow_value(source), self.total_cost())
+    }
 
-            self.augment_path(&path, path_flow);
+    /// Cheapest circulation that meets every edge's lower bound and capacity; beyond
+    /// the bounds, flow only goes around negative-cost cycles. Returns its cost.
+    ///
+    /// Every edge starts at its lower bound, or at capacity when its cost is negative,
+    /// so no residual edge costs less than zero. The surpluses and shortfalls this
+    /// leaves at the nodes are then routed through a super-source and super-sink by
+    /// successive shortest paths; whatever cannot be routed makes the problem
+    /// infeasible, and the flow is reset to zero.
+    pub fn min_cost_circulation(&mut self) -> Result<i64, FlowError> {
+        let num_nodes = self.graph.len();
+        let mut excess = vec![0_i64; num_nodes];
+        for edge_idx in (0..self.edges.len()).step_by(2) {
+            let edge = &self.edges[edge_idx];
+            let lower_bound = edge.lower_bound;
+            if lower_bound > edge.capacity {
+                return Err(FlowError::InvalidBounds {
+                    edge_idx,
+                    lower_bound,
+                    capacity: edge.capacity,
+                });
+            }
+            let flow = if edge.cost < 0 {
+                edge.capacity
+            } else {
+                lower_bound
+            };
+            let (from, to) = (self.edges[edge_idx ^ 1].to, edge.to);
+            self.edges[edge_idx].flow = flow;
+            self.edges[edge_idx ^ 1].flow = -flow;
+            excess[from] -= flow as i64;
+            excess[to] += flow as i64;
+        }
+
+        let num_edges = self.edges.len();
+        let super_source = num_nodes;
+        let super_sink = num_nodes + 1;
+        self.graph.push(vec![]);
+        self.graph.push(vec![]);
+        self.add_balancing_edges(&excess, super_source, super_sink);
+
+        // zero potentials are valid while no residual edge is negative
+        self.augment_shortest_paths(super_source, super_sink, vec![0; num_nodes + 2]);
 
-            max_flow_value += path_flow;
-            total_cost += path_flow * path_cost;
+        let mut unmet: BTreeMap<usize, i64> = BTreeMap::new();
+        for edge_idx in (num_edges..self.edges.len()).step_by(2) {
+            let residual = self.residual_capacity(edge_idx) as i64;
+            if residual == 0 {
+                continue;
+            }
+            let (node, amount) = match self.edges[edge_idx].to {
+                node if node == super_sink => (self.edges[edge_idx ^ 1].to, -residual),
+                node => (node, residual),
+            };
+            *unmet.entry(node).or_default() += amount;
         }
 
-        (max_flow_value, total_cost)
+        self.truncate_edges(num_edges);
+        self.graph.truncate(num_nodes);
+        self.potentials.truncate(num_nodes);
+
+        if !unmet.is_empty() {
+            for edge in &mut self.edges {
+                edge.flow = 0;
+            }
+            self.potentials.clear();
+            return Err(FlowError::Infeasible {
+                unmet: unmet.into_iter().collect(),
+            });
+        }
+        Ok(self.total_cost())
     }
 
-    fn path_cost(&self, path: &[usize]) -> i32 {
-        let mut total_cost = 0;
+    /// Min-cost max flow from `source` to `sink` that also carries every edge's lower
+    /// bound, or why no flow can.
+    ///
+    /// Solved as a circulation with a return edge from sink to source priced below any
+    /// path through the network, so it fills as much as it can before weighing costs;
+    /// supplies must be zero.
+    pub fn max_flow_with_bounds(
+        &mut self,
+        source: usize,
+        sink: usize,
+    ) -> Result<(i32, i64), FlowError> {
+        let num_edges = self.edges.len();
+        let most_flow = self.graph[source]
+            .iter()
+            .filter(|&&edge_idx| edge_idx % 2 == 0)
+            .map(|&edge_idx| self.edges[edge_idx].capacity.max(0))
+            .fold(0, i32::saturating_add);
+        let longest_path = self
+            .edges
+            .iter()
+            .step_by(2)
+            .map(|edge| edge.cost.abs())
+            .fold(0, i64::saturating_add);
+        self.add_edge(sink, source, most_flow, -(longest_path + 1));
 
-        for window in path.windows(2) {
-            let u = window[0];
-            let v = window[1];
+        let result = self.min_cost_circulation();
+        self.truncate_edges(num_edges);
+        result?;
+        self.settle_potentials();
+        Ok((self.flow_value(source), self.total_cost()))
+    }
 
-            for &edge_idx in &self.graph[u] {
-                if self.edges[edge_idx].to == v && self.residual_capacity(edge_idx) > 0 {
-                    total_cost += self.edges[edge_idx].cost;
-                    break;
+    /// Edges from `super_source` into every node with a surplus and from every node short
+    /// of flow into `super_sink`; surpluses past `i32::MAX` are split over parallel edges
+    fn add_balancing_edges(&mut self, excess: &[i64], super_source: usize, super_sink: usize) {
+        for (node, &amount) in excess.iter().enumerate() {
+            let mut remaining = amount.abs();
+            while remaining > 0 {
+                let capacity = i32::try_from(remaining).unwrap_or(i32::MAX);
+                if amount > 0 {
+                    self.add_edge(super_source, node, capacity, 0);
+                } else {
+                    self.add_edge(node, super_sink, capacity, 0);
                 }
+                remaining -= capacity as i64;
             }
         }
+    }
+
+    /// Restore a min-cost max flow after capacities or costs changed, starting from the
+    /// current flow rather than from zero.
+    ///
+    /// Flow over a reduced capacity is drained back to the source and sink. Every residual
+    /// edge whose reduced cost under the previous potentials went negative is then
+    /// saturated, which leaves the flow optimal but unbalanced at a few nodes; routing
+    /// those surpluses to the deficits along shortest paths restores conservation, and
+    /// successive shortest paths then top the flow back up.
+    pub fn reoptimize(&mut self, source: usize, sink: usize) -> (i32, i64) {
+        self.drain_excess_flow(source, sink);
 
-        total_cost
+        let num_nodes = self.graph.len();
+        let mut potentials = std::mem::take(&mut self.potentials);
+        potentials.resize(num_nodes, 0);
+
+        let mut imbalance = vec![0_i64; num_nodes];
+        for from in 0..num_nodes {
+            for &edge_idx in &self.graph[from] {
+                let edge = &self.edges[edge_idx];
+                let residual = self.residual_capacity(edge_idx);
+                if residual > 0 && edge.cost + potentials[from] - potentials[edge.to] < 0 {
+                    imbalance[from] -= residual as i64;
+                    imbalance[edge.to] += residual as i64;
+                    self.edges[edge_idx].flow += residual;
+                    self.edges[edge_idx ^ 1].flow -= residual;
+                }
+            }
+        }
+
+        if imbalance.iter().any(|&amount| amount != 0) {
+            potentials = self.rebalance(&imbalance, potentials);
+        }
+        self.augment_shortest_paths(source, sink, potentials);
+
+        (self.flow_value(source), self.total_cost())
     }
-    // dfs path used in original augmenting path algorithm. Not needed for shortest path approach.
-    // fn dfs_path(&self, u: usize, sink: usize, visited: &mut [bool], path: &mut Vec<usize>) -> bool {
-    //     visited[u] = true;
-    //     path.push(u);
 
-    //     if u == sink {
-    //         return true;
-    //     }
+    /// Route each node's surplus to the nodes short of flow through a temporary
+    /// super-source and super-sink, keeping `potentials` valid for the result.
+    /// The saturations that created the imbalance can always be undone, so every
+    /// surplus finds a deficit.
+    fn rebalance(&mut self, imbalance: &[i64], mut potentials: Vec<i64>) -> Vec<i64> {
+        let num_nodes = self.graph.len();
+        let num_edges = self.edges.len();
+        let super_source = num_nodes;
+        let super_sink = num_nodes + 1;
+        self.graph.push(vec![]);
+        self.graph.push(vec![]);
 
-    //     for &edge_idx in &self.graph[u] {
-    //         let edge = &self.edges[edge_idx];
-    //         if !visited[edge.to] && self.residual_capacity(edge_idx) > 0 {
-    //             if self.dfs_path(edge.to, sink, visited, path) {
-    //                 return true;
-    //             }
-    //         }
-    //     }
+        // zero-cost edges stay non-negative once the super nodes sit above and below
+        // every potential
+        let highest = potentials.iter().copied().max().unwrap_or(0);
+        let lowest = potentials.iter().copied().min().unwrap_or(0);
+        potentials.push(highest);
+        potentials.push(lowest);
+        self.add_balancing_edges(imbalance, super_source, super_sink);
 
-    //     path.pop();
-    //     false
-    // }
+        self.augment_shortest_paths(super_source, super_sink, potentials);
+        debug_assert!(self.graph[super_source]
+            .iter()
+            .all(|&edge_idx| self.residual_capacity(edge_idx) == 0));
 
-    fn path_capacity(&self, path: &[usize]) -> i32 {
-        let mut min_capacity = i32::MAX;
+        self.truncate_edges(num_edges);
+        self.graph.truncate(num_nodes);
+        let mut potentials = std::mem::take(&mut self.potentials);
+        potentials.truncate(num_nodes);
+        potentials
+    }
 
-        for window in path.windows(2) {
-            let u = window[0];
-            let v = window[1];
+    /// Successive shortest paths from the current flow; `potentials` must keep every
+    /// residual edge non-negative
+    fn augment_shortest_paths(&mut self, source: usize, sink: usize, mut potentials: Vec<i64>) {
+        while let Some(paths) = self.shortest_paths(source, sink, &potentials) {
+            // nodes the search never settled are capped at the sink's distance
+            let sink_distance = paths.distances[sink];
+            for (potential, distance) in potentials.iter_mut().zip(paths.distances) {
+                *potential += distance.min(sink_distance);
+            }
 
-            for &edge_idx in &self.graph[u] {
-                if self.edges[edge_idx].to == v {
-                    let residual = self.residual_capacity(edge_idx);
-                    if residual > 0 {
-                        min_capacity = min_capacity.min(residual);
-                        break;
+            self.augment_admissible(source, sink, &potentials);
+        }
+        self.potentials = potentials;
+    }
+
+    /// Swap the current min-cost flow for one that depends only on the network, so
+    /// flows of equal cost always resolve the same way; the flow value is kept.
+    ///
+    /// The potentials `residual_potentials` finds are the greatest non-positive ones
+    /// that keep every residual edge non-negative, and since optimal flows differ only
+    /// by cycles that cost nothing under them, every optimal flow gives the same. A flow
+    /// of the same value is optimal exactly when each edge of positive reduced cost sits
+    /// at its lower bound and each of negative reduced cost at capacity. So every edge
+    /// starts there, those of zero reduced cost at their lower bound, and blocking
+    /// flows over the zero-cost edges carry what that leaves at each node to where it
+    /// is short.
+    pub fn canonical_flow(&mut self, source: usize, sink: usize) {
+        let flow_value = self.flow_value(source) as i64;
+        let mut potentials = self.residual_potentials();
+
+        let num_nodes = self.graph.len();
+        let mut excess = vec![0_i64; num_nodes];
+        excess[source] += flow_value;
+        excess[sink] -= flow_value;
+        for edge_idx in (0..self.edges.len()).step_by(2) {
+            let (from, to) = (self.edges[edge_idx ^ 1].to, self.edges[edge_idx].to);
+            let edge = &self.edges[edge_idx];
+            let flow = if edge.cost + potentials[from] - potentials[to] < 0 {
+                edge.capacity
+            } else {
+                edge.lower_bound
+            };
+            self.edges[edge_idx].flow = flow;
+            self.edges[edge_idx ^ 1].flow = -flow;
+            excess[from] -= flow as i64;
+            excess[to] += flow as i64;
+        }
+
+        let num_edges = self.edges.len();
+        let super_source = num_nodes;
+        let super_sink = num_nodes + 1;
+        self.graph.push(vec![]);
+        self.graph.push(vec![]);
+        self.add_balancing_edges(&excess, super_source, super_sink);
+        // price the balancing edges so they cost nothing under the potentials too
+        potentials.extend([0, 0]);
+        for edge_idx in (num_edges..self.edges.len()).step_by(2) {
+            let (from, to) = (self.edges[edge_idx ^ 1].to, self.edges[edge_idx].to);
+            self.set_cost(edge_idx, potentials[to] - potentials[from]);
+        }
+
+        self.augment_admissible(super_source, super_sink, &potentials);
+        debug_assert!(self.graph[super_source]
+            .iter()
+            .all(|&edge_idx| self.residual_capacity(edge_idx) == 0));
+
+        self.truncate_edges(num_edges);
+        self.graph.truncate(num_nodes);
+        potentials.truncate(num_nodes);
+        self.potentials = potentials;
+    }
+
+    /// Keep potentials for `reoptimize` after the flow was set from outside, e.g. by
+    /// another backend; the flow must already be min-cost
+    pub fn settle_potentials(&mut self) {
+        self.potentials = self.residual_potentials();
+    }
+
+    /// Augmenting paths and cycles pushed so far; solves add to it, so callers measure
+    /// one run by the difference
+    pub fn augmentations(&self) -> usize {
+        self.augmentations
+    }
+
+    /// Flow leaving `source` on forward edges
+    pub fn flow_value(&self, source: usize) -> i32 {
+        self.graph[source]
+            .iter()
+            .filter(|&&edge_idx| edge_idx % 2 == 0)
+            .map(|&edge_idx| self.edges[edge_idx].flow)
+            .sum()
+    }
+
+    pub fn set_capacity(&mut self, edge_idx: usize, capacity: i32) {
+        self.edges[edge_idx].capacity = capacity;
+    }
+
+    pub fn set_cost(&mut self, edge_idx: usize, cost: i64) {
+        self.edges[edge_idx].cost = cost;
+        self.edges[edge_idx ^ 1].cost = -cost;
+    }
+
+    /// Pull flow off every forward edge now carrying more than its capacity, one unit
+    /// at a time along a flow-carrying source -> sink path through the edge
+    fn drain_excess_flow(&mut self, source: usize, sink: usize) {
+        for edge_idx in (0..self.edges.len()).step_by(2) {
+            while self.edges[edge_idx].flow > self.edges[edge_idx].capacity.max(0) {
+                let from = self.edges[edge_idx ^ 1].to;
+                let to = self.edges[edge_idx].to;
+                let path: Vec<usize> =
+                    match (self.flow_path(source, from), self.flow_path(to, sink)) {
+                        (Some(head), Some(tail)) => head
+                            .into_iter()
+                            .chain(std::iter::once(edge_idx))
+                            .chain(tail)
+                            .collect(),
+                        // flow circulating on a cycle that never touches the source
+                        _ => std::iter::once(edge_idx)
+                            .chain(self.flow_path(to, from).expect("flow is conserved"))
+                            .collect(),
+                    };
+                self.push_flow(&path, -1);
+            }
+        }
+    }
+
+    /// Depth-first path from `start` to `end` over forward edges carrying flow
+    fn flow_path(&self, start: usize, end: usize) -> Option<Vec<usize>> {
+        let mut visited = vec![false; self.graph.len()];
+        let mut stack = vec![(start, 0)];
+        let mut path = vec![];
+        visited[start] = true;
+
+        while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+            if node == end {
+                return Some(path);
+            }
+            let Some(&edge_idx) = self.graph[node].get(*next) else {
+                stack.pop();
+                path.pop();
+                continue;
+            };
+            *next += 1;
+
+            let edge = &self.edges[edge_idx];
+            if edge_idx % 2 == 0 && edge.flow > 0 && !visited[edge.to] {
+                visited[edge.to] = true;
+                stack.push((edge.to, 0));
+                path.push(edge_idx);
+            }
+        }
+
+        None
+    }
+
+    fn path_cost(&self, path: &[usize]) -> i64 {
+        path.iter().map(|&edge_idx| self.edges[edge_idx].cost).sum()
+    }
+
+    /// Cost of the current flow, counted on forward edges only
+    pub fn total_cost(&self) -> i64 {
+        self.edges
+            .iter()
+            .step_by(2)
+            .map(|edge| edge.flow as i64 * edge.cost)
+            .sum()
+    }
+
+    /// Residual edge lying on some shortest path under the current potentials
+    fn is_admissible(&self, from: usize, edge_idx: usize, potentials: &[i64]) -> bool {
+        let edge = &self.edges[edge_idx];
+        self.residual_capacity(edge_idx) > 0
+            && edge.cost + potentials[from] - potentials[edge.to] == 0
+    }
+
+    /// Dinic-style blocking flows restricted to admissible edges; returns the flow pushed
+    fn augment_admissible(&mut self, source: usize, sink: usize, potentials: &[i64]) -> i32 {
+        let num_nodes = self.graph.len();
+        let mut pushed_total = 0;
+
+        loop {
+            // BFS levels keep the search off zero-cost cycles
+            let mut levels = vec![usize::MAX; num_nodes];
+            let mut queue = std::collections::VecDeque::new();
+            levels[source] = 0;
+            queue.push_back(source);
+            while let Some(u) = queue.pop_front() {
+                for &edge_idx in &self.graph[u] {
+                    let to = self.edges[edge_idx].to;
+                    if levels[to] == usize::MAX && self.is_admissible(u, edge_idx, potentials) {
+                        levels[to] = levels[u] + 1;
+                        queue.push_back(to);
                     }
                 }
             }
+            if levels[sink] == usize::MAX {
+                return pushed_total;
+            }
+
+            let mut next_edge = vec![0; num_nodes];
+            loop {
+                let pushed = self.push_admissible(
+                    source,
+                    sink,
+                    i32::MAX,
+                    &levels,
+                    &mut next_edge,
+                    potentials,
+                );
+                if pushed == 0 {
+                    break;
+                }
+                self.augmentations += 1;
+                pushed_total += pushed;
+            }
         }
-        min_capacity
     }
 
-    fn augment_path(&mut self, path: &[usize], flow: i32) {
-        for window in path.windows(2) {
-            let u = window[0];
-            let v = window[1];
+    fn push_admissible(
+        &mut self,
+        u: usize,
+        sink: usize,
+        limit: i32,
+        levels: &[usize],
+        next_edge: &mut [usize],
+        potentials: &[i64],
+    ) -> i32 {
+        if u == sink {
+            return limit;
+        }
 
-            for &edge_idx in &self.graph[u] {
-                if self.edges[edge_idx].to == v && self.residual_capacity(edge_idx) > 0 {
-                    self.edges[edge_idx].flow += flow;
-                    self.edges[edge_idx ^ 1].flow -= flow;
-                    break;
+        while next_edge[u] < self.graph[u].len() {
+            let edge_idx = self.graph[u][next_edge[u]];
+            let to = self.edges[edge_idx].to;
+            if levels[to] == levels[u] + 1 && self.is_admissible(u, edge_idx, potentials) {
+                let pushed = self.push_admissible(
+                    to,
+                    sink,
+                    limit.min(self.residual_capacity(edge_idx)),
+                    levels,
+                    next_edge,
+                    potentials,
+                );
+                if pushed > 0 {
+                    self.edges[edge_idx].flow += pushed;
+                    self.edges[edge_idx ^ 1].flow -= pushed;
+                    return pushed;
                 }
             }
+            next_edge[u] += 1;
         }
+
+        0
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// xorshift so the instances are reproducible without pulling in a rand crate
+    pub(crate) struct Rng(pub(crate) u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        pub(crate) fn range(&mut self, low: i64, high: i64) -> i64 {
+            low + (self.next() % (high - low + 1) as u64) as i64
+        }
+    }
+
+    /// people -> roles -> teams assignment instance with per-edge costs
+    struct Instance {
+        edges: Vec<Vec<Option<i64>>>, // [person][role] cost when qualified
+        role_team: Vec<usize>,
+        team_capacity: Vec<i32>,
+        team_cost: Vec<i64>,
+    }
+
+    fn random_instance(rng: &mut Rng) -> Instance {
+        let num_people = rng.range(1, 5) as usize;
+        let num_roles = rng.range(1, 5) as usize;
+        let num_teams = rng.range(1, 3) as usize;
+        let edges = (0..num_people)
+            .map(|_| {
+                (0..num_roles)
+                    .map(|_| (rng.range(0, 2) > 0).then(|| rng.range(-1_000, 1_000)))
+                    .collect()
+            })
+            .collect();
+        Instance {
+            edges,
+            role_team: (0..num_roles)
+                .map(|_| rng.range(0, num_teams as i64 - 1) as usize)
+                .collect(),
+            team_capacity: (0..num_teams).map(|_| rng.range(1, 3) as i32).collect(),
+            team_cost: (0..num_teams).map(|_| rng.range(-500, 500)).collect(),
+        }
+    }
+
+    fn build_graph(instance: &Instance) -> (FlowGraph, usize, usize) {
+        let num_people = instance.edges.len();
+        let num_roles = instance.role_team.len();
+        let num_teams = instance.team_capacity.len();
+        let source = 0;
+        let sink = 1 + num_people + num_roles + num_teams;
+        let mut graph = FlowGraph::new(sink + 1);
+
+        for (p, row) in instance.edges.iter().enumerate() {
+            graph.add_edge(source, 1 + p, 1, 0);
+            for (r, cost) in row.iter().enumerate() {
+                if let Some(cost) = cost {
+                    graph.add_edge(1 + p, 1 + num_people + r, 1, *cost);
+                }
+            }
+        }
+        for (r, &t) in instance.role_team.iter().enumerate() {
+            graph.add_edge(1 + num_people + r, 1 + num_people + num_roles + t, 1, 0);
+        }
+        for t in 0..num_teams {
+            graph.add_edge(
+                1 + num_people + num_roles + t,
+                sink,
+                instance.team_capacity[t],
+                instance.team_cost[t],
+            );
+        }
+
+        (graph, source, sink)
+    }
+
+    fn solve_with_flow(instance: &Instance) -> (i32, i64) {
+        let (mut graph, source, sink) = build_graph(instance);
+        graph.min_cost_max_flow(source, sink)
+    }
+
+    /// Try every way of giving each person one role (or none); keep the largest
+    /// feasible assignment and, among those, the cheapest
+    fn solve_brute_force(instance: &Instance) -> (i32, i64) {
+        fn search(
+            instance: &Instance,
+            person: usize,
+            used_roles: &mut Vec<bool>,
+            team_load: &mut Vec<i32>,
+            count: i32,
+            cost: i64,
+            best: &mut (i32, i64),
+        ) {
+            if person == instance.edges.len() {
+                if count > best.0 || (count == best.0 && cost < best.1) {
+                    *best = (count, cost);
+                }
+                return;
+            }
+
+            search(
+                instance,
+                person + 1,
+                used_roles,
+                team_load,
+                count,
+                cost,
+                best,
+            );
+
+            for (r, edge_cost) in instance.edges[person].iter().enumerate() {
+                let Some(edge_cost) = edge_cost else {
+                    continue;
+                };
+                let t = instance.role_team[r];
+                if used_roles[r] || team_load[t] >= instance.team_capacity[t] {
+                    continue;
+                }
+                used_roles[r] = true;
+                team_load[t] += 1;
+                search(
+                    instance,
+                    person + 1,
+                    used_roles,
+                    team_load,
+                    count + 1,
+                    cost + edge_cost + instance.team_cost[t],
+                    best,
+                );
+                used_roles[r] = false;
+                team_load[t] -= 1;
+            }
+        }
+
+        let mut best = (0, 0);
+        search(
+            instance,
+            0,
+            &mut vec![false; instance.role_team.len()],
+            &mut vec![0; instance.team_capacity.len()],
+            0,
+            0,
+            &mut best,
+        );
+        best
+    }
+
+    #[test]
+    fn test_min_cost_max_flow_matches_brute_force() {
+        let mut rng = Rng(0x5EED_1234_ABCD_0001);
+        for round in 0..500 {
+            let instance = random_instance(&mut rng);
+            assert_eq!(
+                solve_with_flow(&instance),
+                solve_brute_force(&instance),
+                "instance {} disagrees with brute force",
+                round
+            );
+        }
+    }
+
+    #[test]
+    fn test_reoptimize_matches_fresh_solve() {
+        let mut rng = Rng(0x0DDB_A11C_AFE0_0008);
+        for round in 0..500 {
+            let instance = random_instance(&mut rng);
+            let (mut graph, source, sink) = build_graph(&instance);
+            graph.min_cost_max_flow(source, sink);
+
+            // lock people out, reopen them, and reprice edges as a date change would
+            for edge_idx in (0..graph.edges.len()).step_by(2) {
+                match rng.range(0, 9) {
+                    0 => graph.set_capacity(edge_idx, 0),
+                    1 => graph.set_capacity(edge_idx, graph.edges[edge_idx].capacity + 1),
+                    2 => graph.set_cost(edge_idx, rng.range(-1_000, 1_000)),
+                    _ => {}
+                }
+            }
+
+            let mut fresh = graph.clone();
+            assert_eq!(
+                graph.reoptimize(source, sink),
+                fresh.min_cost_max_flow(source, sink),
+                "instance {} disagrees with a fresh solve",
+                round
+            );
+        }
+    }
+
+    #[test]
+    fn test_canonical_flow_ignores_the_flow_it_starts_from() {
+        let mut rng = Rng(0xCA40_0000_5EED_0008);
+        for round in 0..200 {
+            let instance = random_instance(&mut rng);
+            let (mut graph, source, sink) = build_graph(&instance);
+            // costs this coarse leave many optimal flows
+            for edge_idx in (0..graph.edges.len()).step_by(2) {
+                graph.set_cost(edge_idx, rng.range(-1, 1));
+            }
+            let solved = graph.min_cost_max_flow(source, sink);
+            graph.canonical_flow(source, sink);
+            assert_eq!((graph.flow_value(source), graph.total_cost()), solved);
+
+            // reach another optimal flow by way of a different network
+            let mut detour = graph.clone();
+            let costs: Vec<i64> = detour.edges.iter().map(|edge| edge.cost).collect();
+            for edge_idx in (0..detour.edges.len()).step_by(2) {
+                detour.set_cost(edge_idx, rng.range(-1_000, 1_000));
+            }
+            detour.min_cost_max_flow(source, sink);
+            for edge_idx in (0..detour.edges.len()).step_by(2) {
+                detour.set_cost(edge_idx, costs[edge_idx]);
+            }
+            detour.reoptimize(source, sink);
+            detour.canonical_flow(source, sink);
+
+            let flows = |graph: &FlowGraph| -> Vec<i32> {
+                graph.edges.iter().map(|edge| edge.flow).collect()
+            };
+            assert_eq!(flows(&detour), flows(&graph), "instance {} diverged", round);
+        }
+    }
+
+    #[test]
+    fn test_bounded_flow_matches_max_flow_and_honors_bounds() {
+        // with no bounds to meet, the bounded solve is an ordinary min-cost max flow
+        let mut rng = Rng(0xC1C1_0000_5EED_0021);
+        for round in 0..200 {
+            let instance = random_instance(&mut rng);
+            let (mut graph, source, sink) = build_graph(&instance);
+            let mut bounded = graph.clone();
+            assert_eq!(
+                bounded.max_flow_with_bounds(source, sink),
+                Ok(graph.min_cost_max_flow(source, sink)),
+                "instance {} disagrees with min-cost max-flow",
+                round
+            );
+        }
+
+        // the cheap route a holds one unit, b must carry at least one
+        let (s, a, b, t) = (0, 1, 2, 3);
+        let mut graph = FlowGraph::new(4);
+        graph.add_edge(s, a, 1, 0);
+        let bounded_edge = graph.edges.len();
+        graph.add_bounded_edge(s, b, 1, 2, 0);
+        graph.add_edge(a, t, 2, 1);
+        graph.add_edge(b, t, 2, 10);
+        assert_eq!(graph.max_flow_with_bounds(s, t), Ok((3, 21)));
+        // a circulation carries nothing beyond the bounds unless it pays
+        let mut circulation = graph.clone();
+        circulation.add_edge(t, s, 2, 0);
+        assert_eq!(circulation.min_cost_circulation(), Ok(10));
+
+        // b must pass on three units but at most two reach it; t then gets one too many
+        let mut infeasible = graph.clone();
+        infeasible.add_bounded_edge(b, t, 3, 3, 0);
+        infeasible.add_edge(t, s, 3, 0);
+        assert_eq!(
+            infeasible.min_cost_circulation(),
+            Err(FlowError::Infeasible {
+                unmet: vec![(b, -1), (t, 1)]
+            })
+        );
+        assert!(infeasible.edges.iter().all(|edge| edge.flow == 0));
+
+        // and residual searches never pull flow back below a bound
+        graph.set_lower_bound(bounded_edge, 2);
+        assert_eq!(graph.max_flow_with_bounds(s, t), Ok((3, 21)));
+        assert_eq!(graph.residual_capacity(bounded_edge ^ 1), 0);
+        graph.set_lower_bound(bounded_edge, 3);
+        assert!(graph.max_flow_with_bounds(s, t).is_err());
+    }
+
+    #[test]
+    fn test_negative_incentive_reroutes_through_reverse_edge() {
+        // source -> a, b; a -> x (0), a -> y (-1000); b -> y (0); x, y -> sink
+        // Max flow is 2 and the only way to get both is a->x, b->y (cost 0).
+        let mut graph = FlowGraph::new(6);
+        let (source, a, b, x, y, sink) = (0, 1, 2, 3, 4, 5);
+        graph.add_edge(source, a, 1, 0);
+        graph.add_edge(source, b, 1, 0);
+        graph.add_edge(a, x, 1, 0);
+        graph.add_edge(a, y, 1, -1_000);
+        graph.add_edge(b, y, 1, 0);
+        graph.add_edge(x, sink, 1, 0);
+        graph.add_edge(y, sink, 1, 0);
+
+        assert_eq!(graph.min_cost_max_flow(source, sink), (2, 0));
     }
 }
@@ -0,0 +1,165 @@
+/// Cost of a row/column pair that may not be matched; large enough to never be
+/// chosen while any finite pair is left, small enough that potentials cannot overflow
+pub const FORBIDDEN: i64 = i64::MAX / 4;
+
+/// Minimum-cost assignment of every row to a distinct column (Kuhn-Munkres with
+/// potentials, O(rows² x columns)). Needs at least as many columns as rows, and every
+/// row needs a column other than `FORBIDDEN` for the result to mean anything.
+///
+/// Returns the column matched to each row.
+pub fn min_cost_assignment(costs: &[Vec<i64>]) -> Vec<usize> {
+    let rows = costs.len();
+    let Some(columns) = costs.first().map(Vec::len) else {
+        return vec![];
+    };
+    debug_assert!(rows <= columns, "more rows than columns");
+
+    // 1-based; row 0 and column 0 are the free starting point of each search
+    let mut row_potential = vec![0_i64; rows + 1];
+    let mut column_potential = vec![0_i64; columns + 1];
+    let mut row_of_column = vec![0_usize; columns + 1];
+    let mut previous_column = vec![0_usize; columns + 1];
+
+    for row in 1..=rows {
+        row_of_column[0] = row;
+        let mut column = 0;
+        let mut slack = vec![i64::MAX; columns + 1];
+        let mut visited = vec![false; columns + 1];
+
+        // grow a shortest alternating path until it reaches a free column
+        loop {
+            visited[column] = true;
+            let from_row = row_of_column[column];
+            let mut delta = i64::MAX;
+            let mut next_column = 0;
+            for j in 1..=columns {
+                if visited[j] {
+                    continue;
+                }
+                let reduced =
+                    costs[from_row - 1][j - 1] - row_potential[from_row] - column_potential[j];
+                if reduced < slack[j] {
+                    slack[j] = reduced;
+                    previous_column[j] = column;
+                }
+                if slack[j] < delta {
+                    delta = slack[j];
+                    next_column = j;
+                }
+            }
+            for j in 0..=columns {
+                if visited[j] {
+                    row_potential[row_of_column[j]] += delta;
+                    column_potential[j] -= delta;
+                } else {
+                    slack[j] -= delta;
+                }
+            }
+            column = next_column;
+            if row_of_column[column] == 0 {
+                break;
+            }
+        }
+
+        // flip the path so every row on it takes the next column along
+        while column != 0 {
+            let previous = previous_column[column];
+            row_of_column[column] = row_of_column[previous];
+            column = previous;
+        }
+    }
+
+    let mut column_of_row = vec![0; rows];
+    for (column, &row) in row_of_column.iter().enumerate().skip(1) {
+        if row != 0 {
+            column_of_row[row - 1] = column - 1;
+        }
+    }
+    column_of_row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::flow_graph::tests::Rng;
+    use itertools::Itertools;
+
+    /// Forbidden cells used, then the cost of the rest
+    fn assignment_cost(costs: &[Vec<i64>], columns: &[usize]) -> (usize, i64) {
+        let cells: Vec<i64> = columns
+            .iter()
+            .enumerate()
+            .map(|(row, &column)| costs[row][column])
+            .collect();
+        (
+            cells.iter().filter(|&&cost| cost == FORBIDDEN).count(),
+            cells.iter().filter(|&&cost| cost != FORBIDDEN).sum(),
+        )
+    }
+
+    /// Try every way of giving each row a distinct column; keep the cheapest
+    fn solve_brute_force(costs: &[Vec<i64>]) -> (usize, i64) {
+        fn search(
+            costs: &[Vec<i64>],
+            columns: &mut Vec<usize>,
+            used: &mut Vec<bool>,
+            best: &mut Option<(usize, i64)>,
+        ) {
+            if columns.len() == costs.len() {
+                let cost = assignment_cost(costs, columns);
+                if best.is_none_or(|best| cost < best) {
+                    *best = Some(cost);
+                }
+                return;
+            }
+            for column in 0..used.len() {
+                if used[column] {
+                    continue;
+                }
+                used[column] = true;
+                columns.push(column);
+                search(costs, columns, used, best);
+                columns.pop();
+                used[column] = false;
+            }
+        }
+
+        let mut best = None;
+        let columns = costs.first().map_or(0, Vec::len);
+        search(costs, &mut vec![], &mut vec![false; columns], &mut best);
+        best.unwrap_or((0, 0))
+    }
+
+    #[test]
+    fn test_min_cost_assignment_matches_brute_force() {
+        let mut rng = Rng(0x4E46_A121_0000_0020);
+        for round in 0..500 {
+            let rows = rng.range(1, 5) as usize;
+            let columns = rng.range(rows as i64, 7) as usize;
+            let costs: Vec<Vec<i64>> = (0..rows)
+                .map(|_| {
+                    (0..columns)
+                        .map(|_| match rng.range(0, 3) {
+                            0 => FORBIDDEN,
+                            _ => rng.range(-500, 500),
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let assigned = min_cost_assignment(&costs);
+            assert_eq!(assigned.len(), rows);
+            assert!(
+                assigned.iter().all_unique(),
+                "instance {} reuses a column",
+                round
+            );
+            assert_eq!(
+                assignment_cost(&costs, &assigned),
+                solve_brute_force(&costs),
+                "instance {} disagrees with brute force",
+                round
+            );
+        }
+    }
+}
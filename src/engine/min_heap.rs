@@ -27,33 +27,14 @@ impl<T> MinHeap<T> {
 
 #[derive(Debug)]
 pub enum HeapErr {
-    KeyError(usize, usize),
-    HeapOverflow,
+    KeyError,
     HeapUnderflow,
 }
 
 impl<T: PartialEq + PartialOrd + std::fmt::Debug + Clone> MinHeap<T> {
-    pub fn build(source: Vec<T>) -> Result<Self, HeapErr> {
-        let mut heap = MinHeap { elements: source };
-        let n = heap.heap_size();
-        for i in (0..=n / 2).rev() {
-            heap.min_heapify(i)?;
-        }
-        Ok(heap)
-    }
-
-    pub fn valid_min_heap(&self) -> bool {
-        for i in 0..self.heap_size() {
-            if self.elements[self.parent(i)] > self.elements[i] {
-                return false;
-            }
-        }
-        true
-    }
-
     pub fn min_heapify(&mut self, i: usize) -> Result<(), HeapErr> {
         if i > self.heap_size() {
-            return Err(HeapErr::KeyError(i, self.heap_size()));
+            return Err(HeapErr::KeyError);
         }
         let l = self.left(i);
         let r = self.right(i);
@@ -77,13 +58,14 @@ impl<T: PartialEq + PartialOrd + std::fmt::Debug + Clone> MinHeap<T> {
     }
 
     pub fn insert(&mut self, value: T) -> Result<(), HeapErr> {
-        // this is far less than optimal. See page 175 of Cormen
+        // sift the new element up from the bottom - see page 175 of Cormen
         self.elements.push(value);
-        let n = self.heap_size();
-        for i in (0..=n / 2).rev() {
-            self.min_heapify(i)?;
+        let mut i = self.heap_size() - 1;
+        while i > 0 && self.elements[self.parent(i)] > self.elements[i] {
+            let parent = self.parent(i);
+            self.elements.swap(i, parent);
+            i = parent;
         }
-        assert!(self.valid_min_heap());
         Ok(())
     }
 
@@ -105,3 +87,27 @@ impl<T> Default for MinHeap<T> {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_min_heap(heap: &MinHeap<i32>) -> bool {
+        (0..heap.heap_size()).all(|i| heap.elements[heap.parent(i)] <= heap.elements[i])
+    }
+
+    #[test]
+    fn test_insert_keeps_heap_order() {
+        let mut heap = MinHeap::new();
+        for value in [5, 3, 8, 1, 9, 2, 7, 3, 0, 6] {
+            heap.insert(value).unwrap();
+            assert!(is_min_heap(&heap));
+        }
+
+        let mut drained = vec![];
+        while heap.heap_size() > 0 {
+            drained.push(heap.extract_min().unwrap());
+        }
+        assert_eq!(drained, vec![0, 1, 2, 3, 3, 5, 6, 7, 8, 9]);
+    }
+}
@@ -1,13 +1,33 @@
 pub mod assignment;
 
+pub mod backend;
+
+pub mod cost_policy;
+
 pub mod flow_graph;
 
 pub mod person;
 
+pub mod pairing;
+
+pub mod plan_diff;
+
+pub mod projection;
+
+pub mod rate_rank;
+
+pub mod rules;
+
+pub mod scenario;
+
 pub mod team;
 
+pub mod training;
+
 pub mod builder;
 
 mod djikstra;
 
-mod min_heap;
\ No newline at end of file
+mod hungarian;
+
+mod min_heap;
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::assignment::{
+    AssignmentExclusion, AssignmentLock, AssignmentSolver, FlowAssignment, SolvedFlow,
+};
+use crate::engine::person::Person;
+use crate::engine::team::Team;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PairingKind {
+    /// On the same team, or both left unassigned
+    Together,
+    /// Never on the same team
+    Apart,
+}
+
+/// A rule between two people, e.g. a dual-military couple kept together or a
+/// supervisor and subordinate kept apart
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PairingConstraint {
+    pub first: String,
+    pub second: String,
+    pub kind: PairingKind,
+}
+
+impl PairingConstraint {
+    /// `team_of` maps every assigned person to their team. `Together` holds when both
+    /// are left unassigned, since neither is on a team without the other; the fixes
+    /// `enforce_pairings` tries only ever move one of them onto the other's team, so it
+    /// never benches both to get there.
+    pub fn is_satisfied(&self, team_of: &HashMap<&str, &str>) -> bool {
+        let first = team_of.get(self.first.as_str());
+        let second = team_of.get(self.second.as_str());
+        match self.kind {
+            PairingKind::Together => first == second,
+            PairingKind::Apart => first.is_none() || first != second,
+        }
+    }
+}
+
+impl Display for PairingConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            PairingKind::Together => "together",
+            PairingKind::Apart => "apart",
+        };
+        write!(f, "{} & {} {}", self.first, self.second, kind)
+    }
+}
+
+/// Rules broken by a plan, in the order they were given
+pub fn unsatisfied_pairings<'a>(
+    pairings: &[PairingConstraint],
+    placements: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Vec<PairingConstraint> {
+    let team_of: HashMap<&str, &str> = placements.into_iter().collect();
+    pairings
+        .iter()
+        .filter(|pairing| !pairing.is_satisfied(&team_of))
+        .cloned()
+        .collect()
+}
+
+/// Re-solve `solver` under `exclusions`, then repair broken pairing rules by adding
+/// exclusions until every rule holds or no single move helps. Returns the exclusions
+/// added, which the final flow honors along with `exclusions`; rules still broken show
+/// up in `unsatisfied_pairings`.
+///
+/// A flow network cannot say "same team" or "different teams", so each broken rule is
+/// tried with every one-person fix from `pairing_fixes`, and the fix that leaves the
+/// fewest rules broken, then fills the most positions at the lowest cost, is kept. A
+/// fix the plan already honors would re-solve to the same plan and is skipped, so each
+/// broken rule costs at most two warm `reoptimize` runs.
+pub fn enforce_pairings(
+    solver: &mut AssignmentSolver,
+    people: &[Person],
+    teams: &[Team],
+    analysis_date: chrono::NaiveDate,
+    assignment_locks: &[AssignmentLock],
+    exclusions: &[AssignmentExclusion],
+    pairings: &[PairingConstraint],
+) -> Vec<AssignmentExclusion> {
+    let mut solve = |added: &[AssignmentExclusion]| {
+        let exclusions: Vec<AssignmentExclusion> =
+            exclusions.iter().chain(added).cloned().collect();
+        solver.apply_constraints(people, analysis_date, assignment_locks, &exclusions);
+        let (flow, cost) = solver.reoptimize();
+        let assignments = solver.extract_assignments();
+        let placements: Vec<(String, String)> = placements(&assignments, assignment_locks)
+            .map(|(person, team)| (person.to_string(), team.to_string()))
+            .collect();
+        let broken = unsatisfied_pairings(
+            pairings,
+            placements.iter().map(|(p, t)| (p.as_str(), t.as_str())),
+        );
+        Outcome {
+            flow,
+            cost,
+            broken,
+            placements,
+            solved: solver.solved_flow(),
+        }
+    };
+
+    let mut added: Vec<AssignmentExclusion> = vec![];
+    let mut current = solve(&added);
+    let mut given_up: Vec<PairingConstraint> = vec![];
+
+    while let Some(pairing) = current
+        .broken
+        .iter()
+        .find(|pairing| !given_up.contains(pairing))
+        .cloned()
+    {
+        let team_of: HashMap<&str, &str> = current
+            .placements
+            .iter()
+            .map(|(person, team)| (person.as_str(), team.as_str()))
+            .collect();
+
+        let mut best: Option<(Vec<AssignmentExclusion>, Outcome)> = None;
+        for fix in pairing_fixes(&pairing, &team_of, teams) {
+            let honored = fix.iter().all(|exclusion| {
+                team_of
+                    .get(exclusion.person_name.as_str())
+                    .is_none_or(|team| exclusion.team_name.as_deref() != Some(*team))
+            });
+            if honored {
+                continue;
+            }
+            let candidate: Vec<AssignmentExclusion> = added.iter().cloned().chain(fix).collect();
+            let outcome = solve(&candidate);
+            if best.as_ref().is_none_or(|(_, b)| outcome.is_better_than(b)) {
+                best = Some((candidate, outcome));
+            }
+        }
+
+        match best {
+            Some((candidate, outcome)) if outcome.broken.len() < current.broken.len() => {
+                added = candidate;
+                current = outcome;
+            }
+            _ => given_up.push(pairing),
+        }
+    }
+
+    // leave the solver holding the plan that was kept; re-solving could land on a
+    // different plan of the same cost that breaks a rule this one keeps
+    let exclusions: Vec<AssignmentExclusion> = exclusions.iter().chain(&added).cloned().collect();
+    solver.apply_constraints(people, analysis_date, assignment_locks, &exclusions);
+    solver.restore(current.solved);
+    added
+}
+
+struct Outcome {
+    flow: i32,
+    cost: i64,
+    broken: Vec<PairingConstraint>,
+    /// (person, team) for everyone placed
+    placements: Vec<(String, String)>,
+    solved: SolvedFlow,
+}
+
+impl Outcome {
+    fn is_better_than(&self, other: &Outcome) -> bool {
+        (self.broken.len(), -self.flow, self.cost) < (other.broken.len(), -other.flow, other.cost)
+    }
+}
+
+/// Solver assignments plus locked positions, as (person, team)
+pub fn placements<'a>(
+    assignments: &'a [FlowAssignment],
+    assignment_locks: &'a [AssignmentLock],
+) -> impl Iterator<Item = (&'a str, &'a str)> {
+    let locked = assignment_locks.iter().filter_map(|lock| {
+        let team = lock.team_name.as_deref()?;
+        lock.position.as_ref()?;
+        Some((lock.person_name.as_str(), team))
+    });
+    assignments
+        .iter()
+        .map(|a| (a.person_name.as_str(), a.team.as_str()))
+        .chain(locked)
+}
+
+/// Each way of moving one person that could repair `pairing`: `Apart` keeps either
+/// person off the shared team, `Together` confines either to the other's team
+fn pairing_fixes(
+    pairing: &PairingConstraint,
+    team_of: &HashMap<&str, &str>,
+    teams: &[Team],
+) -> Vec<Vec<AssignmentExclusion>> {
+    let keep_off = |person: &str, team: &str| AssignmentExclusion {
+        person_name: person.to_string(),
+        team_name: Some(team.to_string()),
+        qualification: None,
+    };
+    let confine_to = |person: &str, team: &str| -> Vec<AssignmentExclusion> {
+        teams
+            .iter()
+            .filter(|t| t.name != team)
+            .map(|t| keep_off(person, &t.name))
+            .collect()
+    };
+
+    let first = team_of.get(pairing.first.as_str()).copied();
+    let second = team_of.get(pairing.second.as_str()).copied();
+    match pairing.kind {
+        PairingKind::Apart => match first {
+            Some(team) => vec![
+                vec![keep_off(&pairing.first, team)],
+                vec![keep_off(&pairing.second, team)],
+            ],
+            None => vec![],
+        },
+        PairingKind::Together => {
+            let mut fixes = vec![];
+            if let Some(team) = second {
+                fixes.push(confine_to(&pairing.first, team));
+            }
+            if let Some(team) = first {
+                fixes.push(confine_to(&pairing.second, team));
+            }
+            fixes
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::assignment::tests::{person, team};
+    use crate::engine::cost_policy::CostPolicy;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_together_holds_only_on_one_team_or_off_every_team() {
+        let together = PairingConstraint {
+            first: "Adams".to_string(),
+            second: "Baker".to_string(),
+            kind: PairingKind::Together,
+        };
+        let team_of = |placements: &[(&'static str, &'static str)]| -> HashMap<&str, &str> {
+            placements.iter().copied().collect()
+        };
+
+        assert!(together.is_satisfied(&team_of(&[])));
+        assert!(together.is_satisfied(&team_of(&[("Adams", "Det"), ("Baker", "Det")])));
+        assert!(!together.is_satisfied(&team_of(&[("Adams", "Det")])));
+        assert!(!together.is_satisfied(&team_of(&[("Adams", "Det"), ("Baker", "QA")])));
+    }
+
+    #[test]
+    fn test_pairings_are_repaired_or_reported() {
+        let people = vec![
+            person("Adams", &["PC"]),
+            person("Baker", &["PC"]),
+            person("Clark", &["CDI"]),
+            person("Diaz", &["QAR"]),
+        ];
+        let teams = vec![
+            team("Day Check", Some(1), &["PC", "PC", "CDI"]),
+            team("Night Check", Some(2), &["PC", "QAR"]),
+        ];
+        let pairings = vec![
+            PairingConstraint {
+                first: "Adams".to_string(),
+                second: "Baker".to_string(),
+                kind: PairingKind::Apart,
+            },
+            // Diaz can only ever be on Night Check and Clark only on Day Check
+            PairingConstraint {
+                first: "Clark".to_string(),
+                second: "Diaz".to_string(),
+                kind: PairingKind::Together,
+            },
+        ];
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut solver =
+            AssignmentSolver::new(&people, &teams, date, None, &[], &CostPolicy::default());
+        solver.solve();
+
+        let exclusions = enforce_pairings(&mut solver, &people, &teams, date, &[], &[], &pairings);
+        let assignments = solver.extract_assignments();
+
+        assert_eq!(exclusions.len(), 1);
+        assert_eq!(assignments.len(), 4);
+        assert_eq!(
+            unsatisfied_pairings(&pairings, placements(&assignments, &[])),
+            vec![pairings[1].clone()]
+        );
+    }
+}
@@ -1,16 +1,24 @@
+use crate::engine::rate_rank::RateRank;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Person {
     // you can privatize this later
     pub name: String,
-    pub raterank: String,
+    pub raterank: RateRank,
     pub duty_status: DutyStatus,
-    pub qualifications: HashSet<String>,
+    pub qualifications: BTreeSet<String>,
     pub prd: Option<NaiveDate>,
+    /// Last day each qualification is valid; qualifications not listed never expire
+    #[serde(default)]
+    pub qualification_expirations: BTreeMap<String, NaiveDate>,
+    /// First day each qualification still in training counts, i.e. its expected
+    /// completion date
+    #[serde(default)]
+    pub qualification_completions: BTreeMap<String, NaiveDate>,
 }
 
 impl Person {
@@ -26,6 +34,50 @@ impl Person {
         &self.name
     }
 
+    pub fn expiration(&self, qualification: &str) -> Option<NaiveDate> {
+        self.qualification_expirations.get(qualification).copied()
+    }
+
+    /// Expected completion date of a qualification still in training
+    pub fn completion(&self, qualification: &str) -> Option<NaiveDate> {
+        self.qualification_completions.get(qualification).copied()
+    }
+
+    /// Holds the qualification, or will have finished training for it, and it is still
+    /// valid on `date`
+    pub fn is_qualified(&self, qualification: &str, date: NaiveDate) -> bool {
+        self.qualifications.contains(qualification)
+            && self
+                .expiration(qualification)
+                .is_none_or(|expiry| expiry >= date)
+            && self
+                .completion(qualification)
+                .is_none_or(|done| done <= date)
+    }
+
+    /// Count `qualification` from `completion_date` on; a qualification already held
+    /// outright is left alone
+    pub fn add_pending_qualification(&mut self, qualification: &str, completion_date: NaiveDate) {
+        if self.qualifications.contains(qualification) && self.completion(qualification).is_none() {
+            return;
+        }
+        self.qualifications.insert(qualification.to_string());
+        let done = self
+            .qualification_completions
+            .entry(qualification.to_string())
+            .or_insert(completion_date);
+        *done = (*done).min(completion_date);
+    }
+
+    /// The person as the ASM export has them, without qualifications still in training
+    pub fn without_pending_qualifications(&self) -> Person {
+        let mut person = self.clone();
+        for qualification in std::mem::take(&mut person.qualification_completions).keys() {
+            person.qualifications.remove(qualification);
+        }
+        person
+    }
+
     // pub fn get_raterank(&self) -> &str {
     //     &self.raterank
     // }
@@ -45,6 +97,33 @@ impl Display for Person {
     }
 }
 
+/// A qualification someone is expected to finish training for by a date
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PendingQualification {
+    pub person_name: String,
+    pub qualification: String,
+    pub completion_date: NaiveDate,
+}
+
+impl Display for PendingQualification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} completes {} on {}",
+            self.person_name, self.qualification, self.completion_date
+        )
+    }
+}
+
+/// Add each pending qualification to the person it names
+pub fn apply_pending_qualifications(people: &mut [Person], pending: &[PendingQualification]) {
+    for pending in pending {
+        if let Some(person) = people.iter_mut().find(|p| p.name == pending.person_name) {
+            person.add_pending_qualification(&pending.qualification, pending.completion_date);
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DutyStatus {
     Tar,
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::assignment::AssignmentPlan;
+
+/// Where a person sits in a plan. Instance numbers are left out: instances of the
+/// same qualification on a team are interchangeable, so trading them is not a move.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PlanSlot {
+    pub team_name: String,
+    pub qualification: String,
+}
+
+impl Display for PlanSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.team_name, self.qualification)
+    }
+}
+
+/// Where everyone sat in an earlier plan, by person name. The solver favors keeping
+/// people in these slots.
+pub type PlanBaseline = BTreeMap<String, PlanSlot>;
+
+/// The slot each assigned person holds in `plan`
+pub fn plan_baseline(plan: &AssignmentPlan) -> PlanBaseline {
+    plan.assignments
+        .iter()
+        .map(|a| {
+            (
+                a.person.name.clone(),
+                PlanSlot {
+                    team_name: a.team_name.clone(),
+                    qualification: a.position.qualification.clone(),
+                },
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonMove {
+    pub person_name: String,
+    /// `None` when the person was on the bench
+    pub from: Option<PlanSlot>,
+    /// `None` when the person ends up on the bench
+    pub to: Option<PlanSlot>,
+}
+
+/// What changes between two plans for the same roster
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlanDiff {
+    /// Sorted by person name
+    pub moves: Vec<PersonMove>,
+}
+
+impl PlanDiff {
+    pub fn between(baseline: &AssignmentPlan, other: &AssignmentPlan) -> Self {
+        Self::from_baseline(&plan_baseline(baseline), other)
+    }
+
+    /// What changes from a stored baseline to `other`
+    pub fn from_baseline(baseline: &PlanBaseline, other: &AssignmentPlan) -> Self {
+        let mut after = plan_baseline(other);
+
+        let mut moves = vec![];
+        for (person_name, from) in baseline.clone() {
+            let to = after.remove(&person_name);
+            if to.as_ref() != Some(&from) {
+                moves.push(PersonMove {
+                    person_name,
+                    from: Some(from),
+                    to,
+                });
+            }
+        }
+        for (person_name, to) in after {
+            moves.push(PersonMove {
+                person_name,
+                from: None,
+                to: Some(to),
+            });
+        }
+        moves.sort_by(|a, b| a.person_name.cmp(&b.person_name));
+
+        PlanDiff { moves }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::assignment::Assignment;
+    use crate::engine::person::{DutyStatus, Person};
+    use crate::engine::team::Position;
+    use std::rc::Rc;
+
+    fn assignment(name: &str, team: &str, qual: &str, instance: u32) -> Assignment {
+        Assignment {
+            person: Rc::new(Person {
+                name: name.to_string(),
+                raterank: "AM2".into(),
+                duty_status: DutyStatus::Tar,
+                qualifications: Default::default(),
+                prd: None,
+                qualification_expirations: Default::default(),
+                qualification_completions: Default::default(),
+            }),
+            team_name: team.to_string(),
+            position: Position {
+                qualification: qual.to_string(),
+                instance,
+                mandatory: false,
+                ..Default::default()
+            },
+            score: 0,
+            cost: Default::default(),
+            manual_override: false,
+        }
+    }
+
+    fn plan(assignments: Vec<Assignment>) -> AssignmentPlan {
+        AssignmentPlan {
+            assignments,
+            unfilled_positions: vec![],
+            unfilled_mandatory_positions: vec![],
+            unassigned_people: Rc::new(vec![]),
+            unsatisfied_pairings: vec![],
+            composition_violations: vec![],
+        }
+    }
+
+    #[test]
+    fn test_diff_ignores_instance_swaps() {
+        let baseline = plan(vec![
+            assignment("Smith", "Day Check", "SFF", 1),
+            assignment("Jones", "Day Check", "SFF", 2),
+            assignment("Lee", "Det", "CDI", 1),
+        ]);
+        let other = plan(vec![
+            assignment("Smith", "Day Check", "SFF", 2),
+            assignment("Jones", "Day Check", "SFF", 1),
+            assignment("Lee", "Det", "QAR", 1),
+            assignment("Brown", "Det", "CDI", 1),
+        ]);
+
+        let diff = PlanDiff::between(&baseline, &other);
+        let moves: Vec<(&str, Option<String>, Option<String>)> = diff
+            .moves
+            .iter()
+            .map(|m| {
+                (
+                    m.person_name.as_str(),
+                    m.from.as_ref().map(|s| s.to_string()),
+                    m.to.as_ref().map(|s| s.to_string()),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            moves,
+            vec![
+                ("Brown", None, Some("Det CDI".to_string())),
+                (
+                    "Lee",
+                    Some("Det CDI".to_string()),
+                    Some("Det QAR".to_string())
+                ),
+            ]
+        );
+    }
+}
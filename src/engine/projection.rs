@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use chrono::{Months, NaiveDate};
+
+use crate::engine::assignment::{AssignmentExclusion, AssignmentLock};
+use crate::engine::builder::{build_assignment_plan, WarmStartSolver};
+use crate::engine::pairing::PairingConstraint;
+use crate::engine::person::DutyStatus;
+use crate::engine::plan_diff::plan_baseline;
+
+/// How far ahead the Results projection looks
+pub const PROJECTION_MONTHS: u32 = 12;
+
+/// Manning on one date of a projection
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectionPoint {
+    pub date: NaiveDate,
+    pub positions_filled: usize,
+    pub total_positions: usize,
+    pub selres_used: usize,
+    /// Empty positions by (team, qualification)
+    pub unfilled: BTreeMap<(String, String), usize>,
+    /// People whose PRD passed since the previous point
+    pub losses: Vec<String>,
+}
+
+impl ProjectionPoint {
+    /// Share of positions filled, 0 to 100
+    pub fn fill_rate(&self) -> f64 {
+        if self.total_positions == 0 {
+            return 100.0;
+        }
+        self.positions_filled as f64 / self.total_positions as f64 * 100.0
+    }
+}
+
+/// Run the assignment pipeline on `start` and on the same day of each of the next
+/// `months` months. People drop out once their PRD passes, and locks held by them
+/// are released before that month is solved.
+///
+/// Continuity carries through the projection: the first month stays close to the
+/// baseline `solver` was given, each month's plan is the next month's baseline, and
+/// `solver` is left with the last month's plan as its baseline. People only move when
+/// someone leaves or moving gains more than `continuity_bonus`. Each step repairs the
+/// previous month's flow, so a year costs little more than one full solve.
+pub fn project_manning(
+    solver: &mut WarmStartSolver,
+    start: NaiveDate,
+    months: u32,
+    assignment_locks: &[AssignmentLock],
+    exclusions: &[AssignmentExclusion],
+    pairings: &[PairingConstraint],
+) -> Result<Vec<ProjectionPoint>> {
+    let mut points: Vec<ProjectionPoint> = vec![];
+
+    for month in 0..=months {
+        let Some(date) = start.checked_add_months(Months::new(month)) else {
+            break;
+        };
+        // a lock cannot hold someone who has left
+        let departed: Vec<&str> = solver
+            .people()
+            .iter()
+            .filter(|p| p.prd.is_some_and(|prd| prd <= date))
+            .map(|p| p.name.as_str())
+            .collect();
+        let locks: Vec<AssignmentLock> = assignment_locks
+            .iter()
+            .filter(|lock| !departed.contains(&lock.person_name.as_str()))
+            .cloned()
+            .collect();
+        let result = solver.solve(date, Some(locks), exclusions, pairings)?;
+        let plan = build_assignment_plan(&result)?;
+        solver.set_baseline(plan_baseline(&plan));
+
+        let mut unfilled: BTreeMap<(String, String), usize> = BTreeMap::new();
+        for gap in plan
+            .unfilled_mandatory_positions
+            .iter()
+            .chain(&plan.unfilled_positions)
+        {
+            *unfilled
+                .entry((gap.team_name.clone(), gap.position.qualification.clone()))
+                .or_default() += 1;
+        }
+
+        let losses = match points.last() {
+            Some(previous) => result
+                .departed_people
+                .iter()
+                .filter(|p| p.prd.is_some_and(|prd| prd > previous.date))
+                .map(|p| p.name.clone())
+                .collect(),
+            None => vec![],
+        };
+
+        points.push(ProjectionPoint {
+            date,
+            positions_filled: plan.assignments.len(),
+            total_positions: plan.assignments.len() + plan.total_unfilled(),
+            selres_used: plan
+                .assignments
+                .iter()
+                .filter(|a| a.person.duty_status == DutyStatus::Selres)
+                .count(),
+            unfilled,
+            losses,
+        });
+    }
+
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::assignment::tests::{person, team};
+    use crate::engine::cost_policy::CostPolicy;
+
+    #[test]
+    fn test_projection_backfills_losses_with_selres() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let people = vec![
+            person("Adams", &["PC"]).with_prd(NaiveDate::from_ymd_opt(2025, 4, 1)),
+            person("Baker", &["PC"]).with_prd(NaiveDate::from_ymd_opt(2026, 6, 1)),
+            person("Clark", &["PC"]).with_status(DutyStatus::Selres),
+        ];
+        let teams = vec![team("Det", Some(1), &["PC", "PC"]).numbered()];
+        let lock = AssignmentLock {
+            person_name: "Adams".to_string(),
+            team_name: Some("Det".to_string()),
+            position: Some(teams[0].required_positions[0].clone()),
+        };
+        let mut solver = WarmStartSolver::new(people, teams, &CostPolicy::default());
+
+        let points =
+            project_manning(&mut solver, start, PROJECTION_MONTHS, &[lock], &[], &[]).unwrap();
+
+        assert_eq!(points.len(), 13);
+        assert!(points.iter().all(|p| p.positions_filled == 2));
+        assert_eq!(points[2].selres_used, 0);
+        // Adams leaves in April and Clark takes the freed position
+        assert_eq!(points[3].losses, vec!["Adams".to_string()]);
+        assert!(points[3..].iter().all(|p| p.selres_used == 1));
+        assert_eq!(points.iter().filter(|p| !p.losses.is_empty()).count(), 1);
+    }
+}
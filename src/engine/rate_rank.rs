@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// General rates that belong to aviation, including the E-9 only AF and AV. Riggers
+/// (PR) work aviation gear but are left out, so they keep the non-aviation penalty the
+/// cost model has always charged them.
+const AVIATION_RATES: [&str; 22] = [
+    "AB", "ABE", "ABF", "ABH", "AC", "AD", "AE", "AF", "AG", "AM", "AME", "AO", "AS", "AT", "AV",
+    "AW", "AWF", "AWO", "AWR", "AWS", "AWV", "AZ",
+];
+
+/// Rates that only exist at E-9, where the ratings below them merge
+const MASTER_CHIEF_RATES: [&str; 2] = ["AF", "AV"];
+
+/// Paygrade suffixes, longest first so AMCS is not read as AMC and AMCMD not as AMCM.
+/// E-1 to E-3 strikers carry their apprenticeship: airman, seaman, fireman,
+/// constructionman and hospitalman.
+const PAYGRADE_SUFFIXES: [(&str, u8); 22] = [
+    ("CMD", 9),
+    ("CM", 9),
+    ("CS", 8),
+    ("AN", 3),
+    ("AA", 2),
+    ("AR", 1),
+    ("SN", 3),
+    ("SA", 2),
+    ("SR", 1),
+    ("FN", 3),
+    ("FA", 2),
+    ("FR", 1),
+    ("CN", 3),
+    ("CA", 2),
+    ("CR", 1),
+    ("HN", 3),
+    ("HA", 2),
+    ("HR", 1),
+    ("C", 7),
+    ("1", 6),
+    ("2", 5),
+    ("3", 4),
+];
+
+/// A rate/rank such as AM2 or AMCS, parsed once when the roster is read. Serializes as
+/// the text it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct RateRank {
+    /// As written in the ASM report
+    pub text: String,
+    /// General rate, e.g. AM for AMCS; empty for undesignated strikers and officers
+    pub rate: String,
+    /// Enlisted paygrade, e.g. 5 for E-5; `None` for officers and anything unrecognized
+    pub paygrade: Option<u8>,
+    /// E-7 and above
+    pub is_chief: bool,
+    /// E-8
+    pub is_senior: bool,
+    /// E-9
+    pub is_master: bool,
+    /// Command master chief, written with a CMD suffix or as CMDCM
+    pub is_command: bool,
+}
+
+impl RateRank {
+    pub fn parse(text: &str) -> Self {
+        let trimmed = text.trim();
+        let (rate, paygrade, is_command) = match split(trimmed) {
+            Some((rate, suffix, paygrade)) => (rate, Some(paygrade), suffix == "CMD"),
+            None => ("", None, false),
+        };
+        let is_command = is_command || trimmed == "CMDCM";
+        let rate = if trimmed == "CMDCM" { "" } else { rate };
+
+        RateRank {
+            text: text.to_string(),
+            rate: rate.to_string(),
+            paygrade,
+            is_chief: paygrade.is_some_and(|p| p >= 7),
+            is_senior: paygrade == Some(8),
+            is_master: paygrade == Some(9),
+            is_command,
+        }
+    }
+
+    /// One of the AW aircrew rates
+    pub fn is_aw(&self) -> bool {
+        self.rate.starts_with("AW")
+    }
+
+    /// An aviation rate, or an undesignated airman
+    pub fn is_aviation(&self) -> bool {
+        if self.rate.is_empty() {
+            return matches!(self.text.trim(), "AN" | "AA" | "AR");
+        }
+        AVIATION_RATES.contains(&self.rate.as_str())
+    }
+}
+
+/// (rate, suffix, paygrade) for an enlisted rate/rank
+fn split(text: &str) -> Option<(&str, &str, u8)> {
+    // warrant officers end in a digit too
+    if text.starts_with("CWO") || text.starts_with("WO") {
+        return None;
+    }
+    PAYGRADE_SUFFIXES.iter().find_map(|&(suffix, paygrade)| {
+        let rate = text.strip_suffix(suffix)?;
+        let valid = rate.chars().all(|c| c.is_ascii_uppercase())
+            && (paygrade == 9 || !MASTER_CHIEF_RATES.contains(&rate));
+        valid.then_some((rate, suffix, paygrade))
+    })
+}
+
+impl From<&str> for RateRank {
+    fn from(text: &str) -> Self {
+        RateRank::parse(text)
+    }
+}
+
+impl From<String> for RateRank {
+    fn from(text: String) -> Self {
+        RateRank::parse(&text)
+    }
+}
+
+impl From<RateRank> for String {
+    fn from(rate_rank: RateRank) -> Self {
+        rate_rank.text
+    }
+}
+
+impl Display for RateRank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aviation_rate_ranks() {
+        // (text, rate, paygrade); AF and AV only exist at E-9
+        let valid = [
+            ("ABHAN", "ABH", 3),
+            ("ABEAA", "ABE", 2),
+            ("ABF3", "ABF", 4),
+            ("AB1", "AB", 6),
+            ("ACC", "AC", 7),
+            ("ADAR", "AD", 1),
+            ("AE2", "AE", 5),
+            ("AGCS", "AG", 8),
+            ("AM2", "AM", 5),
+            ("AMECS", "AME", 8),
+            ("AOCM", "AO", 9),
+            ("AS1", "AS", 6),
+            ("ATC", "AT", 7),
+            ("AWF2", "AWF", 5),
+            ("AWOCS", "AWO", 8),
+            ("AWR1", "AWR", 6),
+            ("AWS3", "AWS", 4),
+            ("AWVAN", "AWV", 3),
+            ("AZC", "AZ", 7),
+            ("AFCM", "AF", 9),
+            ("AVCM", "AV", 9),
+        ];
+        for (text, rate, paygrade) in valid {
+            let parsed = RateRank::parse(text);
+            assert_eq!(parsed.rate, rate, "{}", text);
+            assert_eq!(parsed.paygrade, Some(paygrade), "{}", text);
+            assert_eq!(parsed.is_chief, paygrade >= 7, "{}", text);
+            assert_eq!(parsed.is_senior, paygrade == 8, "{}", text);
+            assert_eq!(parsed.is_master, paygrade == 9, "{}", text);
+            assert!(!parsed.is_command, "{}", text);
+            assert!(parsed.is_aviation(), "{}", text);
+            assert_eq!(parsed.is_aw(), rate.starts_with("AW"), "{}", text);
+        }
+
+        let invalid = [
+            "AFCS", "AFC", "AF1", "AF2", "AFAN", "AVCS", "AVC", "AV1", "AV3", "AVAR", "AM0", "am2",
+        ];
+        for text in invalid {
+            let parsed = RateRank::parse(text);
+            assert_eq!(parsed.rate, "", "{}", text);
+            assert_eq!(parsed.paygrade, None, "{}", text);
+            assert!(!parsed.is_chief, "{}", text);
+            assert!(!parsed.is_aviation(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_other_rate_ranks() {
+        // (text, rate, paygrade, is_command, is_aviation)
+        let cases = [
+            ("AN", "", Some(3), false, true),
+            ("AA", "", Some(2), false, true),
+            ("SN", "", Some(3), false, false),
+            ("LSSN", "LS", Some(3), false, false),
+            ("LS2", "LS", Some(5), false, false),
+            ("LSCS", "LS", Some(8), false, false),
+            ("YN1", "YN", Some(6), false, false),
+            ("HM3", "HM", Some(4), false, false),
+            ("PR3", "PR", Some(4), false, false),
+            ("AMCMD", "AM", Some(9), true, true),
+            ("CMDCM", "", Some(9), true, false),
+            (" AM2 ", "AM", Some(5), false, true),
+            ("CWO3", "", None, false, false),
+            ("LT", "", None, false, false),
+            ("LCDR", "", None, false, false),
+            ("", "", None, false, false),
+        ];
+        for (text, rate, paygrade, is_command, is_aviation) in cases {
+            let parsed = RateRank::parse(text);
+            assert_eq!(parsed.rate, rate, "{:?}", text);
+            assert_eq!(parsed.paygrade, paygrade, "{:?}", text);
+            assert_eq!(parsed.is_command, is_command, "{:?}", text);
+            assert_eq!(parsed.is_aviation(), is_aviation, "{:?}", text);
+            assert_eq!(parsed.to_string(), text);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+
+use crate::engine::person::Person;
+use crate::engine::team::{CompositionViolation, Team};
+
+/// Team composition rules broken by a plan, in team order
+pub fn composition_violations<'a>(
+    teams: &[Team],
+    people: &[Person],
+    placements: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Vec<CompositionViolation> {
+    let people_by_name: HashMap<&str, &Person> =
+        people.iter().map(|p| (p.name.as_str(), p)).collect();
+    let mut members: HashMap<&str, Vec<&Person>> = HashMap::new();
+    for (person_name, team_name) in placements {
+        if let Some(person) = people_by_name.get(person_name) {
+            members.entry(team_name).or_default().push(person);
+        }
+    }
+
+    teams
+        .iter()
+        .flat_map(|team| {
+            let team_members = members.get(team.name.as_str()).map_or(&[][..], |m| m);
+            team.composition.violations(&team.name, team_members)
+        })
+        .collect()
+}
@@ -0,0 +1,369 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::assignment::{AssignmentExclusion, AssignmentLock, AssignmentPlan};
+use crate::engine::builder::{AssignmentResult, WarmStartSolver};
+use crate::engine::pairing::PairingConstraint;
+use crate::engine::person::Person;
+use crate::engine::plan_diff::PlanDiff;
+use crate::engine::team::{Position, Team};
+
+/// One hypothetical change to the roster or the requirements. Edits only ever touch
+/// copies; the real data stays as loaded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScenarioEdit {
+    /// Take someone off the roster
+    RemovePerson { person_name: String },
+    /// Add a notional person, e.g. an inbound E-5 with the quals they bring
+    AddPerson { person: Person },
+    /// Require `count` of a qualification on a team instead of what Requirements says
+    SetPositionCount {
+        team_name: String,
+        qualification: String,
+        count: u32,
+    },
+    /// Move someone's PRD; `None` keeps them past every analysis date
+    SetPrd {
+        person_name: String,
+        prd: Option<NaiveDate>,
+    },
+}
+
+impl Display for ScenarioEdit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScenarioEdit::RemovePerson { person_name } => write!(f, "Remove {}", person_name),
+            ScenarioEdit::AddPerson { person } => {
+                let quals = person
+                    .qualifications
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "Add {} ({}) with {}",
+                    person.name, person.raterank, quals
+                )
+            }
+            ScenarioEdit::SetPositionCount {
+                team_name,
+                qualification,
+                count,
+            } => write!(f, "{} needs {} {}", team_name, count, qualification),
+            ScenarioEdit::SetPrd { person_name, prd } => match prd {
+                Some(prd) => write!(f, "{} PRD moves to {}", person_name, prd),
+                None => write!(f, "{} has no PRD", person_name),
+            },
+        }
+    }
+}
+
+/// Apply `edits` in order; an edit naming someone or a team that is not there is an
+/// error rather than a silent no-op
+pub fn apply_edits(
+    edits: &[ScenarioEdit],
+    people: &mut Vec<Person>,
+    teams: &mut [Team],
+) -> Result<()> {
+    for edit in edits {
+        match edit {
+            ScenarioEdit::RemovePerson { person_name } => {
+                let before = people.len();
+                people.retain(|p| p.name != *person_name);
+                if people.len() == before {
+                    bail!("Cannot remove {}: not on the roster", person_name);
+                }
+            }
+            ScenarioEdit::AddPerson { person } => {
+                if people.iter().any(|p| p.name == person.name) {
+                    bail!("Cannot add {}: already on the roster", person.name);
+                }
+                people.push(person.clone());
+            }
+            ScenarioEdit::SetPositionCount {
+                team_name,
+                qualification,
+                count,
+            } => {
+                let team = teams
+                    .iter_mut()
+                    .find(|t| t.name == *team_name)
+                    .with_context(|| format!("Cannot resize {}: no such team", team_name))?;
+                set_position_count(team, qualification, *count);
+            }
+            ScenarioEdit::SetPrd { person_name, prd } => {
+                let person = people
+                    .iter_mut()
+                    .find(|p| p.name == *person_name)
+                    .with_context(|| {
+                        format!("Cannot move the PRD of {}: not on the roster", person_name)
+                    })?;
+                person.prd = *prd;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Keep the lowest-numbered instances when shrinking; new instances copy the limits
+/// of the highest existing one
+fn set_position_count(team: &mut Team, qualification: &str, count: u32) {
+    let template = team
+        .required_positions
+        .iter()
+        .filter(|p| p.qualification == qualification)
+        .max_by_key(|p| p.instance)
+        .cloned()
+        .unwrap_or_else(|| Position {
+            qualification: qualification.to_string(),
+            instance: 0,
+            ..Default::default()
+        });
+
+    team.required_positions
+        .retain(|p| p.qualification != qualification || p.instance <= count);
+    for instance in template.instance + 1..=count {
+        team.required_positions.push(Position {
+            instance,
+            ..template.clone()
+        });
+    }
+}
+
+/// Re-run the assignment pipeline on copies of the people and teams in `base` with
+/// `edits` applied, on the same solve path and with the same backend, baseline, locks,
+/// exclusions, pairings and weights as `base`, so no edits means no moves. Locks,
+/// exclusions and pairings naming someone the scenario removes are dropped, as are
+/// locks on positions it removes.
+pub fn run_scenario(base: &AssignmentResult, edits: &[ScenarioEdit]) -> Result<AssignmentResult> {
+    let mut people: Vec<Person> = base
+        .people
+        .iter()
+        .chain(base.departed_people.iter())
+        .cloned()
+        .collect();
+    let mut teams = base.teams.as_ref().clone();
+    apply_edits(edits, &mut people, &mut teams)?;
+
+    let on_roster: BTreeSet<&str> = people.iter().map(|p| p.name.as_str()).collect();
+    let locks: Vec<AssignmentLock> = base
+        .flow_assignments
+        .iter()
+        .filter(|a| a.manual_override && on_roster.contains(a.person_name.as_str()))
+        .filter(|a| {
+            teams
+                .iter()
+                .any(|t| t.name == a.team && t.required_positions.contains(&a.position))
+        })
+        .map(|a| AssignmentLock {
+            person_name: a.person_name.clone(),
+            team_name: Some(a.team.clone()),
+            position: Some(a.position.clone()),
+        })
+        .collect();
+    let exclusions: Vec<AssignmentExclusion> = base
+        .exclusions
+        .iter()
+        .filter(|e| on_roster.contains(e.person_name.as_str()))
+        .cloned()
+        .collect();
+    let pairings: Vec<PairingConstraint> = base
+        .pairings
+        .iter()
+        .filter(|p| on_roster.contains(p.first.as_str()) && on_roster.contains(p.second.as_str()))
+        .cloned()
+        .collect();
+
+    let mut solver = WarmStartSolver::new(people, teams, &base.cost_policy);
+    solver.set_backend(base.backend, false);
+    solver.set_baseline(base.baseline.clone());
+    solver.solve(base.analysis_date, Some(locks), &exclusions, &pairings)
+}
+
+/// How a scenario's plan differs from the plan it started from
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScenarioComparison {
+    /// Who changes team or qualification, joins or leaves the plan
+    pub moves: PlanDiff,
+    /// Role ids filled in the scenario but empty, or missing, in the baseline plan
+    pub gained_positions: Vec<String>,
+    /// Role ids filled in the baseline plan but empty, or missing, in the scenario
+    pub lost_positions: Vec<String>,
+    pub fill_rate_before: f64,
+    pub fill_rate_after: f64,
+}
+
+impl ScenarioComparison {
+    pub fn between(baseline: &AssignmentPlan, scenario: &AssignmentPlan) -> Self {
+        let filled = |plan: &AssignmentPlan| -> BTreeSet<String> {
+            plan.assignments.iter().map(|a| a.role_id()).collect()
+        };
+        let before = filled(baseline);
+        let after = filled(scenario);
+
+        ScenarioComparison {
+            moves: PlanDiff::between(baseline, scenario),
+            gained_positions: after.difference(&before).cloned().collect(),
+            lost_positions: before.difference(&after).cloned().collect(),
+            fill_rate_before: baseline.fill_rate(),
+            fill_rate_after: scenario.fill_rate(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::assignment::tests::person;
+    use crate::engine::backend::BackendKind;
+    use crate::engine::builder::build_assignment_plan;
+    use crate::engine::cost_policy::CostPolicy;
+    use crate::engine::pairing::PairingKind;
+    use crate::engine::plan_diff::{PlanBaseline, PlanSlot};
+    use crate::engine::team::CompositionRules;
+
+    #[test]
+    fn test_scenario_reports_moves_and_positions() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let teams = vec![Team {
+            name: "Det".to_string(),
+            priority: Some(1),
+            required_positions: vec![Position {
+                qualification: "CDI".to_string(),
+                instance: 1,
+                ..Default::default()
+            }],
+            composition: CompositionRules::default(),
+        }];
+        let base = WarmStartSolver::new(
+            vec![person("Adams", &["CDI"])],
+            teams,
+            &CostPolicy::default(),
+        )
+        .solve(date, None, &[], &[])
+        .unwrap();
+        let edits = vec![
+            ScenarioEdit::RemovePerson {
+                person_name: "Adams".to_string(),
+            },
+            ScenarioEdit::AddPerson {
+                person: person("Baker", &["CDI"]),
+            },
+            ScenarioEdit::SetPositionCount {
+                team_name: "Det".to_string(),
+                qualification: "CDI".to_string(),
+                count: 2,
+            },
+        ];
+        let scenario = run_scenario(&base, &edits).unwrap();
+
+        // the real data is untouched
+        assert_eq!(base.people.len(), 1);
+        assert_eq!(base.teams[0].required_positions.len(), 1);
+
+        let comparison = ScenarioComparison::between(
+            &build_assignment_plan(&base).unwrap(),
+            &build_assignment_plan(&scenario).unwrap(),
+        );
+        let movers: Vec<&str> = comparison
+            .moves
+            .moves
+            .iter()
+            .map(|m| m.person_name.as_str())
+            .collect();
+        assert_eq!(movers, vec!["Adams", "Baker"]);
+        assert!(comparison.gained_positions.is_empty());
+        assert!(comparison.lost_positions.is_empty());
+        assert_eq!(comparison.fill_rate_before, 100.0);
+        assert_eq!(comparison.fill_rate_after, 50.0);
+
+        let unknown = ScenarioEdit::RemovePerson {
+            person_name: "Nobody".to_string(),
+        };
+        assert!(run_scenario(&base, &[unknown]).is_err());
+    }
+
+    #[test]
+    fn test_scenario_without_edits_moves_nobody() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let cdi = |instance| Position {
+            qualification: "CDI".to_string(),
+            instance,
+            ..Default::default()
+        };
+        let team = |name: &str, priority, positions| Team {
+            name: name.to_string(),
+            priority: Some(priority),
+            required_positions: positions,
+            composition: CompositionRules::default(),
+        };
+        let people = ["Adams", "Baker", "Carter", "Davis"]
+            .map(|name| person(name, &["CDI"]))
+            .to_vec();
+        let teams = vec![
+            team("Alpha", 1, vec![cdi(1)]),
+            team("Det", 2, vec![cdi(1), cdi(2)]),
+            team("Bravo", 3, vec![cdi(1)]),
+        ];
+
+        // everyone is interchangeable, so the lock, the exclusion and the pairing leave
+        // room for three and the baseline picks Adams over Davis for Det; the scenario
+        // must solve on the same backend too
+        let mut solver = WarmStartSolver::new(people, teams, &CostPolicy::default());
+        solver.set_backend(BackendKind::Hungarian, false);
+        solver.set_baseline(PlanBaseline::from([(
+            "Adams".to_string(),
+            PlanSlot {
+                team_name: "Det".to_string(),
+                qualification: "CDI".to_string(),
+            },
+        )]));
+        let lock = AssignmentLock {
+            person_name: "Baker".to_string(),
+            team_name: Some("Alpha".to_string()),
+            position: Some(cdi(1)),
+        };
+        let exclusion = AssignmentExclusion {
+            person_name: "Carter".to_string(),
+            team_name: Some("Det".to_string()),
+            qualification: None,
+        };
+        let pairing = PairingConstraint {
+            first: "Adams".to_string(),
+            second: "Davis".to_string(),
+            kind: PairingKind::Apart,
+        };
+        let base = solver
+            .solve(date, Some(vec![lock]), &[exclusion], &[pairing])
+            .unwrap();
+        let plan = build_assignment_plan(&base).unwrap();
+        let slots: Vec<(&str, &str)> = plan
+            .assignments
+            .iter()
+            .map(|a| (a.person.name.as_str(), a.team_name.as_str()))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![("Adams", "Det"), ("Carter", "Bravo"), ("Baker", "Alpha")]
+        );
+
+        let scenario = run_scenario(&base, &[]).unwrap();
+        assert_eq!(scenario.backend, BackendKind::Hungarian);
+
+        let comparison =
+            ScenarioComparison::between(&plan, &build_assignment_plan(&scenario).unwrap());
+        assert_eq!(
+            comparison,
+            ScenarioComparison {
+                fill_rate_before: 75.0,
+                fill_rate_after: 75.0,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -1,16 +1,139 @@
 use crate::engine::assignment::RoleId;
+use crate::engine::person::{DutyStatus, Person};
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Team {
     pub name: String,
+    /// Fill order when qualified people run short: 1 fills first, `None` fills last
+    #[serde(default)]
+    pub priority: Option<u32>,
     pub required_positions: Vec<Position>,
+    #[serde(default)]
+    pub composition: CompositionRules,
+}
+
+/// Limits on who a team is made of, counted over everyone on the team
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CompositionRules {
+    #[serde(default)]
+    pub max_selres: Option<u32>,
+    #[serde(default)]
+    pub min_tar: Option<u32>,
+    #[serde(default)]
+    pub max_aw: Option<u32>,
+    /// Fewest people the team takes, whoever they are
+    #[serde(default)]
+    pub min_staff: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompositionRule {
+    MaxSelres(u32),
+    MinTar(u32),
+    MaxAw(u32),
+    MinStaff(u32),
+}
+
+/// A composition rule the team's members break
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompositionViolation {
+    pub team_name: String,
+    pub rule: CompositionRule,
+    /// How many members the rule counts
+    pub actual: u32,
+}
+
+impl CompositionRules {
+    pub fn is_empty(&self) -> bool {
+        self.rules().is_empty()
+    }
+
+    pub fn rules(&self) -> Vec<CompositionRule> {
+        [
+            self.max_selres.map(CompositionRule::MaxSelres),
+            self.min_tar.map(CompositionRule::MinTar),
+            self.max_aw.map(CompositionRule::MaxAw),
+            self.min_staff.map(CompositionRule::MinStaff),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Rules broken by `members`, in field order
+    pub fn violations(&self, team_name: &str, members: &[&Person]) -> Vec<CompositionViolation> {
+        self.rules()
+            .into_iter()
+            .filter_map(|rule| {
+                let actual = members.iter().filter(|p| rule.counts(p)).count() as u32;
+                (!rule.allows(actual)).then(|| CompositionViolation {
+                    team_name: team_name.to_string(),
+                    rule,
+                    actual,
+                })
+            })
+            .collect()
+    }
+}
+
+impl CompositionRule {
+    /// Whether `person` counts toward this rule
+    pub fn counts(&self, person: &Person) -> bool {
+        match self {
+            CompositionRule::MaxSelres(_) => person.duty_status == DutyStatus::Selres,
+            CompositionRule::MinTar(_) => person.duty_status == DutyStatus::Tar,
+            CompositionRule::MaxAw(_) => person.raterank.is_aw(),
+            CompositionRule::MinStaff(_) => true,
+        }
+    }
+
+    pub fn allows(&self, count: u32) -> bool {
+        match *self {
+            CompositionRule::MaxSelres(limit) | CompositionRule::MaxAw(limit) => count <= limit,
+            CompositionRule::MinTar(limit) | CompositionRule::MinStaff(limit) => count >= limit,
+        }
+    }
+}
+
+impl Display for CompositionRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompositionRule::MaxSelres(limit) => write!(f, "at most {} SELRES", limit),
+            CompositionRule::MinTar(limit) => write!(f, "at least {} TAR", limit),
+            CompositionRule::MaxAw(limit) => write!(f, "at most {} AW", limit),
+            CompositionRule::MinStaff(limit) => write!(f, "at least {} people", limit),
+        }
+    }
+}
+
+impl Display for CompositionViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} has {}, needs {}",
+            self.team_name, self.actual, self.rule
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct Position {
     pub qualification: String,
     pub instance: u32,
+    /// Mandatory positions are filled before any optional position
+    #[serde(default)]
+    pub mandatory: bool,
+    /// Lowest enlisted paygrade allowed, e.g. 7 for E-7
+    #[serde(default)]
+    pub min_paygrade: Option<u8>,
+    /// Highest enlisted paygrade allowed
+    #[serde(default)]
+    pub max_paygrade: Option<u8>,
+    /// General rates allowed, e.g. AM, AD and AE; empty allows every rate
+    #[serde(default)]
+    pub allowed_rates: Vec<String>,
 }
 
 impl Position {
@@ -18,6 +141,20 @@ impl Position {
         format!("{}-{}-{:03}", team_name, self.qualification, self.instance)
     }
 
+    /// Whether `person` meets the paygrade and rate limits; holding the qualification
+    /// is checked separately
+    pub fn admits(&self, person: &Person) -> bool {
+        let paygrade_ok = match (self.min_paygrade, self.max_paygrade) {
+            (None, None) => true,
+            (min, max) => person.raterank.paygrade.is_some_and(|paygrade| {
+                min.is_none_or(|min| paygrade >= min) && max.is_none_or(|max| paygrade <= max)
+            }),
+        };
+        let rate_ok =
+            self.allowed_rates.is_empty() || self.allowed_rates.contains(&person.raterank.rate);
+        paygrade_ok && rate_ok
+    }
+
     pub fn as_role_id(&self, team_name: &str) -> RoleId {
         RoleId {
             team: team_name.to_string(),
@@ -0,0 +1,252 @@
+use anyhow::{Context, Result};
+
+use crate::engine::assignment::{AssignmentExclusion, AssignmentPlan, UnfilledPosition};
+use crate::engine::builder::same_qualification_family;
+use crate::engine::cost_policy::CostPolicy;
+use crate::engine::hungarian::{min_cost_assignment, FORBIDDEN};
+use crate::engine::person::Person;
+
+/// One person to put through training, and the gap it closes
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingRecommendation {
+    pub person_name: String,
+    pub qualification: String,
+    /// Role id of the unfilled position the plan gives them
+    pub fills: String,
+    /// Role ids of every unfilled position the qualification makes them eligible for
+    pub unlocks: Vec<String>,
+}
+
+/// Who on the bench to qualify in what so the most unfilled positions get filled,
+/// mandatory positions first.
+///
+/// Each bench person is matched to at most one unfilled position whose rate limits
+/// they meet and whose qualification they do not hold, or hold expired; quals already
+/// in training are left alone. Among plans that fill as many positions, the cheapest
+/// wins: duty status and PRD are priced as the solver prices them, so TAR sailors
+/// with distant PRDs go first, and `related_qualification_bonus` is taken off in
+/// proportion to how many holders of the qualification share one of the person's
+/// quals in the same family, such as 110 CDI towards 120 CDI.
+pub fn recommend_training(
+    plan: &AssignmentPlan,
+    exclusions: &[AssignmentExclusion],
+    cost_policy: &CostPolicy,
+    analysis_date: chrono::NaiveDate,
+) -> Vec<TrainingRecommendation> {
+    let bench = plan.unassigned_people.as_slice();
+    let gaps: Vec<&UnfilledPosition> = plan
+        .unfilled_mandatory_positions
+        .iter()
+        .chain(&plan.unfilled_positions)
+        .collect();
+    if bench.is_empty() || gaps.is_empty() {
+        return vec![];
+    }
+
+    let roster: Vec<&Person> = plan
+        .assignments
+        .iter()
+        .map(|a| a.person.as_ref())
+        .chain(bench)
+        .collect();
+    let can_train = |person: &Person, gap: &UnfilledPosition| {
+        let qualification = &gap.position.qualification;
+        !person.is_qualified(qualification, analysis_date)
+            && person.completion(qualification).is_none()
+            && gap.position.admits(person)
+            && !exclusions
+                .iter()
+                .any(|e| e.excludes(&person.name, &gap.team_name, qualification))
+    };
+
+    let pair_costs: Vec<Vec<Option<i64>>> = bench
+        .iter()
+        .map(|person| {
+            gaps.iter()
+                .map(|gap| {
+                    can_train(person, gap)
+                        .then(|| training_cost(person, gap, &roster, cost_policy, analysis_date))
+                })
+                .collect()
+        })
+        .collect();
+
+    // as in the Hungarian backend, every extra gap filled outweighs any spread in cost
+    let rows = bench.len();
+    let largest = pair_costs
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|cost| cost.abs())
+        .max()
+        .unwrap_or(0);
+    let placement_bonus = (largest + 1) * (2 * rows as i64 + 1);
+    let costs: Vec<Vec<i64>> = pair_costs
+        .iter()
+        .map(|costs| {
+            costs
+                .iter()
+                .map(|cost| cost.map_or(FORBIDDEN, |cost| cost - placement_bonus))
+                .chain(std::iter::repeat_n(0, rows))
+                .collect()
+        })
+        .collect();
+
+    let mut matched: Vec<(usize, usize)> = min_cost_assignment(&costs)
+        .into_iter()
+        .enumerate()
+        .filter(|&(row, column)| column < gaps.len() && pair_costs[row][column].is_some())
+        .map(|(row, column)| (column, row))
+        .collect();
+    matched.sort();
+
+    matched
+        .into_iter()
+        .map(|(column, row)| {
+            let person = &bench[row];
+            let qualification = &gaps[column].position.qualification;
+            TrainingRecommendation {
+                person_name: person.name.clone(),
+                qualification: qualification.clone(),
+                fills: gaps[column].role_id.clone(),
+                unlocks: gaps
+                    .iter()
+                    .filter(|gap| gap.position.qualification == *qualification)
+                    .filter(|gap| can_train(person, gap))
+                    .map(|gap| gap.role_id.clone())
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Lower is a better candidate for `gap`
+fn training_cost(
+    person: &Person,
+    gap: &UnfilledPosition,
+    roster: &[&Person],
+    cost_policy: &CostPolicy,
+    analysis_date: chrono::NaiveDate,
+) -> i64 {
+    let qualification = &gap.position.qualification;
+    let cost = cost_policy.assignment_cost(person, qualification, analysis_date);
+    let optional = if gap.position.mandatory {
+        0
+    } else {
+        cost_policy.optional_position_penalty
+    };
+
+    // only quals in the same family count as a head start, so common quals don't
+    let related: Vec<&String> = person
+        .qualifications
+        .iter()
+        .filter(|q| *q != qualification && same_qualification_family(q, qualification))
+        .collect();
+    let holders: Vec<&&Person> = roster
+        .iter()
+        .filter(|p| p.qualifications.contains(qualification))
+        .collect();
+    let sharing = holders
+        .iter()
+        .filter(|p| related.iter().any(|q| p.qualifications.contains(*q)))
+        .count() as i64;
+    let related_bonus = if holders.is_empty() {
+        0
+    } else {
+        cost_policy.related_qualification_bonus * sharing / holders.len() as i64
+    };
+
+    cost.duty_status + cost.prd_band + optional - related_bonus
+}
+
+/// The plan as a Name, Qual, Fills, Unlocks CSV; unlocked positions are separated by
+/// semicolons
+pub fn training_plan_csv(recommendations: &[TrainingRecommendation]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(["Name", "Qual", "Fills", "Unlocks"])?;
+    for recommendation in recommendations {
+        writer.write_record([
+            recommendation.person_name.as_str(),
+            recommendation.qualification.as_str(),
+            recommendation.fills.as_str(),
+            recommendation.unlocks.join("; ").as_str(),
+        ])?;
+    }
+    let bytes = writer
+        .into_inner()
+        .context("Failed to write training plan CSV")?;
+    String::from_utf8(bytes).context("Training plan CSV is not UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::assignment::tests::{person, team};
+    use crate::engine::builder::{build_assignment_plan, WarmStartSolver};
+    use crate::engine::person::DutyStatus;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_recommends_related_tar_with_distant_prd() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let people = vec![
+            person("Frank", &["120 CDI", "110 CDI"]),
+            person("Baker", &["110 CDI"]).with_status(DutyStatus::Selres),
+            person("Carter", &["110 CDI"]).with_prd(NaiveDate::from_ymd_opt(2025, 3, 1)),
+            person("Davis", &["QAR"]),
+            person("Evans", &["110 CDI"]),
+        ];
+        let teams = vec![
+            team("Alpha", Some(1), &["120 CDI"]).mandatory(),
+            team("Det", Some(2), &["120 CDI"]).mandatory(),
+        ];
+        let policy = CostPolicy::default();
+        let result = WarmStartSolver::new(people, teams, &policy)
+            .solve(date, None, &[], &[])
+            .unwrap();
+        let plan = build_assignment_plan(&result).unwrap();
+
+        let recommendations = recommend_training(&plan, &[], &policy, date);
+        assert_eq!(
+            recommendations,
+            vec![TrainingRecommendation {
+                person_name: "Evans".to_string(),
+                qualification: "120 CDI".to_string(),
+                fills: "Det-120 CDI-001".to_string(),
+                unlocks: vec!["Det-120 CDI-001".to_string()],
+            }]
+        );
+        assert_eq!(
+            training_plan_csv(&recommendations).unwrap(),
+            "Name,Qual,Fills,Unlocks\nEvans,120 CDI,Det-120 CDI-001,Det-120 CDI-001\n"
+        );
+    }
+
+    #[test]
+    fn test_only_related_quals_earn_the_bonus() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let prd = NaiveDate::from_ymd_opt(2026, 1, 1);
+        // everyone holds SFF, so only Baker's 110 CDI says anything about 120 CDI
+        let people = vec![
+            person("Frank", &["120 CDI", "110 CDI", "SFF"]),
+            person("Adams", &["SFF"]).with_prd(prd),
+            person("Baker", &["SFF", "110 CDI"]).with_prd(prd),
+        ];
+        let teams = vec![
+            team("Alpha", Some(1), &["120 CDI"]).mandatory(),
+            team("Det", Some(2), &["120 CDI"]).mandatory(),
+        ];
+        let policy = CostPolicy::default();
+        let result = WarmStartSolver::new(people, teams, &policy)
+            .solve(date, None, &[], &[])
+            .unwrap();
+        let plan = build_assignment_plan(&result).unwrap();
+
+        let recommendations = recommend_training(&plan, &[], &policy, date);
+        let names: Vec<_> = recommendations
+            .iter()
+            .map(|r| r.person_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Baker"]);
+    }
+}
@@ -1,4 +1,10 @@
-use crate::engine::person::Person;
+use crate::engine::assignment::AssignmentExclusion;
+use crate::engine::backend::BackendKind;
+use crate::engine::cost_policy::CostPolicy;
+use crate::engine::pairing::PairingConstraint;
+use crate::engine::person::{PendingQualification, Person};
+use crate::engine::plan_diff::PlanBaseline;
+use crate::engine::scenario::ScenarioEdit;
 use crate::engine::team::{Position, Team};
 use crate::utilities::parsing::{PRDList, QualTable};
 use std::collections::HashMap;
@@ -44,6 +50,19 @@ pub enum ParsedData {
     Personnel(Rc<Vec<Person>>), // Combined ASM + FLTMPS data
 }
 
+impl ParsedData {
+    /// Whether both hold the very same parsed data, without comparing it
+    pub fn ptr_eq(&self, other: &ParsedData) -> bool {
+        match (self, other) {
+            (ParsedData::Requirements(a), ParsedData::Requirements(b)) => Rc::ptr_eq(a, b),
+            (ParsedData::QualDefs(a), ParsedData::QualDefs(b)) => Rc::ptr_eq(a, b),
+            (ParsedData::Fltmps(a), ParsedData::Fltmps(b)) => Rc::ptr_eq(a, b),
+            (ParsedData::Personnel(a), ParsedData::Personnel(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FileUploadConfig {
     pub file_content: Option<Rc<Vec<u8>>>,
@@ -68,6 +87,21 @@ impl FileUploadConfig {
 pub struct AppState {
     pub files: HashMap<String, FileUploadConfig>,
     pub persistent_locks: HashMap<(String, Position), String>,
+    /// People kept off a team or qualification
+    pub exclusions: Vec<AssignmentExclusion>,
+    /// People kept together on one team or apart on different teams
+    pub pairings: Vec<PairingConstraint>,
+    /// Qualifications still in training, counted from their completion dates
+    pub pending_qualifications: Vec<PendingQualification>,
+    pub cost_policy: CostPolicy,
+    /// Earlier plan to stay close to; empty when continuity is off
+    pub baseline: PlanBaseline,
+    /// What-if edits tried on copies of the loaded data; never saved
+    pub scenario: Vec<ScenarioEdit>,
+    /// Algorithm that solves the priced network; a session setting, never saved
+    pub backend: BackendKind,
+    /// Also solve each plan with every other backend and flag any difference
+    pub compare_backends: bool,
 }
 
 impl Default for AppState {
@@ -89,6 +123,14 @@ impl Default for AppState {
         AppState {
             files,
             persistent_locks: HashMap::new(),
+            exclusions: Vec::new(),
+            pairings: Vec::new(),
+            pending_qualifications: Vec::new(),
+            cost_policy: CostPolicy::default(),
+            baseline: PlanBaseline::new(),
+            scenario: Vec::new(),
+            backend: BackendKind::default(),
+            compare_backends: false,
         }
     }
 }
@@ -102,19 +144,19 @@ impl AppState {
         let has_requirements = self
             .files
             .get("Requirements")
-            .map_or(false, |f| f.parsed_data.is_some());
+            .is_some_and(|f| f.parsed_data.is_some());
         let has_asm = self
             .files
             .get("ASM")
-            .map_or(false, |f| f.parsed_data.is_some());
+            .is_some_and(|f| f.parsed_data.is_some());
         let has_qual_defs = self
             .files
             .get("Qual Defs")
-            .map_or(false, |f| f.parsed_data.is_some());
+            .is_some_and(|f| f.parsed_data.is_some());
         let has_fltmps = self
             .files
             .get("FLTMPS")
-            .map_or(false, |f| f.parsed_data.is_some());
+            .is_some_and(|f| f.parsed_data.is_some());
 
         // Save state scenario: Requirements + ASM + Qual Defs (FLTMPS data integrated into Person objects)
         if has_requirements && has_asm && has_qual_defs && !has_fltmps {
@@ -136,19 +178,19 @@ impl AppState {
         let has_requirements = self
             .files
             .get("Requirements")
-            .map_or(false, |f| f.parsed_data.is_some());
+            .is_some_and(|f| f.parsed_data.is_some());
         let has_asm = self
             .files
             .get("ASM")
-            .map_or(false, |f| f.parsed_data.is_some());
+            .is_some_and(|f| f.parsed_data.is_some());
         let has_qual_defs = self
             .files
             .get("Qual Defs")
-            .map_or(false, |f| f.parsed_data.is_some());
+            .is_some_and(|f| f.parsed_data.is_some());
         let has_fltmps = self
             .files
             .get("FLTMPS")
-            .map_or(false, |f| f.parsed_data.is_some());
+            .is_some_and(|f| f.parsed_data.is_some());
 
         if has_requirements && has_asm && has_qual_defs && !has_fltmps {
             // Save state scenario: show 4/4 even though only 3 files loaded
@@ -1,11 +1,17 @@
+#[cfg(target_arch = "wasm32")]
+use crate::engine::training::{training_plan_csv, TrainingRecommendation};
 use crate::engine::{
-    assignment::AssignmentLock,
-    person::Person,
+    assignment::{AssignmentExclusion, AssignmentLock},
+    cost_policy::CostPolicy,
+    pairing::PairingConstraint,
+    person::{PendingQualification, Person},
+    plan_diff::PlanBaseline,
     team::{Position, Team},
 };
 use crate::utilities::parsing::QualTable;
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -27,6 +33,26 @@ pub struct SaveState {
     /// Manual assignment locks
     pub persistent_locks: Vec<AssignmentLock>,
 
+    /// People kept off a team or qualification
+    #[serde(default)]
+    pub exclusions: Vec<AssignmentExclusion>,
+
+    /// People kept together or apart
+    #[serde(default)]
+    pub pairings: Vec<PairingConstraint>,
+
+    /// Qualifications still in training; `people` holds only what ASM reported
+    #[serde(default)]
+    pub pending_qualifications: Vec<PendingQualification>,
+
+    /// Cost weights used by the solver, so the plan can be reproduced exactly
+    #[serde(default)]
+    pub cost_policy: CostPolicy,
+
+    /// Where everyone sat in the plan on screen; the baseline once loaded again
+    #[serde(default)]
+    pub plan: PlanBaseline,
+
     /// Timestamp when this state was exported
     pub export_timestamp: DateTime<Utc>,
 
@@ -42,13 +68,23 @@ impl SaveState {
         teams: &[Team],
         qual_defs: &QualTable,
         persistent_locks: &HashMap<(String, Position), String>,
+        exclusions: &[AssignmentExclusion],
+        cost_policy: &CostPolicy,
     ) -> Self {
         Self {
             analysis_date,
-            people: people.to_vec(),
+            people: people
+                .iter()
+                .map(Person::without_pending_qualifications)
+                .collect(),
             teams: teams.to_vec(),
             qual_defs: qual_defs.clone(),
             persistent_locks: Self::locks_to_vec(persistent_locks),
+            exclusions: exclusions.to_vec(),
+            pairings: vec![],
+            pending_qualifications: vec![],
+            cost_policy: cost_policy.clone(),
+            plan: PlanBaseline::new(),
             export_timestamp: Utc::now(),
             version: env!("CARGO_PKG_VERSION").to_string(),
         }
@@ -57,9 +93,11 @@ impl SaveState {
     /// Convert Results HashMap format to export Vec format
     /// NOTE: The HashMap appears to be structured as (team_name, position) -> person_name
     /// instead of the expected (person_name, position) -> team_name
+    /// Sorted by team and role so the same locks always export the same way
     fn locks_to_vec(locks: &HashMap<(String, Position), String>) -> Vec<AssignmentLock> {
         locks
             .iter()
+            .sorted_by_key(|((team_name, position), _)| position.role_id(team_name))
             .map(|((team_name, position), person_name)| AssignmentLock {
                 person_name: person_name.clone(),
                 position: Some(position.clone()),
@@ -70,6 +108,7 @@ impl SaveState {
 
     /// Convert export Vec format back to Results HashMap format
     /// NOTE: Maintaining the actual structure used: (team_name, position) -> person_name
+    #[cfg(any(target_arch = "wasm32", test))]
     pub fn locks_to_hashmap(&self) -> HashMap<(String, Position), String> {
         self.persistent_locks
             .iter()
@@ -91,35 +130,41 @@ impl SaveState {
         serde_json::to_string_pretty(self).context("Failed to serialize save state to JSON")
     }
 
-    /// Export save state to compact JSON string
-    pub fn to_json_compact(&self) -> Result<String> {
-        serde_json::to_string(self).context("Failed to serialize save state to compact JSON")
-    }
-
     /// Export save state as a downloadable JSON file
     #[cfg(target_arch = "wasm32")]
     pub fn download(&self, filename: &str) -> Result<()> {
         let json = self.to_json()?;
-        trigger_json_download(&json, filename)
+        trigger_download(&json, filename, "application/json")
             .map_err(|e| anyhow::anyhow!("Download failed: {}", e))
     }
 }
 
-/// Trigger a browser download of JSON content
+/// Export a training plan as a downloadable CSV file
 #[cfg(target_arch = "wasm32")]
-fn trigger_json_download(json_content: &str, filename: &str) -> Result<(), String> {
+pub fn download_training_plan(
+    recommendations: &[TrainingRecommendation],
+    filename: &str,
+) -> Result<()> {
+    let csv = training_plan_csv(recommendations)?;
+    trigger_download(&csv, filename, "text/csv")
+        .map_err(|e| anyhow::anyhow!("Download failed: {}", e))
+}
+
+/// Trigger a browser download of `content` with the given MIME type
+#[cfg(target_arch = "wasm32")]
+fn trigger_download(content: &str, filename: &str, mime_type: &str) -> Result<(), String> {
     use wasm_bindgen::JsCast;
 
     let window = web_sys::window().ok_or("Failed to get window")?;
     let document = window.document().ok_or("Failed to get document")?;
 
-    // Create a blob with the JSON content
+    // Create a blob with the content
     let array = js_sys::Array::new();
-    array.push(&wasm_bindgen::JsValue::from_str(json_content));
+    array.push(&wasm_bindgen::JsValue::from_str(content));
 
     let blob_parts = array;
     let blob_options = web_sys::BlobPropertyBag::new();
-    blob_options.set_type("application/json");
+    blob_options.set_type(mime_type);
 
     let blob = web_sys::Blob::new_with_str_sequence_and_options(&blob_parts, &blob_options)
         .map_err(|_| "Failed to create blob")?;
@@ -148,31 +193,40 @@ fn trigger_json_download(json_content: &str, filename: &str) -> Result<(), Strin
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::pairing::PairingKind;
     use crate::engine::person::DutyStatus;
-    use std::collections::HashSet;
+    use crate::engine::plan_diff::PlanSlot;
+    use crate::engine::team::CompositionRules;
+    use std::collections::{BTreeMap, BTreeSet};
 
     #[test]
     fn test_save_state_serialization() {
         // Create test data
         let analysis_date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
 
-        let mut qualifications = HashSet::new();
+        let mut qualifications = BTreeSet::new();
         qualifications.insert("120 CDI".to_string());
 
         let people = vec![Person {
             name: "Smith, John".to_string(),
-            raterank: "AM2".to_string(),
+            raterank: "AM2".into(),
             duty_status: DutyStatus::Tar,
             qualifications,
             prd: Some(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()),
+            qualification_expirations: BTreeMap::new(),
+            qualification_completions: BTreeMap::new(),
         }];
 
         let teams = vec![Team {
             name: "QA".to_string(),
+            priority: Some(1),
             required_positions: vec![Position {
                 qualification: "120 CDI".to_string(),
                 instance: 1,
+                mandatory: false,
+                ..Default::default()
             }],
+            composition: CompositionRules::default(),
         }];
 
         let mut locks = HashMap::new();
@@ -182,16 +236,54 @@ mod tests {
                 Position {
                     qualification: "120 CDI".to_string(),
                     instance: 1,
+                    mandatory: false,
+                    ..Default::default()
                 },
             ),
             "QA".to_string(),
         );
 
-        let mut qual_defs = HashMap::new();
-        qual_defs.insert("120 CDI".to_string(), "120 CDI".to_string());
+        let mut qual_defs = BTreeMap::new();
+        qual_defs.insert("120 CDI".to_string(), vec!["120 CDI".to_string()]);
+
+        let exclusions = vec![AssignmentExclusion {
+            person_name: "Smith, John".to_string(),
+            team_name: Some("Det".to_string()),
+            qualification: None,
+        }];
 
         // Create save state and serialize
-        let save_state = SaveState::new(analysis_date, &people, &teams, &qual_defs, &locks);
+        let cost_policy = CostPolicy {
+            selres_penalty: 2_000,
+            ..Default::default()
+        };
+
+        let pairings = vec![PairingConstraint {
+            first: "Smith, John".to_string(),
+            second: "Doe, Jane".to_string(),
+            kind: PairingKind::Apart,
+        }];
+
+        let plan = PlanBaseline::from([(
+            "Smith, John".to_string(),
+            PlanSlot {
+                team_name: "QA".to_string(),
+                qualification: "120 CDI".to_string(),
+            },
+        )]);
+        let save_state = SaveState {
+            pairings: pairings.clone(),
+            plan: plan.clone(),
+            ..SaveState::new(
+                analysis_date,
+                &people,
+                &teams,
+                &qual_defs,
+                &locks,
+                &exclusions,
+                &cost_policy,
+            )
+        };
         let json = save_state.to_json().expect("Failed to serialize");
 
         // Verify we can deserialize back
@@ -201,6 +293,10 @@ mod tests {
         assert_eq!(deserialized.people.len(), 1);
         assert_eq!(deserialized.teams.len(), 1);
         assert_eq!(deserialized.persistent_locks.len(), 1);
+        assert_eq!(deserialized.exclusions, exclusions);
+        assert_eq!(deserialized.pairings, pairings);
+        assert_eq!(deserialized.cost_policy, cost_policy);
+        assert_eq!(deserialized.plan, plan);
         assert_eq!(deserialized.version, env!("CARGO_PKG_VERSION"));
 
         // Test the conversion back to HashMap format
@@ -211,7 +307,9 @@ mod tests {
                 "Smith, John".to_string(),
                 Position {
                     qualification: "120 CDI".to_string(),
-                    instance: 1
+                    instance: 1,
+                    mandatory: false,
+                    ..Default::default()
                 }
             )),
             Some(&"QA".to_string())
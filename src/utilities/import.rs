@@ -115,7 +115,7 @@ mod tests {
                 }
             ],
             "qual_defs": {
-                "120 CDI": "120 CDI"
+                "120 CDI": ["120 CDI"]
             },
             "persistent_locks": [
                 {
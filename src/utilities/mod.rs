@@ -1,7 +1,7 @@
 pub mod parsing;
 pub use parsing::{
-    enhance_personnel_with_prd, parse_asm_file, parse_fltmps_file, parse_qual_defs,
-    parse_requirements,
+    enhance_personnel_with_prd, parse_asm_file, parse_fltmps_file, parse_pending_qualifications,
+    parse_qual_defs, parse_requirements,
 };
 
 pub mod config;
@@ -10,4 +10,6 @@ pub use config::{AppState, PreviewType};
 pub mod export;
 pub use export::SaveState;
 
+// only the web build loads save states
+#[cfg(any(target_arch = "wasm32", test))]
 pub mod import;
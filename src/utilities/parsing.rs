@@ -1,7 +1,7 @@
-use crate::engine::team::{Position, Team};
+use crate::engine::team::{CompositionRules, Position, Team};
 use anyhow::{anyhow, Result};
 use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::rc::Rc;
 
 // everything related to qualification footprint requirements
@@ -14,33 +14,175 @@ pub struct Requirement {
     pub qual_name: String,
     #[serde(alias = "Num Required")]
     pub qual_qty: usize,
+    #[serde(alias = "Priority", default)]
+    pub priority: Option<u32>,
+    /// Either a yes/no flag for every instance or the number of instances that are mandatory
+    #[serde(alias = "Mandatory", default)]
+    pub mandatory: Option<String>,
+    #[serde(alias = "Max SELRES", default)]
+    pub max_selres: Option<u32>,
+    #[serde(alias = "Min TAR", default)]
+    pub min_tar: Option<u32>,
+    #[serde(alias = "Max AW", default)]
+    pub max_aw: Option<u32>,
+    #[serde(alias = "Min Staff", default)]
+    pub min_staff: Option<u32>,
+    /// Paygrade as E-7, E7 or 7
+    #[serde(alias = "Min Paygrade", default)]
+    pub min_paygrade: Option<String>,
+    #[serde(alias = "Max Paygrade", default)]
+    pub max_paygrade: Option<String>,
+    /// General rates separated by slashes, commas or spaces, e.g. AM/AD/AE
+    #[serde(alias = "Rates", default)]
+    pub rates: Option<String>,
 }
 
+impl Requirement {
+    fn paygrade(&self, value: &Option<String>) -> Result<Option<u8>> {
+        let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+            return Ok(None);
+        };
+        let digits = value.trim_start_matches(['E', 'e']).trim_start_matches('-');
+        match digits.parse::<u8>() {
+            Ok(paygrade @ 1..=9) => Ok(Some(paygrade)),
+            _ => Err(anyhow!(
+                "Invalid paygrade '{}' for {} in {}",
+                value,
+                self.qual_name,
+                self.team_name
+            )),
+        }
+    }
+
+    fn allowed_rates(&self) -> Result<Vec<String>> {
+        self.rates
+            .as_deref()
+            .unwrap_or_default()
+            .split(['/', ',', ';', ' '])
+            .map(|rate| rate.trim().to_uppercase())
+            .filter(|rate| !rate.is_empty())
+            .map(|rate| {
+                if rate.chars().all(|c| c.is_ascii_alphabetic()) {
+                    Ok(rate)
+                } else {
+                    Err(anyhow!(
+                        "Invalid rate '{}' for {} in {}",
+                        rate,
+                        self.qual_name,
+                        self.team_name
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    fn mandatory_count(&self) -> Result<usize> {
+        let Some(value) = self.mandatory.as_deref().map(str::trim) else {
+            return Ok(0);
+        };
+        match value.to_lowercase().as_str() {
+            "" | "n" | "no" | "false" => Ok(0),
+            "y" | "yes" | "true" | "x" => Ok(self.qual_qty),
+            count => count
+                .parse::<usize>()
+                .map(|n| n.min(self.qual_qty))
+                .map_err(|_| {
+                    anyhow!(
+                        "Invalid Mandatory value '{}' for {} in {}",
+                        value,
+                        self.qual_name,
+                        self.team_name
+                    )
+                }),
+        }
+    }
+}
+
+/// Take a team-wide value from a row, unless an earlier row gave a different one
+fn merge_team_value(
+    current: &mut Option<u32>,
+    value: Option<u32>,
+    column: &str,
+    team_name: &str,
+) -> Result<()> {
+    match (*current, value) {
+        (Some(current), Some(value)) if current != value => Err(anyhow!(
+            "Conflicting {} for {}: {} and {}",
+            column,
+            team_name,
+            current,
+            value
+        )),
+        (None, Some(value)) => {
+            *current = Some(value);
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Teams come back in the order they first appear in the file
 pub fn parse_requirements(data: Rc<Vec<u8>>) -> Result<Vec<Team>> {
-    let mut teams = HashMap::new();
+    let mut teams: Vec<Team> = Vec::new();
+    let mut team_index: HashMap<String, usize> = HashMap::new();
     let mut rdr = csv::Reader::from_reader(&data[..]);
 
     for record in rdr.deserialize() {
         let record: Requirement = record?;
-        let team = teams.entry(record.team_name.clone()).or_insert(Team {
-            name: record.team_name.clone(),
-            required_positions: Vec::new(),
-        });
+        let idx = *team_index
+            .entry(record.team_name.clone())
+            .or_insert_with(|| {
+                teams.push(Team {
+                    name: record.team_name.clone(),
+                    priority: None,
+                    required_positions: Vec::new(),
+                    composition: CompositionRules::default(),
+                });
+                teams.len() - 1
+            });
+        let team = &mut teams[idx];
+        // priority and composition rules only need to appear on one row per team, but
+        // every row that gives one must agree
+        let name = &record.team_name;
+        merge_team_value(&mut team.priority, record.priority, "Priority", name)?;
+        let composition = &mut team.composition;
+        merge_team_value(
+            &mut composition.max_selres,
+            record.max_selres,
+            "Max SELRES",
+            name,
+        )?;
+        merge_team_value(&mut composition.min_tar, record.min_tar, "Min TAR", name)?;
+        merge_team_value(&mut composition.max_aw, record.max_aw, "Max AW", name)?;
+        merge_team_value(
+            &mut composition.min_staff,
+            record.min_staff,
+            "Min Staff",
+            name,
+        )?;
+        let mandatory_count = record.mandatory_count()?;
+        let min_paygrade = record.paygrade(&record.min_paygrade)?;
+        let max_paygrade = record.paygrade(&record.max_paygrade)?;
+        let allowed_rates = record.allowed_rates()?;
         for instance in 1..=record.qual_qty {
             let position = Position {
                 qualification: record.qual_name.clone(),
                 instance: instance as u32,
+                mandatory: instance <= mandatory_count,
+                min_paygrade,
+                max_paygrade,
+                allowed_rates: allowed_rates.clone(),
             };
             team.required_positions.push(position);
         }
     }
 
-    Ok(teams.into_values().collect())
+    Ok(teams)
 }
 
 // everything dealing with translating ASM to common qual names
 
-pub type QualTable = HashMap<String, Vec<String>>;
+pub type QualTable = BTreeMap<String, Vec<String>>;
 
 pub fn parse_qual_defs(data: Rc<Vec<u8>>) -> Result<QualTable> {
     let mut quals = QualTable::new();
@@ -67,14 +209,21 @@ pub fn parse_qual_defs(data: Rc<Vec<u8>>) -> Result<QualTable> {
 // everything dealing with parsing ASM files to display members and their ASM quals
 use calamine::{open_workbook_from_rs, Data, Reader, Xlsx};
 
-use crate::engine::person::{DutyStatus, Person};
+use crate::engine::person::{DutyStatus, PendingQualification, Person};
 
+/// People come back sorted by name. Qualification expiry comes from the
+/// EXPIRATION_DATE column when the export has one.
 pub fn parse_asm_file(data: Rc<Vec<u8>>) -> Result<Vec<Person>> {
     let data = data.as_ref();
-    let mut people: HashMap<String, Person> = HashMap::new();
+    let mut people: BTreeMap<String, Person> = BTreeMap::new();
     let cursor = std::io::Cursor::new(data);
     let mut workbook: Xlsx<_> = open_workbook_from_rs(cursor)?;
     if let Some(Ok(range)) = workbook.worksheet_range_at(0) {
+        let expiration_col = range.rows().next().and_then(|header| {
+            header
+                .iter()
+                .position(|cell| data_to_string(cell).trim() == "EXPIRATION_DATE")
+        });
         for row in range.rows().skip(1) {
             if row.len() < 4 {
                 //log::warn!("Skipping row with {} columns (expected 4+)", row.len());
@@ -94,11 +243,24 @@ pub fn parse_asm_file(data: Rc<Vec<u8>>) -> Result<Vec<Person>> {
                 let raterank = name_parts.last().unwrap_or("");
                 let person = people.entry(name.to_string()).or_insert(Person {
                     name: name.to_string(),
-                    raterank: raterank.to_string(),
+                    raterank: raterank.into(),
                     duty_status: DutyStatus::Selres, // this will be overridden later if needed
-                    qualifications: HashSet::new(),
+                    qualifications: BTreeSet::new(),
                     prd: None,
+                    qualification_expirations: BTreeMap::new(),
+                    qualification_completions: BTreeMap::new(),
                 });
+                let expiration = expiration_col
+                    .and_then(|col| row.get(col))
+                    .and_then(asm_date);
+                if let Some(expiration) = expiration {
+                    // a qualification listed twice lasts until the later date
+                    let latest = person
+                        .qualification_expirations
+                        .entry(qual.clone())
+                        .or_insert(expiration);
+                    *latest = (*latest).max(expiration);
+                }
                 person.qualifications.insert(qual);
             }
         }
@@ -106,10 +268,76 @@ pub fn parse_asm_file(data: Rc<Vec<u8>>) -> Result<Vec<Person>> {
     Ok(people.into_values().collect())
 }
 
+/// A date cell from the ASM export: an Excel date, or text such as 2025-06-30 or 06/30/2025
+fn asm_date(cell: &Data) -> Option<NaiveDate> {
+    let serial = match cell {
+        Data::DateTime(datetime) => Some(datetime.as_f64()),
+        Data::Float(serial) => Some(*serial),
+        Data::Int(serial) => Some(*serial as f64),
+        _ => None,
+    };
+    if let Some(serial) = serial {
+        // Excel counts days from 1899-12-30
+        let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?;
+        return epoch.checked_add_days(chrono::Days::new(serial.max(0.0) as u64));
+    }
+    let text = match cell {
+        Data::DateTimeIso(text) => Cow::Borrowed(text.as_str()),
+        _ => data_to_string(cell),
+    };
+    parse_date_text(&text)
+}
+
+/// 2025-06-30 or 06/30/2025; ISO text may carry a time after the date
+fn parse_date_text(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    NaiveDate::parse_from_str(text.get(..10).unwrap_or(text), "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(text, "%m/%d/%Y"))
+        .ok()
+}
+
+// everything dealing with qualifications still in training
+
+#[derive(Debug, Deserialize)]
+struct PendingRecord {
+    #[serde(alias = "Name")]
+    person_name: String,
+    #[serde(alias = "Qual")]
+    qualification: String,
+    #[serde(alias = "Completion Date")]
+    completion_date: String,
+}
+
+/// A CSV of Name, Qual, Completion Date rows; names match the ASM export and quals
+/// the Requirements file
+pub fn parse_pending_qualifications(data: &[u8]) -> Result<Vec<PendingQualification>> {
+    let mut rdr = csv::Reader::from_reader(data);
+    let mut pending = vec![];
+
+    for record in rdr.deserialize() {
+        let record: PendingRecord = record?;
+        let completion_date = parse_date_text(&record.completion_date).ok_or_else(|| {
+            anyhow!(
+                "Invalid completion date '{}' for {} in {}",
+                record.completion_date,
+                record.qualification,
+                record.person_name
+            )
+        })?;
+        pending.push(PendingQualification {
+            person_name: record.person_name.trim().to_string(),
+            qualification: record.qualification.trim().to_string(),
+            completion_date,
+        });
+    }
+
+    Ok(pending)
+}
+
 // everything dealing with parsing FLTMPS files to display members and their PRDs
 use chrono::NaiveDate;
 use std::borrow::Cow;
-pub type PRDList = HashMap<String, Option<NaiveDate>>;
+pub type PRDList = BTreeMap<String, Option<NaiveDate>>;
 
 fn fltmps_prd_to_date(prd_str: &str) -> Result<NaiveDate, chrono::ParseError> {
     let with_day = format!("01/{}", prd_str.trim());
@@ -204,7 +432,7 @@ pub fn parse_fltmps_file(data: Rc<Vec<u8>>) -> Result<PRDList> {
     Ok(prds)
 }
 
-fn prd_lookup(name: &str, prds: &HashMap<String, Option<NaiveDate>>) -> Option<NaiveDate> {
+fn prd_lookup(name: &str, prds: &PRDList) -> Option<NaiveDate> {
     let parts: Vec<&str> = name.splitn(2, ", ").collect();
     if let [last_name, rest] = parts.as_slice() {
         let matches: Vec<&String> = prds.keys().filter(|n| n.starts_with(last_name)).collect();
@@ -222,3 +450,121 @@ fn prd_lookup(name: &str, prds: &HashMap<String, Option<NaiveDate>>) -> Option<N
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "Name,Qual,Num Required,Priority,Mandatory,Max SELRES,Min TAR,Max AW,Min Staff,Min Paygrade,Max Paygrade,Rates";
+
+    fn parse(rows: &[&str]) -> Result<Vec<Team>> {
+        let csv = std::iter::once(HEADER)
+            .chain(rows.iter().copied())
+            .collect::<Vec<_>>()
+            .join("\n");
+        parse_requirements(Rc::new(csv.into_bytes()))
+    }
+
+    #[test]
+    fn test_priority_column() {
+        let teams = parse(&["Det,PC,1,2,,,,,,,,", "Day Check,PC,1,,,,,,,,,"]).unwrap();
+        assert_eq!(teams[0].priority, Some(2));
+        assert_eq!(teams[1].priority, None);
+        assert!(parse(&["Det,PC,1,high,,,,,,,,"]).is_err());
+    }
+
+    #[test]
+    fn test_mandatory_column() {
+        let mandatory = |value: &str| -> Result<Vec<bool>> {
+            let row = format!("Det,PC,3,,{},,,,,,,", value);
+            let teams = parse(&[row.as_str()])?;
+            Ok(teams[0]
+                .required_positions
+                .iter()
+                .map(|p| p.mandatory)
+                .collect())
+        };
+        assert_eq!(mandatory("yes").unwrap(), vec![true, true, true]);
+        assert_eq!(mandatory("X").unwrap(), vec![true, true, true]);
+        assert_eq!(mandatory("no").unwrap(), vec![false, false, false]);
+        assert_eq!(mandatory("2").unwrap(), vec![true, true, false]);
+        assert_eq!(mandatory("5").unwrap(), vec![true, true, true]);
+        assert_eq!(mandatory("").unwrap(), vec![false, false, false]);
+        assert!(mandatory("maybe").is_err());
+        assert!(mandatory("-1").is_err());
+    }
+
+    #[test]
+    fn test_composition_columns() {
+        // the rule each column after Mandatory sets, in column order
+        let rules: [fn(&CompositionRules) -> Option<u32>; 4] = [
+            |c| c.max_selres,
+            |c| c.min_tar,
+            |c| c.max_aw,
+            |c| c.min_staff,
+        ];
+        for (column, rule) in rules.into_iter().enumerate() {
+            let row = |value: &str| {
+                let mut cells = [""; 4];
+                cells[column] = value;
+                format!("Det,PC,2,,,{},,,", cells.join(","))
+            };
+            let teams = parse(&[row("1").as_str()]).unwrap();
+            assert_eq!(rule(&teams[0].composition), Some(1), "column {}", column);
+            let teams = parse(&[row("").as_str()]).unwrap();
+            assert_eq!(rule(&teams[0].composition), None, "column {}", column);
+            assert!(parse(&[row("one").as_str()]).is_err(), "column {}", column);
+            assert!(parse(&[row("-1").as_str()]).is_err(), "column {}", column);
+        }
+    }
+
+    #[test]
+    fn test_paygrade_columns() {
+        let paygrades = |min: &str, max: &str| -> Result<(Option<u8>, Option<u8>)> {
+            let row = format!("Det,PC,1,,,,,,,{},{},", min, max);
+            let position = parse(&[row.as_str()])?[0].required_positions[0].clone();
+            Ok((position.min_paygrade, position.max_paygrade))
+        };
+        assert_eq!(paygrades("E-5", "E7").unwrap(), (Some(5), Some(7)));
+        assert_eq!(paygrades("e4", "9").unwrap(), (Some(4), Some(9)));
+        assert_eq!(paygrades("", " ").unwrap(), (None, None));
+        assert!(paygrades("E-10", "").is_err());
+        assert!(paygrades("", "Chief").is_err());
+        assert!(paygrades("0", "").is_err());
+    }
+
+    #[test]
+    fn test_rates_column() {
+        let rates = |value: &str| -> Result<Vec<String>> {
+            let row = format!("Det,PC,1,,,,,,,,,\"{}\"", value);
+            Ok(parse(&[row.as_str()])?[0].required_positions[0]
+                .allowed_rates
+                .clone())
+        };
+        assert_eq!(rates("AM/ad, AE;AZ").unwrap(), vec!["AM", "AD", "AE", "AZ"]);
+        assert_eq!(rates("").unwrap(), Vec::<String>::new());
+        assert!(rates("AM/AD2").is_err());
+        assert!(rates("AM-AD").is_err());
+    }
+
+    #[test]
+    fn test_team_values_must_agree_across_rows() {
+        // a value given once covers the team, and repeating it is fine
+        let teams = parse(&[
+            "Det,PC,1,1,,2,,,,,,",
+            "Det,CDI,1,,,,1,,,,,",
+            "Det,QAR,1,1,,2,1,,,,,",
+        ])
+        .unwrap();
+        assert_eq!(teams.len(), 1);
+        assert_eq!(teams[0].priority, Some(1));
+        assert_eq!(teams[0].composition.max_selres, Some(2));
+        assert_eq!(teams[0].composition.min_tar, Some(1));
+        assert_eq!(teams[0].required_positions.len(), 3);
+
+        let error = parse(&["Det,PC,1,1,,,,,,,,", "Det,CDI,1,2,,,,,,,,"]).unwrap_err();
+        assert_eq!(error.to_string(), "Conflicting Priority for Det: 1 and 2");
+        let error = parse(&["Det,PC,1,,,,,1,,,,", "Det,CDI,1,,,,,0,,,,"]).unwrap_err();
+        assert_eq!(error.to_string(), "Conflicting Max AW for Det: 1 and 0");
+    }
+}
@@ -5,10 +5,8 @@ use crate::components::ProgressBar;
 use crate::utilities::config::PAGES;
 use crate::utilities::AppState;
 
-use std::rc::Rc;
-
 #[cfg(target_arch = "wasm32")]
-use {wasm_bindgen::JsCast, web_sys};
+use {std::rc::Rc, wasm_bindgen::JsCast, web_sys};
 
 #[component]
 pub fn Navbar() -> Element {
@@ -236,6 +234,12 @@ pub fn Navbar() -> Element {
 
                                                                                 // Restore persistent locks
                                                                                 current_state.persistent_locks = save_state.locks_to_hashmap();
+                                                                                current_state.exclusions = save_state.exclusions.clone();
+                                                                                current_state.pairings = save_state.pairings.clone();
+                                                                                current_state.pending_qualifications = save_state.pending_qualifications.clone();
+                                                                                current_state.cost_policy = save_state.cost_policy.clone();
+                                                                                // re-solving the saved roster should keep the saved plan
+                                                                                current_state.baseline = save_state.plan.clone();
 
                                                                                 state_clone.set(current_state);
 
@@ -1,4 +1,5 @@
 // Standard library imports
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -10,15 +11,25 @@ use {wasm_bindgen, web_sys};
 
 // Local crate imports - engine
 use crate::engine::{
-    assignment::{AssignmentLock, FlowAssignment},
+    assignment::{AssignmentExclusion, AssignmentLock},
     builder::{
-        build_assignment_plan, generate_assignments, generate_assignments_from_processed_data,
-        AssignmentResult,
+        add_pending_qualifications, build_assignment_plan, build_roster, build_teams,
+        generate_alternative_plans, AlternativePlan, AssignmentResult, SolverInputs,
+        WarmStartSolver,
     },
-    person::Person,
+    cost_policy::CostPolicy,
+    pairing::PairingConstraint,
+    person::{DutyStatus, PendingQualification, Person},
+    plan_diff::{plan_baseline, PlanDiff},
+    projection::{project_manning, PROJECTION_MONTHS},
+    scenario::{run_scenario, ScenarioComparison, ScenarioEdit},
     team::{Position, Team},
+    training::recommend_training,
 };
 
+/// How many ranked plans "Find Alternative Plans" asks the solver for
+const ALTERNATIVE_PLAN_COUNT: usize = 5;
+
 // Type aliases to reduce complexity in function signatures
 pub type SelectionChangeHandler = Callback<((String, Option<String>, Option<Position>), bool)>;
 pub type PersonHoverHandler = Callback<(Person, Option<String>, (f64, f64))>;
@@ -30,17 +41,28 @@ pub type AssignmentSelection = Vec<(String, Option<String>, Option<Position>)>;
 use crate::{
     components::{
         AnalysisDateBar, AssignmentStats, InteractionAction, InteractionBar, InteractionMode,
-        PlayerCard, RolePopup, TeamCard, UnassignedTable,
+        ManningProjection, PairingRules, PendingQualifications, PlanSummary, PlanSwitcher,
+        PlayerCard, RolePopup, ScenarioSandbox, ShadowPriceTable, SolverDiagnostics, TeamCard,
+        TrainingPlan, UnassignedTable,
     },
-    utilities::{AppState, SaveState},
+    utilities::{parse_pending_qualifications, AppState, SaveState},
 };
 
+/// Which view of the results is showing
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ResultsTab {
+    Assignments,
+    Projection,
+    Scenario,
+}
+
 // Context for shared assignment UI state
 #[derive(Clone)]
 pub struct AssignmentUIContext {
     pub interaction_mode: Signal<InteractionMode>,
     pub selected_assignments: Signal<AssignmentSelection>,
     pub people: ReadOnlySignal<Rc<Vec<Person>>>, // for eligibility calculations
+    pub exclusions: ReadOnlySignal<Vec<AssignmentExclusion>>,
 }
 
 #[component]
@@ -58,12 +80,23 @@ pub fn Results() -> Element {
     let mut app_state = use_context::<Signal<AppState>>();
 
     // Raw data storage
-    let mut raw_data = use_signal(|| None::<(Vec<FlowAssignment>, Rc<Vec<Person>>, Rc<Vec<Team>>)>);
+    let mut raw_data = use_signal(|| None::<Rc<AssignmentResult>>);
+
+    // Solver kept between runs - not a signal, so repairing it never re-triggers the effect
+    let warm_solver = use_hook(|| Rc::new(RefCell::new(None::<(SolverInputs, WarmStartSolver)>)));
+
+    // Ranked alternatives for the current inputs - plan 0 is the solver's best
+    let mut alternatives = use_signal(Vec::<AlternativePlan>::new);
+    // Whether to rank qualifications by what one more holder would gain
+    let shadow_prices_open = use_signal(|| false);
+    let mut selected_plan = use_signal(|| 0_usize);
 
     // Context state - these will be provided to child components
     let mut interaction_mode = use_signal(|| InteractionMode::ViewOnly);
+    let mut active_tab = use_signal(|| ResultsTab::Assignments);
     let mut selected_assignments =
         use_signal(Vec::<(String, Option<String>, Option<Position>)>::new);
+    let prices_solver = warm_solver.clone();
     use_effect(move || {
         // Read app state to trigger recomputation on changes
         let app_state_val = app_state();
@@ -93,10 +126,11 @@ pub fn Results() -> Element {
             if let Some(people) = people_data {
                 filtered_persistent_locks.retain(|_key, person_name| {
                     people.iter().any(|person| {
-                        person.name == *person_name && match person.prd {
-                            Some(prd_date) => prd_date > current_date,
-                            None => true,
-                        }
+                        person.name == *person_name
+                            && match person.prd {
+                                Some(prd_date) => prd_date > current_date,
+                                None => true,
+                            }
                     })
                 });
 
@@ -109,154 +143,141 @@ pub fn Results() -> Element {
             }
         }
 
-        let all_locks = if !filtered_persistent_locks.is_empty() {
-            let assignment_locks = filtered_persistent_locks
-                .iter()
-                .map(|((team_name, position), person)| AssignmentLock {
-                    person_name: person.clone(),
-                    team_name: Some(team_name.clone()),
-                    position: Some(position.clone()),
-                })
-                .collect();
-            Some(assignment_locks)
-        } else {
-            None
-        };
+        let all_locks = assignment_locks(&filtered_persistent_locks);
 
         // Generate fresh assignments
         let app_state_read = &app_state_val;
 
-        // Check if we have data loaded from save state vs file uploads
-        let has_fltmps = app_state_read
-            .files
-            .get("FLTMPS")
-            .and_then(|config| config.parsed_data.as_ref())
-            .is_some();
-        let has_requirements = app_state_read
-            .files
-            .get("Requirements")
-            .and_then(|config| config.parsed_data.as_ref())
-            .is_some();
-        let has_asm = app_state_read
-            .files
-            .get("ASM")
-            .and_then(|config| config.parsed_data.as_ref())
-            .is_some();
-
-        let data = if has_requirements && has_asm && !has_fltmps {
-            // This looks like save state data - use processed data directly
-            let teams = app_state_read
-                .files
-                .get("Requirements")
-                .and_then(|config| config.parsed_data.as_ref())
-                .and_then(|data| match data {
-                    crate::utilities::config::ParsedData::Requirements(teams) => {
-                        Some(teams.as_ref().clone())
-                    }
-                    _ => None,
-                });
-            let people = app_state_read
-                .files
-                .get("ASM")
-                .and_then(|config| config.parsed_data.as_ref())
-                .and_then(|data| match data {
-                    crate::utilities::config::ParsedData::Personnel(people) => {
-                        Some(people.as_ref().clone())
-                    }
-                    _ => None,
-                });
-
-            match (people, teams) {
-                (Some(people), Some(teams)) => {
-                    #[cfg(target_arch = "wasm32")]
-                    let start_time = web_sys::js_sys::Date::now();
-                    
-                    let result = generate_assignments_from_processed_data(
-                        selected_date(),
-                        all_locks,
-                        people,
-                        teams,
-                    );
-                    
-                    #[cfg(target_arch = "wasm32")]
-                    {
-                        let duration = web_sys::js_sys::Date::now() - start_time;
-                        web_sys::console::log_1(&format!(
-                            "🚀 Assignment generation (processed data): {:.2}ms",
-                            duration
-                        ).into());
-                    }
-                    
-                    match result {
-                        Ok(AssignmentResult {
-                            flow_assignments,
-                            people,
-                            teams,
-                        }) => Some((flow_assignments, people, teams)),
-                        Err(e) => {
-                            eprintln!("Error generating assignments from processed data: {:?}", e);
-                            None
-                        }
-                    }
+        // the roster and network are rebuilt only when the files, pending quals or cost
+        // policy change; locks, exclusions, pairings and the date reuse the last flow
+        let inputs = SolverInputs::of(app_state_read);
+        let mut warm = warm_solver.borrow_mut();
+        if !warm
+            .as_ref()
+            .is_some_and(|(built_for, _)| *built_for == inputs)
+        {
+            *warm = match solver_roster(app_state_read) {
+                Ok((people, teams)) => Some((
+                    inputs,
+                    WarmStartSolver::new(people, teams, &app_state_read.cost_policy),
+                )),
+                Err(e) => {
+                    eprintln!("Error preparing assignment inputs: {:?}", e);
+                    None
                 }
-                _ => None,
-            }
-        } else {
-            // Use normal file upload flow
-            #[cfg(target_arch = "wasm32")]
-            let start_time = web_sys::js_sys::Date::now();
-            
-            let result = generate_assignments(selected_date(), all_locks, &app_state_read);
-            
-            #[cfg(target_arch = "wasm32")]
-            {
-                let duration = web_sys::js_sys::Date::now() - start_time;
-                web_sys::console::log_1(&format!(
-                    "🚀 Assignment generation (file upload): {:.2}ms",
-                    duration
-                ).into());
-            }
-            
-            match result {
-                Ok(AssignmentResult {
-                    flow_assignments,
-                    people,
-                    teams,
-                }) => Some((flow_assignments, people, teams)),
+            };
+        }
+
+        let data = warm.as_mut().and_then(|(_, warm)| {
+            warm.set_baseline(app_state_read.baseline.clone());
+            warm.set_backend(app_state_read.backend, app_state_read.compare_backends);
+            match warm.solve(
+                current_date,
+                all_locks,
+                &app_state_read.exclusions,
+                &app_state_read.pairings,
+            ) {
+                Ok(result) => Some(Rc::new(result)),
                 Err(e) => {
                     eprintln!("Error generating assignments: {:?}", e);
                     None
                 }
             }
-        };
+        });
+        // the shadow price memo borrows the solver once raw_data changes
+        drop(warm);
 
         raw_data.set(data);
+        // alternatives were ranked for the old inputs
+        alternatives.set(vec![]);
+        selected_plan.set(0);
     });
 
     // Build assignment plan using a memoized signal to avoid ownership issues
     let assignments = use_memo(move || {
+        if let Some(alternative) = alternatives.read().get(selected_plan()) {
+            return build_assignment_plan(&alternative.result).ok();
+        }
+
         let raw_data_current = raw_data.read();
-        let Some((ref flow_assignments, ref people, ref teams)) = *raw_data_current else {
-            return None;
+        let result = raw_data_current.as_ref()?;
+
+        build_assignment_plan(result).ok() // TODO: better error handling
+    });
+
+    // Only ranked while the panel is open: every qualification costs a re-solve
+    let shadow_prices = use_memo(move || {
+        if !shadow_prices_open() {
+            return vec![];
+        }
+        let Some(result) = raw_data() else {
+            return vec![];
         };
+        // the solver still holds the flow it handed back as raw_data
+        prices_solver
+            .borrow_mut()
+            .as_mut()
+            .map(|(_, warm)| warm.shadow_prices(result.analysis_date))
+            .unwrap_or_default()
+    });
 
-        build_assignment_plan(people, teams, flow_assignments).ok() // TODO: better error handling
+    let cost_policy = use_memo(move || app_state().cost_policy);
+
+    let solve_stats = use_memo(move || {
+        if let Some(alternative) = alternatives.read().get(selected_plan()) {
+            return Some(alternative.result.solve_stats);
+        }
+        raw_data.read().as_ref().map(|result| result.solve_stats)
+    });
+
+    let plan_summaries = use_memo(move || {
+        alternatives
+            .read()
+            .iter()
+            .filter_map(|alternative| {
+                let plan = build_assignment_plan(&alternative.result).ok()?;
+                Some(PlanSummary {
+                    positions_filled: plan.assignments.len(),
+                    selres_used: plan
+                        .assignments
+                        .iter()
+                        .filter(|a| a.person.duty_status == DutyStatus::Selres)
+                        .count(),
+                    cost: alternative.cost,
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // What the selected alternative changes relative to the best plan
+    let plan_diff = use_memo(move || {
+        let alternatives_current = alternatives.read();
+        let Some(best) = alternatives_current.first() else {
+            return PlanDiff::default();
+        };
+        match (build_assignment_plan(&best.result), assignments()) {
+            (Ok(best_plan), Some(selected)) => PlanDiff::between(&best_plan, &selected),
+            _ => PlanDiff::default(),
+        }
     });
 
     // Create the people signal for context
     let people_signal = use_memo(move || {
         let raw_data_current = raw_data.read();
         match raw_data_current.as_ref() {
-            Some((_flow_assignments, people, _teams)) => people.clone(),
+            Some(result) => result.people.clone(),
             None => Rc::new(Vec::new()),
         }
     });
 
+    let exclusions_signal = use_memo(move || app_state().exclusions);
+
     // Create the context
     let ui_context = AssignmentUIContext {
         interaction_mode,
         selected_assignments,
         people: people_signal.into(),
+        exclusions: exclusions_signal.into(),
     };
 
     // Check if assignments were generated successfully
@@ -294,18 +315,19 @@ pub fn Results() -> Element {
             .collect::<Vec<_>>()
     });
 
-    // Memoize teams sorted by assignment count
+    // Memoize teams sorted by priority, then assignment count
     let teams_sorted = use_memo(move || {
         let assignments_current = assignments.read();
         let Some(ref assignments_plan) = *assignments_current else {
             return Vec::new();
         };
         let raw_data_current = raw_data.read();
-        let Some((_flow_assignments, _people, teams)) = raw_data_current.as_ref() else {
+        let Some(result) = raw_data_current.as_ref() else {
             return Vec::new();
         };
 
-        teams
+        result
+            .teams
             .iter()
             .map(|team| {
                 let assignment_count = assignments_plan
@@ -315,7 +337,7 @@ pub fn Results() -> Element {
                     .count();
                 (team, assignment_count)
             })
-            .sorted_by_key(|(_, count)| *count)
+            .sorted_by_key(|(team, count)| (team.priority.unwrap_or(u32::MAX), *count))
             .map(|(team, _)| team.clone())
             .collect::<Vec<_>>()
     });
@@ -385,6 +407,228 @@ pub fn Results() -> Element {
         }
     });
 
+    let on_exclude = Callback::new(move |exclusion: AssignmentExclusion| {
+        app_state.with_mut(|state| {
+            // a lock would keep the person right where the exclusion forbids
+            state
+                .persistent_locks
+                .retain(|(team_name, position), person_name| {
+                    !exclusion.excludes(person_name, team_name, &position.qualification)
+                });
+            if !state.exclusions.contains(&exclusion) {
+                state.exclusions.push(exclusion);
+            }
+        });
+    });
+
+    let on_remove_exclusion = Callback::new(move |exclusion: AssignmentExclusion| {
+        app_state.with_mut(|state| state.exclusions.retain(|e| *e != exclusion));
+    });
+
+    let on_add_pairing = Callback::new(move |pairing: PairingConstraint| {
+        app_state.with_mut(|state| {
+            if !state.pairings.contains(&pairing) {
+                state.pairings.push(pairing);
+            }
+        });
+    });
+
+    let on_remove_pairing = Callback::new(move |pairing: PairingConstraint| {
+        app_state.with_mut(|state| state.pairings.retain(|p| *p != pairing));
+    });
+
+    let on_add_pending = Callback::new(move |pending: PendingQualification| {
+        app_state.with_mut(|state| {
+            if !state.pending_qualifications.contains(&pending) {
+                state.pending_qualifications.push(pending);
+            }
+        });
+    });
+
+    let on_remove_pending = Callback::new(move |pending: PendingQualification| {
+        app_state.with_mut(|state| state.pending_qualifications.retain(|p| *p != pending));
+    });
+
+    let on_import_pending = Callback::new(move |csv_content: String| {
+        match parse_pending_qualifications(csv_content.as_bytes()) {
+            Ok(imported) => app_state.with_mut(|state| {
+                for pending in imported {
+                    if !state.pending_qualifications.contains(&pending) {
+                        state.pending_qualifications.push(pending);
+                    }
+                }
+            }),
+            Err(e) => eprintln!("Error importing pending qualifications: {:?}", e),
+        }
+    });
+
+    // Every qualification a team asks for, for the pending qualification picker
+    let required_qualifications = use_memo(move || {
+        raw_data()
+            .map(|result| {
+                result
+                    .teams
+                    .iter()
+                    .flat_map(|team| team.required_positions.iter())
+                    .map(|position| position.qualification.clone())
+                    .sorted()
+                    .dedup()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    });
+
+    // Who to train in what to close the gaps in the plan on screen
+    let training_recommendations = use_memo(move || {
+        let state = app_state();
+        assignments()
+            .map(|plan| {
+                recommend_training(
+                    &plan,
+                    &state.exclusions,
+                    &state.cost_policy,
+                    selected_date(),
+                )
+            })
+            .unwrap_or_default()
+    });
+
+    let on_export_training = Callback::new(move |_| {
+        let recommendations = training_recommendations();
+        #[cfg(target_arch = "wasm32")]
+        {
+            let filename = format!("roboamo-training-plan-{}.csv", selected_date());
+            if let Err(e) =
+                crate::utilities::export::download_training_plan(&recommendations, &filename)
+            {
+                web_sys::console::error_1(&wasm_bindgen::JsValue::from_str(&format!(
+                    "Failed to download training plan: {}",
+                    e
+                )));
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // For non-WASM platforms, just log the CSV
+            match crate::engine::training::training_plan_csv(&recommendations) {
+                Ok(csv) => println!("Training Plan CSV:\n{}", csv),
+                Err(e) => eprintln!("Failed to export training plan: {}", e),
+            }
+        }
+    });
+
+    let unsatisfied_pairings = use_memo(move || {
+        assignments()
+            .map(|plan| plan.unsatisfied_pairings)
+            .unwrap_or_default()
+    });
+
+    // Only solved while the projection tab is open; twelve re-solves are not free
+    let projection = use_memo(move || {
+        if active_tab() != ResultsTab::Projection {
+            return vec![];
+        }
+        let Some(result) = raw_data() else {
+            return vec![];
+        };
+        let app_state_read = app_state.read();
+        let people = result
+            .people
+            .iter()
+            .chain(result.departed_people.iter())
+            .cloned()
+            .collect();
+        let mut solver = WarmStartSolver::new(
+            people,
+            result.teams.as_ref().clone(),
+            &app_state_read.cost_policy,
+        );
+        solver.set_baseline(app_state_read.baseline.clone());
+        solver.set_backend(app_state_read.backend, false);
+        project_manning(
+            &mut solver,
+            selected_date(),
+            PROJECTION_MONTHS,
+            &assignment_locks(&app_state_read.persistent_locks).unwrap_or_default(),
+            &app_state_read.exclusions,
+            &app_state_read.pairings,
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("Error projecting manning: {:?}", e);
+            vec![]
+        })
+    });
+
+    // Only solved while the what-if tab is open, and only once there is an edit
+    let scenario_outcome = use_memo(move || -> Result<Option<ScenarioComparison>, String> {
+        if active_tab() != ResultsTab::Scenario {
+            return Ok(None);
+        }
+        let app_state_read = app_state.read();
+        if app_state_read.scenario.is_empty() {
+            return Ok(None);
+        }
+        let Some(result) = raw_data() else {
+            return Ok(None);
+        };
+        // against the solved plan rather than a picked alternative, which the
+        // scenario would not be solved like
+        let plan = build_assignment_plan(&result).map_err(|e| e.to_string())?;
+        let scenario_plan = run_scenario(&result, &app_state_read.scenario)
+            .and_then(|scenario| build_assignment_plan(&scenario))
+            .map_err(|e| e.to_string())?;
+        Ok(Some(ScenarioComparison::between(&plan, &scenario_plan)))
+    });
+
+    let on_add_scenario_edit = Callback::new(move |edit: ScenarioEdit| {
+        app_state.with_mut(|state| state.scenario.push(edit));
+    });
+
+    let on_remove_scenario_edit = Callback::new(move |index: usize| {
+        app_state.with_mut(|state| {
+            if index < state.scenario.len() {
+                state.scenario.remove(index);
+            }
+        });
+    });
+
+    let on_clear_scenario = Callback::new(move |_| {
+        app_state.with_mut(|state| state.scenario.clear());
+    });
+
+    // How far the plan on screen strays from the baseline; `None` without one
+    let baseline_moves = use_memo(move || {
+        let baseline = app_state.read().baseline.clone();
+        if baseline.is_empty() {
+            return None;
+        }
+        let plan = assignments()?;
+        Some(PlanDiff::from_baseline(&baseline, &plan).moves.len())
+    });
+
+    let on_find_alternatives = move |_| {
+        let Some(result) = raw_data() else {
+            return;
+        };
+        let app_state_read = app_state.read();
+
+        match generate_alternative_plans(
+            &result,
+            selected_date(),
+            assignment_locks(&app_state_read.persistent_locks),
+            &app_state_read.cost_policy,
+            app_state_read.backend,
+            ALTERNATIVE_PLAN_COUNT,
+        ) {
+            Ok(plans) => {
+                alternatives.set(plans);
+                selected_plan.set(0);
+            }
+            Err(e) => eprintln!("Error generating alternative plans: {:?}", e),
+        }
+    };
+
     // Handle InteractionBar actions
     let on_interaction_action = move |action: InteractionAction| {
         match action {
@@ -404,10 +648,32 @@ pub fn Results() -> Element {
                     state.persistent_locks.clear();
                 });
             }
+            InteractionAction::SetBaseline => {
+                if let Some(plan) = assignments() {
+                    app_state.with_mut(|state| state.baseline = plan_baseline(&plan));
+                }
+            }
+            InteractionAction::ClearBaseline => {
+                app_state.with_mut(|state| state.baseline.clear());
+            }
+            InteractionAction::SetBackend(backend) => {
+                app_state.with_mut(|state| state.backend = backend);
+            }
+            InteractionAction::SetCompareBackends(compare) => {
+                app_state.with_mut(|state| state.compare_backends = compare);
+            }
+            InteractionAction::LoadCostPolicy(json_content) => {
+                match CostPolicy::from_json(&json_content) {
+                    Ok(cost_policy) => app_state.with_mut(|state| {
+                        state.cost_policy = cost_policy;
+                    }),
+                    Err(e) => eprintln!("Failed to load cost policy: {:?}", e),
+                }
+            }
             InteractionAction::SaveState => {
                 // Extract current data for export
                 let current_raw_data = raw_data.read();
-                if let Some((_, ref people, ref teams)) = *current_raw_data {
+                if let Some(ref result) = *current_raw_data {
                     // Extract qual_defs from app state
                     let app_state_read = app_state.read();
                     let qual_defs = app_state_read
@@ -423,13 +689,22 @@ pub fn Results() -> Element {
                         .cloned()
                         .unwrap_or_default();
 
-                    let save_state = SaveState::new(
-                        selected_date(),
-                        people,
-                        teams,
-                        &qual_defs,
-                        &app_state_read.persistent_locks,
-                    );
+                    let save_state = SaveState {
+                        pairings: app_state_read.pairings.clone(),
+                        pending_qualifications: app_state_read.pending_qualifications.clone(),
+                        plan: assignments()
+                            .map(|plan| plan_baseline(&plan))
+                            .unwrap_or_default(),
+                        ..SaveState::new(
+                            selected_date(),
+                            &result.people,
+                            &result.teams,
+                            &qual_defs,
+                            &app_state_read.persistent_locks,
+                            &app_state_read.exclusions,
+                            &app_state_read.cost_policy,
+                        )
+                    };
 
                     // Trigger download with timestamp
                     #[cfg(target_arch = "wasm32")]
@@ -467,11 +742,32 @@ pub fn Results() -> Element {
         AssignmentStats {
             assignments_signal: assignments,
         }
+        SolverDiagnostics {
+            stats_signal: solve_stats,
+        }
+        // Only filled when comparing backends, and only when they disagree
+        for mismatch in raw_data().map(|result| result.backend_mismatches.clone()).unwrap_or_default() {
+            div {
+                class: "m-1 px-3 py-2 bg-red-50 border border-red-300 text-red-800 rounded text-sm",
+                "⚠️ Solver backends disagree: {mismatch}"
+            }
+        }
+        // Floors the flow could not route; the plan is solved without them
+        for floor in raw_data().map(|result| result.dropped_floors.clone()).unwrap_or_default() {
+            div {
+                class: "m-1 px-3 py-2 bg-amber-50 border border-amber-300 text-amber-800 rounded text-sm",
+                title: "{floor.error}",
+                "⚠️ Minimum staffing left out: {floor}"
+            }
+        }
         // Interaction toolbar
         InteractionBar {
             interaction_mode_signal: interaction_mode,
             selected_count_signal: use_memo(move || selected_assignments().len()),
             persistent_locks_count_signal: use_memo(move || app_state().persistent_locks.len()),
+            baseline_moves_signal: baseline_moves,
+            backend_signal: use_memo(move || app_state().backend),
+            compare_backends_signal: use_memo(move || app_state().compare_backends),
             on_action: on_interaction_action,
         }
         // Analysis date selector
@@ -479,45 +775,123 @@ pub fn Results() -> Element {
             selected_date_signal: selected_date,
         }
 
-        // Assignments by Team
         div {
-            class: "section-card",
-            h2 {
-                class: "section-title",
-                "Assignments by Team"
+            class: "flex gap-2 mb-4",
+            for (tab, label) in [(ResultsTab::Assignments, "Assignments"), (ResultsTab::Projection, "12-Month Projection"), (ResultsTab::Scenario, "What-If")] {
+                button {
+                    key: "{label}",
+                    class: if active_tab() == tab {
+                        "px-4 py-2 bg-indigo-600 text-white rounded text-sm font-medium"
+                    } else {
+                        "px-4 py-2 bg-gray-100 text-gray-700 rounded text-sm font-medium hover:bg-gray-200"
+                    },
+                    onclick: move |_| active_tab.set(tab),
+                    "{label}"
+                }
             }
+        }
+
+        if active_tab() == ResultsTab::Projection {
+            ManningProjection {
+                points_signal: projection,
+            }
+        } else if active_tab() == ResultsTab::Scenario {
+            ScenarioSandbox {
+                edits: app_state().scenario,
+                teams: raw_data().map(|result| result.teams.as_ref().clone()).unwrap_or_default(),
+                outcome_signal: scenario_outcome,
+                on_add: on_add_scenario_edit,
+                on_remove: on_remove_scenario_edit,
+                on_clear: on_clear_scenario,
+            }
+        } else {
+            // Ranked alternatives and what differs between them
+            PlanSwitcher {
+                plan_summaries_signal: plan_summaries,
+                selected_plan_signal: selected_plan,
+                plan_diff_signal: plan_diff,
+                on_find_alternatives: on_find_alternatives,
+            }
+
+            // Who must share a team and who must not
+            PairingRules {
+                pairings: app_state().pairings,
+                unsatisfied_signal: unsatisfied_pairings,
+                on_add: on_add_pairing,
+                on_remove: on_remove_pairing,
+            }
+
+            // Qualifications counted from when training finishes
+            PendingQualifications {
+                pending: app_state().pending_qualifications,
+                qualifications: required_qualifications(),
+                on_add: on_add_pending,
+                on_remove: on_remove_pending,
+                on_import: on_import_pending,
+            }
+
+            // Which qualification is the binding constraint
+            ShadowPriceTable {
+                prices_signal: shadow_prices,
+                open_signal: shadow_prices_open,
+            }
+
+            // Who to qualify in what
+            TrainingPlan {
+                recommendations_signal: training_recommendations,
+                on_export: on_export_training,
+            }
+
+            // Assignments by Team
             div {
-                class: "teams-grid",
-                for team in teams_sorted() {
-                    TeamCard {
-                        team: team.clone(),
-                        assignments_signal: assignments,
-                        analysis_date_signal: selected_date,
-                        on_selection_change: on_selection_change,
-                        on_person_hover: on_person_hover,
-                        on_person_leave: on_person_leave,
-                        on_role_popup_open: on_role_popup_open,
+                class: "section-card",
+                h2 {
+                    class: "section-title",
+                    "Assignments by Team"
+                }
+                div {
+                    class: "teams-grid",
+                    for team in teams_sorted() {
+                        TeamCard {
+                            team: team.clone(),
+                            assignments_signal: assignments,
+                            analysis_date_signal: selected_date,
+                            on_selection_change: on_selection_change,
+                            on_person_hover: on_person_hover,
+                            on_person_leave: on_person_leave,
+                            on_role_popup_open: on_role_popup_open,
+                        }
                     }
                 }
             }
-        }
 
-        // Unassigned Personnel
-        if !unassigned_people().is_empty() {
-            UnassignedTable {
-                assignments_signal: assignments,
-                analysis_date_signal: selected_date,
-                on_selection_change: on_selection_change,
-                on_person_hover: on_person_hover,
-                on_person_leave: on_person_leave,
+            // Unassigned Personnel
+            if !unassigned_people().is_empty() || !exclusions_signal().is_empty() {
+                UnassignedTable {
+                    assignments_signal: assignments,
+                    analysis_date_signal: selected_date,
+                    team_names: teams_sorted().iter().map(|team| team.name.clone()).collect::<Vec<_>>(),
+                    on_exclude: on_exclude,
+                    on_remove_exclusion: on_remove_exclusion,
+                    on_selection_change: on_selection_change,
+                    on_person_hover: on_person_hover,
+                    on_person_leave: on_person_leave,
+                }
             }
         }
 
         if let Some((person, assignment)) = hovered_person() {
             PlayerCard {
+                cost: assignments().and_then(|plan| {
+                    plan.assignments
+                        .iter()
+                        .find(|a| a.person.name == person.name)
+                        .map(|a| a.cost)
+                }),
                 person,
                 current_assignment: assignment,
                 position: mouse_position(),
+                analysis_date: selected_date(),
             }
         }
 
@@ -528,8 +902,11 @@ pub fn Results() -> Element {
                 team_name,
                 current_person,
                 assignments_signal: assignments,
+                analysis_date_signal: selected_date,
+                cost_policy_signal: cost_policy,
                 popup_position,
                 on_swap: on_role_swap,
+                on_exclude: on_exclude,
                 on_close: on_role_popup_close,
             }
         }
@@ -539,6 +916,79 @@ pub fn Results() -> Element {
 
 // Helper functions for interaction actions
 
+/// People and teams for the solver, from a save state or from the uploaded files
+fn solver_roster(app_state: &AppState) -> anyhow::Result<(Vec<Person>, Vec<Team>)> {
+    // Check if we have data loaded from save state vs file uploads
+    let has_fltmps = app_state
+        .files
+        .get("FLTMPS")
+        .and_then(|config| config.parsed_data.as_ref())
+        .is_some();
+    let has_requirements = app_state
+        .files
+        .get("Requirements")
+        .and_then(|config| config.parsed_data.as_ref())
+        .is_some();
+    let has_asm = app_state
+        .files
+        .get("ASM")
+        .and_then(|config| config.parsed_data.as_ref())
+        .is_some();
+
+    if has_requirements && has_asm && !has_fltmps {
+        // This looks like save state data - use processed data directly
+        let teams = app_state
+            .files
+            .get("Requirements")
+            .and_then(|config| config.parsed_data.as_ref())
+            .and_then(|data| match data {
+                crate::utilities::config::ParsedData::Requirements(teams) => {
+                    Some(teams.as_ref().clone())
+                }
+                _ => None,
+            });
+        let people = app_state
+            .files
+            .get("ASM")
+            .and_then(|config| config.parsed_data.as_ref())
+            .and_then(|data| match data {
+                crate::utilities::config::ParsedData::Personnel(people) => {
+                    Some(people.as_ref().clone())
+                }
+                _ => None,
+            });
+
+        match (people, teams) {
+            (Some(mut people), Some(teams)) => {
+                add_pending_qualifications(&mut people, &app_state.pending_qualifications);
+                Ok((people, teams))
+            }
+            _ => Err(anyhow::anyhow!("Save state is missing people or teams")),
+        }
+    } else {
+        // Use normal file upload flow
+        build_roster(app_state).and_then(|people| Ok((people, build_teams(app_state)?)))
+    }
+}
+
+fn assignment_locks(
+    persistent_locks: &HashMap<(String, Position), String>,
+) -> Option<Vec<AssignmentLock>> {
+    if persistent_locks.is_empty() {
+        return None;
+    }
+    Some(
+        persistent_locks
+            .iter()
+            .map(|((team_name, position), person)| AssignmentLock {
+                person_name: person.clone(),
+                team_name: Some(team_name.clone()),
+                position: Some(position.clone()),
+            })
+            .collect(),
+    )
+}
+
 fn execute_lock_action(
     selections: &[(String, Option<String>, Option<Position>)],
     persistent_locks: &mut HashMap<(String, Position), String>,
@@ -550,7 +1000,7 @@ fn execute_lock_action(
     }
 }
 
-fn should_add_selection(interaction_mode: InteractionMode, current_count: usize) -> bool {
+fn should_add_selection(interaction_mode: InteractionMode) -> bool {
     match interaction_mode {
         InteractionMode::Lock => true,
         InteractionMode::ViewOnly => false,
@@ -565,7 +1015,7 @@ fn toggle_assignment_selection(
 ) -> Vec<(String, Option<String>, Option<Position>)> {
     if is_checked {
         let mut updated = current_selections;
-        if should_add_selection(interaction_mode, updated.len()) {
+        if should_add_selection(interaction_mode) {
             updated.push(assignment_id);
         }
         updated
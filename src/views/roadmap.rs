@@ -72,7 +72,7 @@ pub fn ProductRoadmap() -> Element {
                                 icon: "📈",
                                 title: "12-Month Manning Projection",
                                 description: "Strategic time-series visualization showing unfilled positions and SELRES requirements as TAR personnel rotate out",
-                                status: "planned"
+                                status: "complete"
                             }
 
                             // STRATEGIC FEATURES
@@ -86,7 +86,7 @@ pub fn ProductRoadmap() -> Element {
                                 icon: "🦺",
                                 title: "Weighted Team Priorities",
                                 description: "Prioritize critical teams over others (e.g. Home Guard takes precedence over Det assignments)",
-                                status: "planned"
+                                status: "complete"
                             }
                             FeatureCard {
                                 icon: "⚙️",
@@ -130,12 +130,6 @@ pub fn ProductRoadmap() -> Element {
                                 description: "Need definitive rules for standard qualification definitions (100 CDI, 040 SUP, etc.)",
                                 workaround: "Get wing validated standard definitions"
                             }
-                            IssueCard {
-                                severity: "medium",
-                                title: "Expiration Dates",
-                                description: "System doesn't currently track qualification expiration dates",
-                                workaround: "Ensure all uploaded data reflects current qualifications only"
-                            }
                             IssueCard {
                                 severity: "medium",
                                 title: "File Upload Error Recovery",